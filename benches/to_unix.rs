@@ -1,11 +1,14 @@
-use std::{hint::black_box, sync::LazyLock};
+use std::{hint::black_box, sync::OnceLock};
 
 use criterion::{criterion_group, criterion_main, Criterion};
 use eolify::{Normalize, CRLF, LF};
 use newline_normalizer::ToUnixNewlines;
 use regex::Regex;
 
-static UNIX_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\r\n?").unwrap());
+fn unix_regex() -> &'static Regex {
+    static UNIX_REGEX: OnceLock<Regex> = OnceLock::new();
+    UNIX_REGEX.get_or_init(|| Regex::new(r"\r\n?").unwrap())
+}
 
 fn bench_to_unix_newlines(c: &mut Criterion) {
     let input = "
@@ -18,7 +21,7 @@ fn bench_to_unix_newlines(c: &mut Criterion) {
     "
     .to_string();
     let pre_normalized_input = LF::normalize_str(&input);
-    assert_eq!(pre_normalized_input, UNIX_REGEX.replace_all(&input, "\n"));
+    assert_eq!(pre_normalized_input, unix_regex().replace_all(&input, "\n"));
     assert_eq!(pre_normalized_input, newline_converter::dos2unix(&input));
     assert_eq!(pre_normalized_input, input.to_unix_newlines());
 
@@ -27,7 +30,7 @@ fn bench_to_unix_newlines(c: &mut Criterion) {
     let pre_normalized_large_input = LF::normalize_str(&large_input);
     assert_eq!(
         pre_normalized_large_input,
-        UNIX_REGEX.replace_all(&large_input, "\n")
+        unix_regex().replace_all(&large_input, "\n")
     );
     assert_eq!(
         pre_normalized_large_input,
@@ -36,19 +39,19 @@ fn bench_to_unix_newlines(c: &mut Criterion) {
     assert_eq!(pre_normalized_large_input, large_input.to_unix_newlines());
 
     c.bench_function("regex", |b| {
-        b.iter(|| UNIX_REGEX.replace_all(black_box(&input), "\n"))
+        b.iter(|| unix_regex().replace_all(black_box(&input), "\n"))
     });
 
     c.bench_function("regex with pre-normalized text", |b| {
-        b.iter(|| UNIX_REGEX.replace_all(black_box(&pre_normalized_input), "\n"))
+        b.iter(|| unix_regex().replace_all(black_box(&pre_normalized_input), "\n"))
     });
 
     c.bench_function("regex with large ASCII text", |b| {
-        b.iter(|| UNIX_REGEX.replace_all(black_box(&large_input), "\n"))
+        b.iter(|| unix_regex().replace_all(black_box(&large_input), "\n"))
     });
 
     c.bench_function("regex with large pre-normalized ASCII text", |b| {
-        b.iter(|| UNIX_REGEX.replace_all(black_box(&pre_normalized_large_input), "\n"))
+        b.iter(|| unix_regex().replace_all(black_box(&pre_normalized_large_input), "\n"))
     });
 
     c.bench_function("3rd party crate \"newline-converter\": dos2unix()", |b| {
@@ -0,0 +1,117 @@
+//! Support code for the [`assert_eol_eq!`](crate::assert_eol_eq) and
+//! [`assert_eol_normalized!`](crate::assert_eol_normalized) macros.
+
+use std::{fmt::Write, path::Path};
+
+use crate::{types::LF, Normalize};
+
+/// The maximum number of differing lines listed in a failure message.
+const MAX_REPORTED_LINES: usize = 10;
+
+/// Assert that two byte strings are equal, including their line endings.
+///
+/// On failure the differing lines are listed with `\r` and `\n` made visible, so mismatching
+/// line endings are easy to spot.
+///
+/// ```should_panic
+/// eolify::assert_eol_eq!("one\r\ntwo\n", "one\ntwo\n");
+/// ```
+#[macro_export]
+macro_rules! assert_eol_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert::assert_eol_eq(
+            ::core::convert::AsRef::<[u8]>::as_ref(&$left),
+            ::core::convert::AsRef::<[u8]>::as_ref(&$right),
+            stringify!($left),
+            stringify!($right),
+        )
+    };
+}
+
+/// Assert that the file at `path` is normalized to the given format.
+///
+/// On failure the offending lines are listed with `\r` and `\n` made visible.
+///
+/// ```no_run
+/// use eolify::LF;
+///
+/// eolify::assert_eol_normalized!("README.md", LF);
+/// ```
+#[macro_export]
+macro_rules! assert_eol_normalized {
+    ($path:expr, $format:ty $(,)?) => {
+        $crate::assert::assert_eol_normalized::<$format>(
+            ::core::convert::AsRef::<::std::path::Path>::as_ref(&$path),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_eol_eq(left: &[u8], right: &[u8], left_expr: &str, right_expr: &str) {
+    if left != right {
+        panic!(
+            "assertion `{left_expr} == {right_expr}` failed: line endings or content differ\n{}",
+            describe_differences(left, right, "left", "right")
+        );
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_eol_normalized<N: Normalize>(path: &Path) {
+    let content = std::fs::read(path)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {err}", path.display()));
+    let normalized = N::normalize(&content);
+    if content != normalized {
+        panic!(
+            "`{}` is not normalized\n{}",
+            path.display(),
+            describe_differences(&content, &normalized, "actual", "expected")
+        );
+    }
+}
+
+/// List the lines that differ between `left` and `right`, with visible line endings.
+fn describe_differences(left: &[u8], right: &[u8], left_name: &str, right_name: &str) -> String {
+    let mut left_lines = left.split_inclusive(|&b| b == LF);
+    let mut right_lines = right.split_inclusive(|&b| b == LF);
+    let width = left_name.len().max(right_name.len());
+
+    let mut description = String::new();
+    let mut differences = 0;
+    for line_number in 1.. {
+        let (left_line, right_line) = match (left_lines.next(), right_lines.next()) {
+            (None, None) => break,
+            lines => lines,
+        };
+        if left_line == right_line {
+            continue;
+        }
+
+        differences += 1;
+        if differences > MAX_REPORTED_LINES {
+            continue;
+        }
+        let _ = writeln!(description, "line {line_number}:");
+        for (name, line) in [(left_name, left_line), (right_name, right_line)] {
+            let _ = match line {
+                Some(line) => writeln!(
+                    description,
+                    "  {name:>width$}: \"{}\"",
+                    String::from_utf8_lossy(line).escape_debug()
+                ),
+                None => writeln!(description, "  {name:>width$}: <missing>"),
+            };
+        }
+    }
+
+    if differences > MAX_REPORTED_LINES {
+        let _ = writeln!(
+            description,
+            "... and {} more differing lines",
+            differences - MAX_REPORTED_LINES
+        );
+    }
+    description
+}
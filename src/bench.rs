@@ -0,0 +1,147 @@
+//! The `bench` module provides a small harness to measure the throughput of a format's
+//! `normalize_chunk`, using the same input patterns as the crate's own benchmarks.
+//!
+//! This module requires the `bench-util` feature to be enabled.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{helpers::vec_to_uninit_mut, NormalizeChunk};
+
+/// Input patterns used to exercise the different code paths of a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// Pseudo-random bytes (deterministic LCG).
+    Random,
+    /// All LF bytes.
+    AllLf,
+    /// All CR bytes.
+    AllCr,
+    /// Repeating CRLF sequences.
+    Crlf,
+    /// Intermittent lone CRs and LFs between regular text.
+    Mixed,
+}
+
+impl Pattern {
+    /// All patterns, in the order used by the crate's benchmarks.
+    pub const ALL: [Pattern; 5] = [
+        Pattern::Random,
+        Pattern::AllLf,
+        Pattern::AllCr,
+        Pattern::Crlf,
+        Pattern::Mixed,
+    ];
+
+    /// Generate a buffer of `size` bytes following this pattern.
+    #[must_use]
+    pub fn generate(self, size: usize) -> Vec<u8> {
+        match self {
+            Pattern::Random => {
+                let mut state: u64 = 0x1234_5678;
+                (0..size)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(1);
+                        state.to_le_bytes()[0]
+                    })
+                    .collect()
+            }
+            Pattern::AllLf => vec![b'\n'; size],
+            Pattern::AllCr => vec![b'\r'; size],
+            Pattern::Crlf => (0..size)
+                .map(|i| if i % 2 == 0 { b'\r' } else { b'\n' })
+                .collect(),
+            Pattern::Mixed => (0..size)
+                .map(|i| match i % 7 {
+                    0 => b'\r',
+                    1 => b'\n',
+                    2 => b'a',
+                    _ => b'b',
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of a throughput measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throughput {
+    bytes: u64,
+    elapsed: Duration,
+}
+
+impl Throughput {
+    pub(crate) fn new(bytes: u64, elapsed: Duration) -> Self {
+        Self { bytes, elapsed }
+    }
+
+    /// The total number of input bytes processed.
+    #[must_use]
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The total time spent processing.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The number of input bytes processed per second.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Normalize `input` with `N` in chunks of `chunk_size` bytes, `iterations` times over, and
+/// measure the throughput. The state is carried across chunks like the wrappers do.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero or if `N` reports an error for a correctly sized output
+/// buffer.
+#[must_use]
+pub fn measure_chunked<N: NormalizeChunk>(
+    input: &[u8],
+    chunk_size: usize,
+    iterations: u32,
+) -> Throughput {
+    assert!(chunk_size > 0, "chunk size must be non-zero");
+    let mut out = Vec::with_capacity(N::max_output_size_for_chunk(
+        chunk_size.min(input.len()),
+        None,
+        true,
+    ));
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let mut state = None;
+        let mut chunks = input.chunks(chunk_size).peekable();
+        while let Some(chunk) = chunks.next() {
+            let is_last_chunk = chunks.peek().is_none();
+            out.reserve(N::max_output_size_for_chunk(
+                chunk.len(),
+                state.as_ref(),
+                is_last_chunk,
+            ));
+            let status = N::normalize_chunk(
+                black_box(chunk),
+                vec_to_uninit_mut(&mut out),
+                state.as_ref(),
+                is_last_chunk,
+            )
+            .unwrap_or_else(|err| panic!("{err}"));
+            black_box(status.output_len());
+            state = status.state().cloned();
+        }
+        black_box(&state);
+    }
+
+    Throughput::new(input.len() as u64 * u64::from(iterations), start.elapsed())
+}
@@ -0,0 +1,625 @@
+//! The `builder` module provides `Normalizer`, a single entry point to configure how
+//! normalization is performed and to construct the matching readers, writers and copy helpers.
+
+use std::{
+    io::{BufRead, Read, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    wrappers::{
+        commit::CommitWriter,
+        io::{Reader, Writer},
+        output_buffer_size,
+        spool::{Spool, SpooledOutput},
+        BufferSizeHint, FlushPolicy, DEFAULT_BUFFER_SIZE, DEFAULT_SPILL_THRESHOLD, MAX_BUFFER_SIZE,
+    },
+    EnsureFinalNewline, NormalizeChunk, StripBom, Terminal, Tidy,
+};
+
+/// Result of a cancellable copy, such as [`NormalizerBuilder::copy_cancellable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyOutcome {
+    /// All input was normalized and written; `written` bytes were written to the writer.
+    Complete { written: u64 },
+    /// The copy was cancelled. `consumed` bytes were read from the reader and all output
+    /// normalized from them so far, `written` bytes, was written to the writer in full. The
+    /// format may still hold back the end of the consumed input, such as a `\r` that could
+    /// start a `\r\n`, so that part has not produced any output yet.
+    Cancelled { consumed: u64, written: u64 },
+}
+
+impl CopyOutcome {
+    /// Returns the number of bytes written to the writer.
+    #[must_use]
+    pub fn written(&self) -> u64 {
+        match *self {
+            CopyOutcome::Complete { written } | CopyOutcome::Cancelled { written, .. } => written,
+        }
+    }
+
+    /// Returns `true` if the copy was cancelled before all input was processed.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, CopyOutcome::Cancelled { .. })
+    }
+}
+
+/// Entry point for configuring a normalizer through a [`NormalizerBuilder`].
+///
+/// ```
+/// use std::io::Read;
+/// use eolify::{Normalizer, LF};
+///
+/// let normalizer = Normalizer::builder().target(LF).buffer_size(64 * 1024);
+///
+/// let mut out = String::new();
+/// normalizer
+///     .reader(b"one\r\ntwo\rthree".as_ref())
+///     .read_to_string(&mut out)
+///     .unwrap();
+/// assert_eq!(out, "one\ntwo\nthree");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Normalizer;
+
+impl Normalizer {
+    /// Start configuring a normalizer. A target format must be selected with
+    /// [`NormalizerBuilder::target`] before readers or writers can be constructed.
+    #[must_use]
+    pub fn builder() -> NormalizerBuilder<()> {
+        NormalizerBuilder {
+            target: (),
+            buffer_size: None,
+            flush_policy: FlushPolicy::default(),
+            spill_threshold: None,
+            throttle: None,
+        }
+    }
+}
+
+/// Builder holding all options for normalization.
+///
+/// The type parameter `N` is the selected target format, or `()` if none has been selected yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizerBuilder<N> {
+    target: N,
+    buffer_size: Option<usize>,
+    flush_policy: FlushPolicy,
+    spill_threshold: Option<usize>,
+    throttle: Option<u64>,
+}
+
+impl<N> NormalizerBuilder<N> {
+    /// Select the target format.
+    #[must_use]
+    pub fn target<T: NormalizeChunk>(self, target: T) -> NormalizerBuilder<T> {
+        NormalizerBuilder {
+            target,
+            buffer_size: self.buffer_size,
+            flush_policy: self.flush_policy,
+            spill_threshold: self.spill_threshold,
+            throttle: self.throttle,
+        }
+    }
+
+    /// Additionally strip a leading BOM and make sure the output ends with a line ending,
+    /// fused into the same pass as the line ending normalization. See [`Tidy`].
+    #[must_use]
+    pub fn tidy(self) -> NormalizerBuilder<Tidy<N>>
+    where
+        N: NormalizeChunk,
+    {
+        NormalizerBuilder {
+            target: Tidy(self.target),
+            buffer_size: self.buffer_size,
+            flush_policy: self.flush_policy,
+            spill_threshold: self.spill_threshold,
+            throttle: self.throttle,
+        }
+    }
+
+    /// Treat the input as terminal output, keeping `\r` redraws and ANSI escape sequences
+    /// intact. See [`Terminal`].
+    #[must_use]
+    pub fn terminal(self) -> NormalizerBuilder<Terminal<N>>
+    where
+        N: NormalizeChunk,
+    {
+        NormalizerBuilder {
+            target: Terminal(self.target),
+            buffer_size: self.buffer_size,
+            flush_policy: self.flush_policy,
+            spill_threshold: self.spill_threshold,
+            throttle: self.throttle,
+        }
+    }
+
+    /// Additionally strip a leading UTF-8 or UTF-16 byte order mark. See [`StripBom`].
+    #[must_use]
+    pub fn strip_bom(self) -> NormalizerBuilder<StripBom<N>>
+    where
+        N: NormalizeChunk,
+    {
+        NormalizerBuilder {
+            target: StripBom(self.target),
+            buffer_size: self.buffer_size,
+            flush_policy: self.flush_policy,
+            spill_threshold: self.spill_threshold,
+            throttle: self.throttle,
+        }
+    }
+
+    /// Additionally make sure non-empty output ends with a line ending. See
+    /// [`EnsureFinalNewline`].
+    #[must_use]
+    pub fn ensure_final_newline(self) -> NormalizerBuilder<EnsureFinalNewline<N>>
+    where
+        N: NormalizeChunk,
+    {
+        NormalizerBuilder {
+            target: EnsureFinalNewline(self.target),
+            buffer_size: self.buffer_size,
+            flush_policy: self.flush_policy,
+            spill_threshold: self.spill_threshold,
+            throttle: self.throttle,
+        }
+    }
+
+    /// Set the size of the internal input buffer used by the wrappers.
+    ///
+    /// This overrides both the default size and any size suggested through
+    /// [`BufferSizeHint`].
+    #[must_use]
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = Some(buffer_size);
+        self
+    }
+
+    /// Set the `FlushPolicy` used by the writers.
+    #[must_use]
+    pub fn flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Set the amount of normalized output kept in memory before spilling to a temporary file,
+    /// used by [`commit_writer`](Self::commit_writer) and
+    /// [`normalize_large`](Self::normalize_large).
+    #[must_use]
+    pub fn spill_threshold(mut self, spill_threshold: usize) -> Self {
+        self.spill_threshold = Some(spill_threshold);
+        self
+    }
+
+    /// Limit the readers and writers to `bytes_per_second` of input.
+    ///
+    /// The sync wrappers sleep the current thread, the `tokio` wrappers use
+    /// `tokio::time::sleep`. The `futures-io` wrappers have no timer to rely on and are not
+    /// throttled.
+    #[must_use]
+    pub fn throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(bytes_per_second);
+        self
+    }
+
+    fn buffer_size_or_default(&self) -> usize {
+        self.buffer_size.unwrap_or(DEFAULT_BUFFER_SIZE)
+    }
+}
+
+impl<N: NormalizeChunk> NormalizerBuilder<N> {
+    /// Pick the largest buffer size for which the readers and writers allocate at most `limit`
+    /// bytes for their input and output buffers together.
+    ///
+    /// The wrappers allocate their buffers once and never grow them, whatever the shape of the
+    /// input, so this is a hard bound on the memory each wrapper uses for normalization; see
+    /// `buffer_capacity` on the readers and writers. Formats may keep a bounded amount of data
+    /// in their state on top of that: [`TrimTrailingNewlines`](crate::TrimTrailingNewlines)
+    /// holds back at most a chunk of input while it writes a long run of line endings. Helpers that spill to memory or disk
+    /// ([`commit_writer`](Self::commit_writer), [`normalize_large`](Self::normalize_large))
+    /// and the transfer buffer of the copy helpers are not covered by the limit.
+    ///
+    /// ```
+    /// use eolify::{Normalizer, CRLF};
+    ///
+    /// let normalizer = Normalizer::builder().target(CRLF).memory_limit(4096);
+    /// assert!(normalizer.writer(Vec::new()).buffer_capacity() <= 4096);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is too small to hold buffers for a single byte of input.
+    #[must_use]
+    pub fn memory_limit(self, limit: usize) -> Self {
+        let capacity = |buf_size: usize| buf_size.saturating_add(output_buffer_size::<N>(buf_size));
+        assert!(
+            capacity(1) <= limit,
+            "memory limit of {limit} bytes is too small for any buffer"
+        );
+
+        // The capacity grows with the buffer size, so search for the largest size that fits.
+        let (mut low, mut high) = (1, MAX_BUFFER_SIZE.min(limit));
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if capacity(mid) <= limit {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        self.buffer_size(low)
+    }
+
+    /// Wrap a reader with a newline-normalizing `Reader` using the configured options.
+    pub fn reader<R: Read>(&self, reader: R) -> Reader<R, N> {
+        self.configure_reader(Reader::new(reader, self.buffer_size_or_default()))
+    }
+
+    /// Wrap a writer with a newline-normalizing `Writer` using the configured options.
+    pub fn writer<W: Write>(&self, writer: W) -> Writer<W, N> {
+        self.configure_writer(Writer::new(writer, self.buffer_size_or_default()))
+    }
+
+    /// Wrap a writer with a `CommitWriter` using the configured options, so normalized output
+    /// only reaches `sink` once committed.
+    ///
+    /// Output is kept in memory unless a [`spill_threshold`](Self::spill_threshold) was set.
+    pub fn commit_writer<W: Write>(&self, sink: W) -> CommitWriter<W, N> {
+        let writer = CommitWriter::new(sink, self.buffer_size_or_default())
+            .with_flush_policy(self.flush_policy);
+        match self.spill_threshold {
+            Some(threshold) => writer.with_spill_threshold(threshold),
+            None => writer,
+        }
+    }
+
+    /// Normalize everything from `reader` into a `SpooledOutput`, which keeps the output in
+    /// memory up to the [`spill_threshold`](Self::spill_threshold) (8 MiB by default) and in a
+    /// temporary file beyond it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or by the temporary file.
+    pub fn normalize_large<R: Read>(&self, reader: R) -> std::io::Result<SpooledOutput> {
+        let mut spool = Spool::new(Some(
+            self.spill_threshold.unwrap_or(DEFAULT_SPILL_THRESHOLD),
+        ));
+        std::io::copy(&mut self.reader(reader), &mut spool)?;
+        spool.into_output()
+    }
+
+    /// Wrap a reader with a newline-normalizing `Reader`, using the buffer size suggested by
+    /// the reader's [`BufferSizeHint`] unless one was set explicitly.
+    pub fn tuned_reader<R: Read + BufferSizeHint>(&self, reader: R) -> Reader<R, N> {
+        let buffer_size = self
+            .buffer_size
+            .unwrap_or_else(|| reader.buffer_size_hint());
+        self.configure_reader(Reader::new(reader, buffer_size))
+    }
+
+    /// Wrap a writer with a newline-normalizing `Writer`, using the buffer size suggested by
+    /// the writer's [`BufferSizeHint`] unless one was set explicitly.
+    pub fn tuned_writer<W: Write + BufferSizeHint>(&self, writer: W) -> Writer<W, N> {
+        let buffer_size = self
+            .buffer_size
+            .unwrap_or_else(|| writer.buffer_size_hint());
+        self.configure_writer(Writer::new(writer, buffer_size))
+    }
+
+    /// Copy all data from `reader` to `writer`, normalizing it on the way.
+    ///
+    /// Returns the number of bytes written to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub fn copy<R: Read + ?Sized, W: Write + ?Sized>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> std::io::Result<u64> {
+        // Write straight from the reader's output buffer, `std::io::copy` would copy everything
+        // through a buffer of its own first.
+        let mut reader = self.reader(reader);
+        let mut total = 0;
+        loop {
+            let buf = match reader.fill_buf() {
+                Ok([]) => return Ok(total),
+                Ok(buf) => buf,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(buf)?;
+            let n = buf.len();
+            reader.consume(n);
+            total += n as u64;
+        }
+    }
+
+    /// Copy all data from `reader` to `writer` like [`copy`](Self::copy), calling `progress`
+    /// with the number of bytes written so far each time at least `interval` more bytes were
+    /// written.
+    ///
+    /// This lets callers keep an event loop responsive or report progress during large
+    /// conversions. Returns the number of bytes written to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub fn copy_with_progress<R, W, F>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        interval: u64,
+        mut progress: F,
+    ) -> std::io::Result<u64>
+    where
+        R: Read + ?Sized,
+        W: Write + ?Sized,
+        F: FnMut(u64),
+    {
+        let mut reader = self.reader(reader);
+        let mut buf = vec![0; DEFAULT_BUFFER_SIZE];
+        let mut total = 0;
+        let mut next_progress = interval;
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => return Ok(total),
+                Ok(n) => n,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buf[..n])?;
+            total += n as u64;
+            if total >= next_progress {
+                progress(total);
+                next_progress = total.saturating_add(interval);
+            }
+        }
+    }
+
+    /// Copy all data from `reader` to `writer` like [`copy`](Self::copy), stopping early once
+    /// `cancel` is set.
+    ///
+    /// `cancel` is checked each time the normalized data of a chunk was written in full, so
+    /// another thread can abort a long-running conversion. A cancelled copy never leaves
+    /// normalized data behind, but some of the input reported in [`CopyOutcome::Cancelled`] may
+    /// not have produced output yet: the format can hold back the end of a chunk, such as a
+    /// `\r` that could start a `\r\n`, until it sees what follows.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub fn copy_cancellable<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        cancel: &AtomicBool,
+    ) -> std::io::Result<CopyOutcome>
+    where
+        R: Read + ?Sized,
+        W: Write + ?Sized,
+    {
+        // Like `copy`, every chunk is written in full before the next one is read, so no
+        // normalized data is left in the reader when checking for cancellation.
+        let mut reader = self.reader(reader);
+        let mut total = 0;
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Ok(CopyOutcome::Cancelled {
+                    consumed: reader.bytes_consumed(),
+                    written: total,
+                });
+            }
+            let buf = match reader.fill_buf() {
+                Ok([]) => return Ok(CopyOutcome::Complete { written: total }),
+                Ok(buf) => buf,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(buf)?;
+            let n = buf.len();
+            reader.consume(n);
+            total += n as u64;
+        }
+    }
+
+    fn configure_reader<R: Read>(&self, reader: Reader<R, N>) -> Reader<R, N> {
+        match self.throttle {
+            Some(bytes_per_second) => reader.with_throttle(bytes_per_second),
+            None => reader,
+        }
+    }
+
+    fn configure_writer<W: Write>(&self, writer: Writer<W, N>) -> Writer<W, N> {
+        let writer = writer.with_flush_policy(self.flush_policy);
+        match self.throttle {
+            Some(bytes_per_second) => writer.with_throttle(bytes_per_second),
+            None => writer,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<N: NormalizeChunk> NormalizerBuilder<N> {
+    /// Wrap a `tokio::io::AsyncRead` with a newline-normalizing `AsyncReader` using the
+    /// configured options.
+    ///
+    /// This method requires the `tokio` feature to be enabled.
+    pub fn tokio_reader<R: tokio::io::AsyncRead>(
+        &self,
+        reader: R,
+    ) -> crate::wrappers::tokio::AsyncReader<R, N> {
+        let reader =
+            crate::wrappers::tokio::AsyncReader::new(reader, self.buffer_size_or_default());
+        match self.throttle {
+            Some(bytes_per_second) => reader.with_throttle(bytes_per_second),
+            None => reader,
+        }
+    }
+
+    /// Wrap a `tokio::io::AsyncWrite` with a newline-normalizing `AsyncWriter` using the
+    /// configured options.
+    ///
+    /// This method requires the `tokio` feature to be enabled.
+    pub fn tokio_writer<W: tokio::io::AsyncWrite>(
+        &self,
+        writer: W,
+    ) -> crate::wrappers::tokio::AsyncWriter<W, N> {
+        let writer =
+            crate::wrappers::tokio::AsyncWriter::new(writer, self.buffer_size_or_default())
+                .with_flush_policy(self.flush_policy);
+        match self.throttle {
+            Some(bytes_per_second) => writer.with_throttle(bytes_per_second),
+            None => writer,
+        }
+    }
+
+    /// Asynchronously copy all data from `reader` to `writer`, normalizing it on the way.
+    ///
+    /// Returns the number of bytes written to `writer`. This method requires the `tokio`
+    /// feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub async fn tokio_copy<R, W>(&self, reader: &mut R, writer: &mut W) -> std::io::Result<u64>
+    where
+        R: tokio::io::AsyncRead + Unpin + ?Sized,
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        let mut reader = std::pin::pin!(self.tokio_reader(reader));
+        tokio::io::copy(&mut reader, writer).await
+    }
+
+    /// Asynchronously copy all data from `reader` to `writer` like
+    /// [`tokio_copy`](Self::tokio_copy), yielding to the scheduler each time at least
+    /// `interval` more bytes were written.
+    ///
+    /// Returns the number of bytes written to `writer`. This method requires the `tokio`
+    /// feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub async fn tokio_copy_yielding<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        interval: u64,
+    ) -> std::io::Result<u64>
+    where
+        R: tokio::io::AsyncRead + Unpin + ?Sized,
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut reader = std::pin::pin!(self.tokio_reader(reader));
+        let mut buf = vec![0; DEFAULT_BUFFER_SIZE];
+        let mut total = 0;
+        let mut next_yield = interval;
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(total);
+            }
+            writer.write_all(&buf[..n]).await?;
+            total += n as u64;
+            if total >= next_yield {
+                YieldNow(false).await;
+                next_yield = total.saturating_add(interval);
+            }
+        }
+    }
+
+    /// Asynchronously copy all data from `reader` to `writer` like
+    /// [`tokio_copy`](Self::tokio_copy), stopping early once `cancel` is set.
+    ///
+    /// `cancel` is checked each time all normalized data read so far was written; see
+    /// [`copy_cancellable`](Self::copy_cancellable) for the meaning of the outcome. Dropping the
+    /// future also stops the copy, but leaves it unknown how much was written. This method
+    /// requires the `tokio` feature to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    pub async fn tokio_copy_cancellable<R, W>(
+        &self,
+        reader: &mut R,
+        writer: &mut W,
+        cancel: &AtomicBool,
+    ) -> std::io::Result<CopyOutcome>
+    where
+        R: tokio::io::AsyncRead + Unpin + ?Sized,
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut reader = std::pin::pin!(self.tokio_reader(reader));
+        let mut buf = vec![0; DEFAULT_BUFFER_SIZE];
+        let mut total = 0;
+        loop {
+            // Normalized data still buffered in the reader was consumed but not written yet.
+            if reader.is_drained() && cancel.load(Ordering::Relaxed) {
+                return Ok(CopyOutcome::Cancelled {
+                    consumed: reader.bytes_consumed(),
+                    written: total,
+                });
+            }
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(CopyOutcome::Complete { written: total });
+            }
+            writer.write_all(&buf[..n]).await?;
+            total += n as u64;
+        }
+    }
+}
+
+/// Future that returns `Pending` once, so other tasks get a chance to run.
+#[cfg(feature = "tokio")]
+struct YieldNow(bool);
+
+#[cfg(feature = "tokio")]
+impl std::future::Future for YieldNow {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        if self.0 {
+            return std::task::Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<N: NormalizeChunk> NormalizerBuilder<N> {
+    /// Wrap a `futures::io::AsyncRead` with a newline-normalizing `AsyncReader` using the
+    /// configured options.
+    ///
+    /// This method requires the `futures-io` feature to be enabled.
+    pub fn futures_io_reader<R: futures_io::AsyncRead>(
+        &self,
+        reader: R,
+    ) -> crate::wrappers::futures_io::AsyncReader<R, N> {
+        crate::wrappers::futures_io::AsyncReader::new(reader, self.buffer_size_or_default())
+    }
+
+    /// Wrap a `futures::io::AsyncWrite` with a newline-normalizing `AsyncWriter` using the
+    /// configured options.
+    ///
+    /// This method requires the `futures-io` feature to be enabled.
+    pub fn futures_io_writer<W: futures_io::AsyncWrite>(
+        &self,
+        writer: W,
+    ) -> crate::wrappers::futures_io::AsyncWriter<W, N> {
+        crate::wrappers::futures_io::AsyncWriter::new(writer, self.buffer_size_or_default())
+            .with_flush_policy(self.flush_policy)
+    }
+}
@@ -0,0 +1,101 @@
+//! The `capabilities` module describes what this build of eolify supports, for diagnostics.
+
+use std::fmt;
+
+use crate::{
+    registry,
+    wrappers::{DEFAULT_BUFFER_SIZE, MAX_BUFFER_SIZE},
+};
+
+/// A description of this build of eolify, as returned by [`capabilities`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// The version of the crate.
+    pub version: String,
+    /// The names of the formats in the [`registry`](crate::registry), including any
+    /// registered at runtime.
+    pub formats: Vec<String>,
+    /// The optional features compiled in.
+    pub features: Features,
+    /// Limits of the wrappers.
+    pub limits: Limits,
+}
+
+/// The optional features of the crate and whether they are compiled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Features {
+    /// The `tokio` feature: async wrappers for tokio.
+    pub tokio: bool,
+    /// The `futures-io` feature: async wrappers for `futures-io`.
+    pub futures_io: bool,
+    /// The `serde` feature: serializable chunk states.
+    pub serde: bool,
+    /// The `trace` feature: chunk call recording in the synchronous wrappers.
+    pub trace: bool,
+    /// The `bench-util` feature: the benchmarking and comparison helpers.
+    pub bench_util: bool,
+}
+
+/// Limits of the wrappers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limits {
+    /// The input buffer size used when none is given.
+    pub default_buffer_size: usize,
+    /// The largest input buffer size; larger requests are clamped to it.
+    pub max_buffer_size: usize,
+}
+
+/// Describe this build of eolify: its version, formats, compiled-in features and limits.
+///
+/// ```
+/// let capabilities = eolify::capabilities();
+/// assert!(capabilities.formats.iter().any(|name| name == "crlf"));
+/// println!("{capabilities}");
+/// ```
+#[must_use]
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_owned(),
+        formats: registry::format_names(),
+        features: Features {
+            tokio: cfg!(feature = "tokio"),
+            futures_io: cfg!(feature = "futures-io"),
+            serde: cfg!(feature = "serde"),
+            trace: cfg!(feature = "trace"),
+            bench_util: cfg!(feature = "bench-util"),
+        },
+        limits: Limits {
+            default_buffer_size: DEFAULT_BUFFER_SIZE,
+            max_buffer_size: MAX_BUFFER_SIZE,
+        },
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let features = &self.features;
+        let enabled: Vec<&str> = [
+            ("bench-util", features.bench_util),
+            ("futures-io", features.futures_io),
+            ("serde", features.serde),
+            ("tokio", features.tokio),
+            ("trace", features.trace),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect();
+
+        writeln!(f, "eolify {}", self.version)?;
+        writeln!(f, "formats: {}", self.formats.join(", "))?;
+        writeln!(f, "features: {}", enabled.join(", "))?;
+        write!(
+            f,
+            "buffer size: {} (max {})",
+            self.limits.default_buffer_size, self.limits.max_buffer_size
+        )
+    }
+}
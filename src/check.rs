@@ -0,0 +1,277 @@
+//! The `check` module answers whether input is already normalized, without producing the
+//! normalized output. Outcomes map onto the exit codes a dry-run tool is expected to use.
+//! The `report_*` functions additionally audit whether the input ends with a single newline.
+//!
+//! ```
+//! use eolify::{check::{self, CheckOutcome}, CRLF};
+//!
+//! let outcome = check::check_slice::<CRLF>(b"one\r\n")
+//!     .merge(check::check_slice::<CRLF>(b"two\n"));
+//! assert!(matches!(outcome, CheckOutcome::WouldChange(1)));
+//! assert_eq!(outcome.exit_code(), 1);
+//! ```
+
+use std::{
+    io::{self, ErrorKind, Read},
+    path::Path,
+};
+
+use crate::{
+    types::{CR, LF},
+    wrappers::DEFAULT_BUFFER_SIZE,
+    NormalizeChunk,
+};
+
+/// Combined result of checking one or more inputs.
+#[derive(Debug)]
+pub enum CheckOutcome {
+    /// All inputs are normalized. Maps to exit code 0.
+    Clean,
+    /// The given number of inputs would change when normalized. Maps to exit code 1.
+    WouldChange(usize),
+    /// Some inputs could not be checked. Maps to exit code 2, regardless of whether other
+    /// inputs would change.
+    Errors(Vec<std::io::Error>),
+}
+
+impl CheckOutcome {
+    /// Returns the process exit code for this outcome: 0 for `Clean`, 1 for `WouldChange` and
+    /// 2 for `Errors`.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CheckOutcome::Clean => 0,
+            CheckOutcome::WouldChange(_) => 1,
+            CheckOutcome::Errors(_) => 2,
+        }
+    }
+
+    /// Returns `true` if all inputs are normalized.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        matches!(self, CheckOutcome::Clean)
+    }
+
+    /// Combine the outcomes of two sets of inputs. Errors take precedence over changes, which
+    /// take precedence over clean inputs.
+    #[must_use]
+    pub fn merge(self, other: CheckOutcome) -> CheckOutcome {
+        match (self, other) {
+            (CheckOutcome::Errors(mut a), CheckOutcome::Errors(b)) => {
+                a.extend(b);
+                CheckOutcome::Errors(a)
+            }
+            (errors @ CheckOutcome::Errors(_), _) | (_, errors @ CheckOutcome::Errors(_)) => errors,
+            (CheckOutcome::WouldChange(a), CheckOutcome::WouldChange(b)) => {
+                CheckOutcome::WouldChange(a + b)
+            }
+            (changed @ CheckOutcome::WouldChange(_), CheckOutcome::Clean)
+            | (CheckOutcome::Clean, changed @ CheckOutcome::WouldChange(_)) => changed,
+            (CheckOutcome::Clean, CheckOutcome::Clean) => CheckOutcome::Clean,
+        }
+    }
+}
+
+impl From<std::io::Error> for CheckOutcome {
+    fn from(err: std::io::Error) -> Self {
+        CheckOutcome::Errors(vec![err])
+    }
+}
+
+impl FromIterator<CheckOutcome> for CheckOutcome {
+    fn from_iter<I: IntoIterator<Item = CheckOutcome>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(CheckOutcome::Clean, CheckOutcome::merge)
+    }
+}
+
+/// Check whether `input` is normalized to `N`.
+#[must_use]
+pub fn check_slice<N: NormalizeChunk>(input: &[u8]) -> CheckOutcome {
+    if N::contains_foreign_eol(input) {
+        CheckOutcome::WouldChange(1)
+    } else {
+        CheckOutcome::Clean
+    }
+}
+
+/// Check whether everything read from `reader` is normalized to `N`, reading it in chunks.
+///
+/// A read error is reported as [`CheckOutcome::Errors`].
+pub fn check_reader<N: NormalizeChunk, R: Read>(reader: R) -> CheckOutcome {
+    match scan::<N, R>(reader, false) {
+        Ok(report) => report.eol_outcome(),
+        Err(err) => err.into(),
+    }
+}
+
+/// Check whether the file at `path` is normalized to `N`.
+///
+/// Failing to open or read the file is reported as [`CheckOutcome::Errors`].
+pub fn check_path<N: NormalizeChunk>(path: impl AsRef<Path>) -> CheckOutcome {
+    match std::fs::File::open(path) {
+        Ok(file) => check_reader::<N, _>(file),
+        Err(err) => err.into(),
+    }
+}
+
+/// How the input ends, audited independently of its line ending style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FinalNewline {
+    /// The input ends with exactly one line ending, or is empty.
+    Present,
+    /// The last line of the input is not terminated.
+    Missing,
+    /// The input ends with the given number of consecutive line endings, i.e. there are
+    /// trailing blank lines.
+    Multiple(usize),
+}
+
+/// Findings from a single scan of one input.
+///
+/// The line ending style and the end of the input are reported as separate categories, so a
+/// style check can flag either problem without scanning twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CheckReport {
+    /// Whether normalizing the input would change it.
+    pub would_change: bool,
+    /// How the input ends. Line endings of any style count.
+    pub final_newline: FinalNewline,
+}
+
+impl CheckReport {
+    /// Returns `true` if the input is normalized and ends with exactly one line ending.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        !self.would_change && self.final_newline == FinalNewline::Present
+    }
+
+    /// Returns the line ending style part of the report as a [`CheckOutcome`], ignoring how
+    /// the input ends.
+    #[must_use]
+    pub fn eol_outcome(&self) -> CheckOutcome {
+        if self.would_change {
+            CheckOutcome::WouldChange(1)
+        } else {
+            CheckOutcome::Clean
+        }
+    }
+}
+
+/// Check `input` against `N` and audit how it ends.
+#[must_use]
+pub fn report_slice<N: NormalizeChunk>(input: &[u8]) -> CheckReport {
+    let mut tail = TrailingNewlines::default();
+    tail.update(input);
+    CheckReport {
+        would_change: N::contains_foreign_eol(input),
+        final_newline: tail.finish(),
+    }
+}
+
+/// Check everything read from `reader` against `N` and audit how it ends, reading it in
+/// chunks.
+///
+/// # Errors
+///
+/// Returns any error from reading `reader`, other than [`ErrorKind::Interrupted`].
+pub fn report_reader<N: NormalizeChunk, R: Read>(reader: R) -> io::Result<CheckReport> {
+    scan::<N, R>(reader, true)
+}
+
+/// Check the file at `path` against `N` and audit how it ends.
+///
+/// # Errors
+///
+/// Returns any error from opening or reading the file.
+pub fn report_path<N: NormalizeChunk>(path: impl AsRef<Path>) -> io::Result<CheckReport> {
+    report_reader::<N, _>(std::fs::File::open(path)?)
+}
+
+/// Counts the line endings at the end of the input seen so far.
+#[derive(Debug, Default)]
+struct TrailingNewlines {
+    seen_input: bool,
+    count: usize,
+    ends_with_cr: bool,
+}
+
+impl TrailingNewlines {
+    fn update(&mut self, chunk: &[u8]) {
+        let is_eol = |b: &u8| *b == CR || *b == LF;
+        let run = match chunk.iter().rposition(|b| !is_eol(b)) {
+            Some(pos) => {
+                self.count = 0;
+                self.ends_with_cr = false;
+                &chunk[pos + 1..]
+            }
+            None => chunk,
+        };
+        self.seen_input |= !chunk.is_empty();
+        for &b in run {
+            // A `\n` directly after a `\r` completes a line ending that was already counted.
+            if b == CR || !self.ends_with_cr {
+                self.count += 1;
+            }
+            self.ends_with_cr = b == CR;
+        }
+    }
+
+    fn finish(&self) -> FinalNewline {
+        match self.count {
+            0 if self.seen_input => FinalNewline::Missing,
+            0 | 1 => FinalNewline::Present,
+            n => FinalNewline::Multiple(n),
+        }
+    }
+}
+
+/// Compare the input with its normalized form chunk by chunk. Unless `audit_eof` is set, the
+/// scan stops at the first difference and the reported `final_newline` is meaningless.
+fn scan<N: NormalizeChunk, R: Read>(mut reader: R, audit_eof: bool) -> io::Result<CheckReport> {
+    let mut input = vec![0; DEFAULT_BUFFER_SIZE];
+    let mut output = Vec::new();
+    // Input and output that could not be compared yet, because the other side lags behind.
+    let mut pending_input = Vec::new();
+    let mut pending_output = Vec::new();
+    let mut state = None;
+    let mut would_change = false;
+    let mut tail = TrailingNewlines::default();
+    loop {
+        let n = match reader.read(&mut input) {
+            Ok(n) => n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let is_last_chunk = n == 0;
+        tail.update(&input[..n]);
+
+        if !would_change {
+            output.clear();
+            let status =
+                N::normalize_chunk_into(&input[..n], &mut output, state.as_ref(), is_last_chunk)
+                    .unwrap_or_else(|err| unreachable!("{err} (should be impossible)",));
+            state = status.state().cloned();
+
+            pending_input.extend_from_slice(&input[..n]);
+            pending_output.extend_from_slice(&output);
+            let common = pending_input.len().min(pending_output.len());
+            would_change = pending_input[..common] != pending_output[..common]
+                || (is_last_chunk && pending_input.len() != pending_output.len());
+            pending_input.drain(..common);
+            pending_output.drain(..common);
+
+            if would_change && !audit_eof {
+                break;
+            }
+        }
+
+        if is_last_chunk {
+            break;
+        }
+    }
+    Ok(CheckReport {
+        would_change,
+        final_newline: tail.finish(),
+    })
+}
@@ -0,0 +1,124 @@
+//! The `classify` module decides from a bounded prefix of the input whether it is text, and
+//! which line endings it uses, in a single read. The prefix is handed back so the caller can
+//! wrap the complete input afterwards.
+//!
+//! ```
+//! use std::io::Read;
+//! use eolify::{classify::{classify, ContentKind}, ReadExt, CRLF};
+//!
+//! let classification = classify(b"\xEF\xBB\xBFa\nb\r\n".as_ref(), 1024)?;
+//! let ContentKind::Text { utf8_bom, eols } = classification.kind else {
+//!     panic!("not text");
+//! };
+//! assert!(utf8_bom);
+//! assert_eq!((eols.lf, eols.crlf), (1, 1));
+//!
+//! let mut output = Vec::new();
+//! classification.into_reader().normalize_newlines(CRLF).read_to_end(&mut output)?;
+//! assert_eq!(output, b"\xEF\xBB\xBFa\r\nb\r\n");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{Chain, Cursor, ErrorKind, Read};
+
+use memchr::memchr;
+
+use crate::{helpers::LineEndingCounter, EolStats};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// What kind of content the classified prefix holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentKind {
+    /// The input is empty.
+    Empty,
+    /// The prefix contains a NUL byte, which text does not, so the input should not be
+    /// normalized. UTF-16 and UTF-32 text is classified as binary too, since it can't be
+    /// normalized byte by byte.
+    Binary,
+    /// The input looks like text.
+    Text {
+        /// Whether the input starts with a UTF-8 byte order mark.
+        utf8_bom: bool,
+        /// The line endings found in the prefix. A `\r` at the end of an incomplete prefix is
+        /// not counted, since it may be the start of a `\r\n`.
+        eols: EolStats,
+    },
+}
+
+/// The result of [`classify`]: the kind of content along with the input.
+#[derive(Debug)]
+pub struct Classification<R> {
+    /// The kind of content.
+    pub kind: ContentKind,
+    /// Whether the prefix holds the complete input, i.e. the reader reached its end within the
+    /// budget.
+    pub complete: bool,
+    /// The bytes read from the input to classify it.
+    pub prefix: Vec<u8>,
+    /// The reader, positioned right after the prefix.
+    pub reader: R,
+}
+
+impl<R: Read> Classification<R> {
+    /// Returns a reader yielding the complete input, starting with the prefix.
+    pub fn into_reader(self) -> Chain<Cursor<Vec<u8>>, R> {
+        Cursor::new(self.prefix).chain(self.reader)
+    }
+}
+
+/// Classify the input of `reader` by reading at most `budget` bytes of it.
+///
+/// # Errors
+///
+/// Returns any error from reading `reader`, other than [`ErrorKind::Interrupted`]. The bytes
+/// read so far are lost in that case.
+pub fn classify<R: Read>(mut reader: R, budget: usize) -> std::io::Result<Classification<R>> {
+    let mut prefix = vec![0; budget];
+    let mut len = 0;
+    let complete = loop {
+        if len == budget {
+            break false;
+        }
+        match reader.read(&mut prefix[len..]) {
+            Ok(0) => break true,
+            Ok(n) => len += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    };
+    prefix.truncate(len);
+
+    let kind = if prefix.is_empty() {
+        if complete {
+            ContentKind::Empty
+        } else {
+            // With a budget of zero there is nothing to go on, so assume text like an empty
+            // prefix of a text file would be.
+            ContentKind::Text {
+                utf8_bom: false,
+                eols: EolStats::default(),
+            }
+        }
+    } else if memchr(0, &prefix).is_some() {
+        ContentKind::Binary
+    } else {
+        let (lf, crlf, cr) = LineEndingCounter::default().count(&prefix, complete);
+        ContentKind::Text {
+            utf8_bom: prefix.starts_with(UTF8_BOM),
+            eols: EolStats {
+                bytes: prefix.len() as u64,
+                lf,
+                crlf,
+                cr,
+            },
+        }
+    };
+
+    Ok(Classification {
+        kind,
+        complete,
+        prefix,
+        reader,
+    })
+}
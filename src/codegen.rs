@@ -0,0 +1,74 @@
+//! The `codegen` module helps build scripts emit generated source files with `\n` line endings
+//! on every platform, so `include!`d artifacts are byte-identical across machines regardless of
+//! the line endings of templates or of a `core.autocrlf` checkout.
+//!
+//! Files are only written when their content changes, so unchanged output does not trigger
+//! rebuilds. Output of code formatters such as `prettyplease::unparse` can be passed to
+//! [`write_out_dir_file`] directly; code written piece by piece through `std::fmt::Write` can
+//! be normalized with [`FmtWriter`](crate::FmtWriter).
+//!
+//! ```no_run
+//! // build.rs
+//! fn main() -> std::io::Result<()> {
+//!     let code = format!("pub const ANSWER: u32 = {};\r\n", 42);
+//!     eolify::codegen::write_out_dir_file("answer.rs", &code)?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{Normalize, NormalizeChunk, LF};
+
+/// Returns the path of `name` inside the `OUT_DIR` of the running build script.
+fn out_dir_path(name: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            "OUT_DIR is not set, this is only available to build scripts",
+        )
+    })?;
+    Ok(Path::new(&out_dir).join(name))
+}
+
+/// Write `contents` to `path` unless it already holds exactly that.
+fn write_if_changed(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    match std::fs::read(path) {
+        Ok(existing) if existing == contents => Ok(()),
+        Ok(_) => std::fs::write(path, contents),
+        Err(err) if err.kind() == ErrorKind::NotFound => std::fs::write(path, contents),
+        Err(err) => Err(err),
+    }
+}
+
+/// Write `contents` with `\n` line endings to the file `name` in `OUT_DIR` and return its path.
+///
+/// # Errors
+///
+/// Returns an error if `OUT_DIR` is not set, or if the file can't be read or written.
+pub fn write_out_dir_file(
+    name: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> std::io::Result<PathBuf> {
+    let path = out_dir_path(name)?;
+    write_if_changed(&path, &LF::normalize(contents.as_ref()))?;
+    Ok(path)
+}
+
+/// Convert the line endings of the existing file `name` in `OUT_DIR` to `\n`, e.g. after a
+/// code generator that writes platform line endings, and return its path.
+///
+/// # Errors
+///
+/// Returns an error if `OUT_DIR` is not set, or if the file can't be read or written.
+pub fn normalize_out_dir_file(name: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+    let path = out_dir_path(name)?;
+    let contents = std::fs::read(&path)?;
+    if LF::contains_foreign_eol(&contents) {
+        std::fs::write(&path, LF::normalize(&contents))?;
+    }
+    Ok(path)
+}
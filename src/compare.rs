@@ -0,0 +1,139 @@
+//! The `compare` module runs a format and an alternative normalizer over the same inputs,
+//! asserting that they produce identical output and measuring both. Use it to validate that
+//! eolify is a drop-in replacement for an existing normalizer before switching over.
+//!
+//! ```
+//! use eolify::{compare::compare_str, LF};
+//!
+//! let inputs = ["one\r\ntwo\r\n", "no line ending", "three\r\n\r\n"];
+//! let comparison = compare_str::<LF, _, _>(&inputs, 10, |input| input.replace("\r\n", "\n"));
+//! assert_eq!(comparison.eolify().bytes(), comparison.alternative().bytes());
+//! ```
+//!
+//! This module requires the `bench-util` feature to be enabled.
+
+use std::{hint::black_box, time::Instant};
+
+use crate::{bench::Throughput, Normalize};
+
+/// Outcome of a comparison: the throughput of both implementations over the same inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    eolify: Throughput,
+    alternative: Throughput,
+}
+
+impl Comparison {
+    /// The throughput of the eolify format.
+    #[must_use]
+    pub fn eolify(&self) -> Throughput {
+        self.eolify
+    }
+
+    /// The throughput of the alternative implementation.
+    #[must_use]
+    pub fn alternative(&self) -> Throughput {
+        self.alternative
+    }
+
+    /// How many times faster eolify was than the alternative; below 1 if it was slower.
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        self.alternative.elapsed().as_secs_f64() / self.eolify.elapsed().as_secs_f64()
+    }
+}
+
+/// Normalize every input with `N` and with `alternative`, `iterations` times over, and measure
+/// both.
+///
+/// # Panics
+///
+/// Panics if the outputs differ for any input, reporting the input and the first differing
+/// offset.
+pub fn compare<N, F, O>(inputs: &[&[u8]], iterations: u32, mut alternative: F) -> Comparison
+where
+    N: Normalize,
+    F: FnMut(&[u8]) -> O,
+    O: AsRef<[u8]>,
+{
+    for (index, input) in inputs.iter().enumerate() {
+        assert_identical(index, &N::normalize(input), alternative(input).as_ref());
+    }
+
+    let eolify = measure(inputs, iterations, |input| {
+        black_box(N::normalize(black_box(input)));
+    });
+    let alternative = measure(inputs, iterations, |input| {
+        black_box(alternative(black_box(input)));
+    });
+    Comparison {
+        eolify,
+        alternative,
+    }
+}
+
+/// Like [`compare`], for alternatives that work on strings, such as `Regex::replace_all`.
+///
+/// # Panics
+///
+/// Panics if the outputs differ for any input, reporting the input and the first differing
+/// offset.
+pub fn compare_str<N, F, O>(inputs: &[&str], iterations: u32, mut alternative: F) -> Comparison
+where
+    N: Normalize,
+    F: FnMut(&str) -> O,
+    O: AsRef<str>,
+{
+    for (index, input) in inputs.iter().enumerate() {
+        assert_identical(
+            index,
+            N::normalize_str(input).as_bytes(),
+            alternative(input).as_ref().as_bytes(),
+        );
+    }
+
+    let inputs: Vec<&[u8]> = inputs.iter().map(|input| input.as_bytes()).collect();
+    let eolify = measure(&inputs, iterations, |input| {
+        black_box(N::normalize(black_box(input)));
+    });
+    let alternative = measure(&inputs, iterations, |input| {
+        // SAFETY: The bytes were taken from a `&str` above.
+        let input = unsafe { std::str::from_utf8_unchecked(input) };
+        black_box(alternative(black_box(input)));
+    });
+    Comparison {
+        eolify,
+        alternative,
+    }
+}
+
+fn assert_identical(index: usize, expected: &[u8], actual: &[u8]) {
+    if expected == actual {
+        return;
+    }
+    let offset = expected
+        .iter()
+        .zip(actual)
+        .position(|(a, b)| a != b)
+        .unwrap_or(expected.len().min(actual.len()));
+    fn context(output: &[u8], offset: usize) -> std::slice::EscapeAscii<'_> {
+        output[offset..output.len().min(offset + 16)].escape_ascii()
+    }
+    panic!(
+        "outputs for input {index} differ at offset {offset}: eolify produced \"{}\", the \
+         alternative \"{}\"",
+        context(expected, offset),
+        context(actual, offset),
+    );
+}
+
+fn measure(inputs: &[&[u8]], iterations: u32, mut run: impl FnMut(&[u8])) -> Throughput {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        for input in inputs {
+            run(input);
+        }
+    }
+    let bytes = inputs.iter().map(|input| input.len() as u64).sum::<u64>();
+    Throughput::new(bytes * u64::from(iterations), start.elapsed())
+}
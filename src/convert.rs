@@ -0,0 +1,118 @@
+//! The `convert` module provides conversion between two known line ending styles, validating
+//! that the input really uses the declared source style.
+
+use core::fmt;
+
+use crate::{helpers::find_next_eol, types::LineTerminator};
+
+/// What [`convert_with`] does with a line ending that contradicts the declared source style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RepairPolicy {
+    /// Fail with a [`ConvertError`]. This is what [`convert`] does.
+    #[default]
+    Error,
+    /// Drop the line ending from the output.
+    Skip,
+    /// Replace the line ending with the target line ending, as if it had been the declared one.
+    Replace,
+}
+
+/// Error returned by [`convert`] when the input contradicts the declared source style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConvertError {
+    /// Offset of the offending line ending in the input.
+    pub offset: usize,
+    /// The line ending found at `offset`.
+    pub found: LineTerminator,
+    /// The line ending style the input was declared to use.
+    pub expected: LineTerminator,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "found {:?} line ending at offset {} in input declared as {:?}",
+            self.found, self.offset, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// Convert `input` whose line endings are all `from` to line endings `to`.
+///
+/// Unlike the normalizing formats, which accept any mix of line endings, this assumes the
+/// producer's style is known and rejects input containing any other line ending. Converting to
+/// the same style only validates the input.
+///
+/// ```
+/// use eolify::{convert, LineTerminator};
+///
+/// let out = convert(b"a\r\nb\r\n", LineTerminator::CrLf, LineTerminator::Lf).unwrap();
+/// assert_eq!(out, b"a\nb\n");
+///
+/// let err = convert(b"a\r\nb\n", LineTerminator::CrLf, LineTerminator::Lf).unwrap_err();
+/// assert_eq!(err.offset, 4);
+/// assert_eq!(err.found, LineTerminator::Lf);
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`ConvertError`] for the first line ending in `input` that is not `from`.
+pub fn convert(
+    input: &[u8],
+    from: LineTerminator,
+    to: LineTerminator,
+) -> Result<Vec<u8>, ConvertError> {
+    convert_with(input, from, to, RepairPolicy::Error)
+}
+
+/// Like [`convert`], but lets `policy` decide what happens to each line ending that is not
+/// `from`, trading strictness for best-effort recovery.
+///
+/// ```
+/// use eolify::{convert_with, LineTerminator, RepairPolicy};
+///
+/// let input = b"a\r\nb\nc\r\n";
+/// let (from, to) = (LineTerminator::CrLf, LineTerminator::Lf);
+/// assert_eq!(convert_with(input, from, to, RepairPolicy::Replace).unwrap(), b"a\nb\nc\n");
+/// assert_eq!(convert_with(input, from, to, RepairPolicy::Skip).unwrap(), b"a\nbc\n");
+/// ```
+///
+/// # Errors
+///
+/// With [`RepairPolicy::Error`], returns a [`ConvertError`] for the first line ending in `input`
+/// that is not `from`. The other policies never fail.
+pub fn convert_with(
+    input: &[u8],
+    from: LineTerminator,
+    to: LineTerminator,
+    policy: RepairPolicy,
+) -> Result<Vec<u8>, ConvertError> {
+    let mut output = Vec::with_capacity(if to.len() > from.len() {
+        input.len().saturating_mul(2)
+    } else {
+        input.len()
+    });
+
+    let mut read_pos = 0;
+    while let Some((i, found)) = find_next_eol(input, read_pos) {
+        output.extend_from_slice(&input[read_pos..i]);
+        match policy {
+            _ if found == from => output.extend_from_slice(to.as_bytes()),
+            RepairPolicy::Error => {
+                return Err(ConvertError {
+                    offset: i,
+                    found,
+                    expected: from,
+                })
+            }
+            RepairPolicy::Skip => {}
+            RepairPolicy::Replace => output.extend_from_slice(to.as_bytes()),
+        }
+        read_pos = i + found.len();
+    }
+    output.extend_from_slice(&input[read_pos..]);
+    Ok(output)
+}
@@ -0,0 +1,175 @@
+//! The `edit` module provides incremental re-normalization of an already normalized buffer
+//! after a small edit, without re-processing the whole buffer.
+
+use std::ops::Range;
+
+use crate::{
+    types::{CR, LF},
+    NormalizeChunk,
+};
+
+/// Describes which part of a buffer was rewritten by [`NormalizeEdit::normalize_edit`].
+///
+/// Bytes before the rewritten region keep their offsets, bytes after it are shifted by the
+/// difference in length between the old and the new region.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedEdit {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+impl NormalizedEdit {
+    /// The range in the buffer before the edit that was replaced.
+    ///
+    /// This can be slightly larger than the requested edit range when bytes directly after it
+    /// had to be normalized again (e.g. an `\n` that became part of a `\r\n`).
+    #[must_use]
+    pub fn old_range(&self) -> Range<usize> {
+        self.old.clone()
+    }
+
+    /// The range in the buffer after the edit holding the newly normalized bytes.
+    #[must_use]
+    pub fn new_range(&self) -> Range<usize> {
+        self.new.clone()
+    }
+
+    /// Map an offset in the buffer before the edit to the corresponding offset after the edit.
+    ///
+    /// Returns `None` for offsets strictly inside the replaced region, as those have no
+    /// counterpart.
+    #[must_use]
+    pub fn map_offset(&self, offset: usize) -> Option<usize> {
+        if offset <= self.old.start {
+            Some(offset)
+        } else if offset >= self.old.end {
+            Some(offset - self.old.end + self.new.end)
+        } else {
+            None
+        }
+    }
+}
+
+/// Trait to apply edits to normalized buffers while keeping them normalized.
+pub trait NormalizeEdit {
+    /// Replace `range` of the already `normalized` buffer with `replacement` and normalize the
+    /// result again, only touching the bytes around the edit.
+    ///
+    /// The outcome is identical to normalizing the edited buffer from scratch, provided that
+    /// `normalized` was normalized to the same format before the edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `normalized` or its start is after its end.
+    fn normalize_edit(
+        normalized: &mut Vec<u8>,
+        range: Range<usize>,
+        replacement: &[u8],
+    ) -> NormalizedEdit;
+}
+
+impl NormalizeEdit for crate::LF {
+    fn normalize_edit(
+        normalized: &mut Vec<u8>,
+        range: Range<usize>,
+        replacement: &[u8],
+    ) -> NormalizedEdit {
+        normalize_edit::<Self>(normalized, range, replacement)
+    }
+}
+
+impl NormalizeEdit for crate::CRLF {
+    fn normalize_edit(
+        normalized: &mut Vec<u8>,
+        range: Range<usize>,
+        replacement: &[u8],
+    ) -> NormalizedEdit {
+        normalize_edit::<Self>(normalized, range, replacement)
+    }
+}
+
+impl NormalizeEdit for crate::CR {
+    fn normalize_edit(
+        normalized: &mut Vec<u8>,
+        range: Range<usize>,
+        replacement: &[u8],
+    ) -> NormalizedEdit {
+        normalize_edit::<Self>(normalized, range, replacement)
+    }
+}
+
+/// Shared implementation for the formats whose only state is whether the previous chunk ended
+/// with a `\r`.
+fn normalize_edit<N: NormalizeChunk<State = bool>>(
+    normalized: &mut Vec<u8>,
+    range: Range<usize>,
+    replacement: &[u8],
+) -> NormalizedEdit {
+    let Range { start, end } = range;
+    assert!(
+        start <= end && end <= normalized.len(),
+        "edit range {start}..{end} out of bounds for buffer of length {}",
+        normalized.len()
+    );
+
+    // Bytes before the edit are untouched, but a `\r` directly in front of it may pair up
+    // with a `\n` at the start of the replacement.
+    let preceded_by_cr = start > 0 && normalized[start - 1] == CR;
+
+    let mut output = Vec::new();
+    let mut state = normalize_into::<N>(
+        replacement,
+        preceded_by_cr,
+        end == normalized.len(),
+        &mut output,
+    );
+
+    // Bytes after the edit are normalized already, except when the replacement ended with a
+    // dangling `\r` or when the edit separated a `\n` from the `\r` it belonged to.
+    let mut old_end = end;
+    while old_end < normalized.len() && (state || (old_end == end && normalized[end] == LF)) {
+        let is_last_chunk = old_end + 1 == normalized.len();
+        state = normalize_into::<N>(
+            &normalized[old_end..=old_end],
+            state,
+            is_last_chunk,
+            &mut output,
+        );
+        old_end += 1;
+    }
+
+    let new_end = start + output.len();
+    normalized.splice(start..old_end, output);
+    NormalizedEdit {
+        old: start..old_end,
+        new: start..new_end,
+    }
+}
+
+/// Normalize `input` and append the result to `output`, returning the new state.
+fn normalize_into<N: NormalizeChunk<State = bool>>(
+    input: &[u8],
+    preceded_by_cr: bool,
+    is_last_chunk: bool,
+    output: &mut Vec<u8>,
+) -> bool {
+    output.reserve(N::max_output_size_for_chunk(
+        input.len(),
+        Some(&preceded_by_cr),
+        is_last_chunk,
+    ));
+    let status = N::normalize_chunk(
+        input,
+        output.spare_capacity_mut(),
+        Some(&preceded_by_cr),
+        is_last_chunk,
+    )
+    .unwrap_or_else(|err| unreachable!("{err} (should be impossible)"));
+
+    // SAFETY: We trust that the implementation of normalize_chunk correctly reports the number
+    // of bytes it initialized.
+    unsafe {
+        output.set_len(output.len() + status.output_len());
+    }
+    status.state().copied().unwrap_or(false)
+}
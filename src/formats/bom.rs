@@ -0,0 +1,290 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        normalize_adapter_at_boundary, normalize_into, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    Error, Result,
+};
+
+/// A byte order mark at the start of a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bom {
+    /// `EF BB BF`, the UTF-8 BOM.
+    Utf8,
+    /// `FF FE`, the UTF-16 little-endian BOM.
+    Utf16Le,
+    /// `FE FF`, the UTF-16 big-endian BOM.
+    Utf16Be,
+}
+
+impl Bom {
+    const ALL: [Bom; 3] = [Bom::Utf8, Bom::Utf16Le, Bom::Utf16Be];
+
+    /// Returns the bytes making up the BOM.
+    #[must_use]
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Utf8 => b"\xEF\xBB\xBF",
+            Self::Utf16Le => b"\xFF\xFE",
+            Self::Utf16Be => b"\xFE\xFF",
+        }
+    }
+
+    /// Returns the BOM `buf` starts with, if any.
+    ///
+    /// ```
+    /// use eolify::Bom;
+    ///
+    /// assert_eq!(Bom::detect(b"\xEF\xBB\xBFtext"), Some(Bom::Utf8));
+    /// assert_eq!(Bom::detect(b"\xFF\xFEt\0"), Some(Bom::Utf16Le));
+    /// assert_eq!(Bom::detect(b"text"), None);
+    /// ```
+    #[must_use]
+    pub fn detect(buf: &[u8]) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|bom| buf.starts_with(bom.as_bytes()))
+    }
+
+    /// Whether `buf` is too short to tell, i.e. it is a proper prefix of a BOM.
+    fn is_incomplete(buf: &[u8]) -> bool {
+        Self::ALL
+            .into_iter()
+            .any(|bom| bom.as_bytes().len() > buf.len() && bom.as_bytes().starts_with(buf))
+    }
+}
+
+/// The most bytes held back at the start of a stream, one less than the longest BOM.
+const MAX_HELD: usize = 2;
+
+/// Format adapter that strips a UTF-8 or UTF-16 byte order mark from the start of the stream
+/// and normalizes the rest with the wrapped format.
+///
+/// Up to two bytes at the very start of the stream are held back until it is clear whether they
+/// start a BOM. Without this adapter a BOM is kept, as the formats pass it through unchanged;
+/// use [`AddBom`] to make sure the output starts with one.
+///
+/// Only the BOM itself is handled: the rest of the stream is normalized byte by byte, so
+/// UTF-16 content must not go through this adapter, as its line endings would be rewritten as
+/// single bytes. Transcode it to UTF-8 first.
+///
+/// ```
+/// use eolify::{Normalize, StripBom, CRLF};
+///
+/// assert_eq!(StripBom::<CRLF>::normalize(b"\xEF\xBB\xBFone\ntwo"), b"one\r\ntwo");
+/// assert_eq!(StripBom::<CRLF>::normalize(b"\xEF\xBBnot a BOM"), b"\xEF\xBBnot a BOM");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StripBom<N>(pub N);
+
+/// Format adapter that makes sure the output starts with a UTF-8 byte order mark, inserting one
+/// unless the stream already starts with a UTF-8 or UTF-16 BOM, and normalizes the rest with
+/// the wrapped format.
+///
+/// An empty stream stays empty. Up to two bytes at the very start of the stream are held back
+/// until it is clear whether they start a BOM.
+///
+/// Like [`StripBom`], this only handles the BOM itself and normalizes the rest byte by byte, so
+/// UTF-16 content must not go through this adapter either.
+///
+/// ```
+/// use eolify::{AddBom, Normalize, CRLF};
+///
+/// assert_eq!(AddBom::<CRLF>::normalize(b"one\n"), b"\xEF\xBB\xBFone\r\n");
+/// assert_eq!(AddBom::<CRLF>::normalize(b"\xEF\xBB\xBFone\n"), b"\xEF\xBB\xBFone\r\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AddBom<N>(pub N);
+
+/// State carried between chunks by [`StripBom`] and [`AddBom`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BomState<S> {
+    inner: Option<S>,
+    /// Bytes at the start of the stream that were held back, or `None` once past the start.
+    held: Option<([u8; MAX_HELD], usize)>,
+}
+
+impl<S> Default for BomState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            held: Some(([0; MAX_HELD], 0)),
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for StripBom<N> {
+    type State = BomState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        max_output_size::<N>(chunk_size, state, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_chunk::<N>(input, output, state, is_last_chunk, false)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for AddBom<N> {
+    type State = BomState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        max_output_size::<N>(chunk_size, state, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_chunk::<N>(input, output, state, is_last_chunk, true)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+fn max_output_size<N: NormalizeChunk>(
+    chunk_size: usize,
+    state: Option<&BomState<N::State>>,
+    is_last_chunk: bool,
+) -> usize {
+    // The BOM, held back bytes and the chunk itself are each normalized separately.
+    let inner = state.and_then(|state| state.inner.as_ref());
+    N::max_output_size_for_chunk(Bom::Utf8.as_bytes().len(), inner, false)
+        .saturating_add(N::max_output_size_for_chunk(MAX_HELD, inner, false))
+        .saturating_add(N::max_output_size_for_chunk(
+            chunk_size,
+            inner,
+            is_last_chunk,
+        ))
+}
+
+fn normalize_chunk<N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    state: Option<&BomState<N::State>>,
+    is_last_chunk: bool,
+    add: bool,
+) -> Result<NormalizeChunkResult<BomState<N::State>>> {
+    let output_required = max_output_size::<N>(input.len(), state, is_last_chunk);
+    if output.len() < output_required {
+        return Err(Error::OutputBufferTooSmall {
+            required: output_required,
+        });
+    }
+
+    let mut state = state.cloned().unwrap_or_default();
+    let mut input = input;
+    let mut write_pos = 0;
+
+    if let Some((held, held_len)) = state.held.take() {
+        // Look at the held back bytes together with enough of the input to complete any BOM.
+        let mut start = [0u8; 2 * MAX_HELD + 1];
+        let taken = input.len().min(MAX_HELD + 1);
+        start[..held_len].copy_from_slice(&held[..held_len]);
+        start[held_len..held_len + taken].copy_from_slice(&input[..taken]);
+        let start = &start[..held_len + taken];
+
+        if let Some(bom) = Bom::detect(start) {
+            // We found:
+            // - a complete BOM, keep it only when adding one
+            if add {
+                normalize_into::<N>(
+                    bom.as_bytes(),
+                    output,
+                    &mut write_pos,
+                    &mut state.inner,
+                    false,
+                )?;
+            }
+            input = &input[bom.as_bytes().len() - held_len..];
+        } else if Bom::is_incomplete(start) && !is_last_chunk {
+            // We found:
+            // - the start of a BOM at the end of the chunk, which means all of the input was
+            //   taken; hold it back
+            let mut held = [0; MAX_HELD];
+            held[..start.len()].copy_from_slice(start);
+            state.held = Some((held, start.len()));
+            return Ok(NormalizeChunkResult::new(0, Some(state)));
+        } else if start.is_empty() && !is_last_chunk {
+            // We found:
+            // - no data yet
+            state.held = Some((held, held_len));
+            return Ok(NormalizeChunkResult::new(0, Some(state)));
+        } else {
+            // We found:
+            // - content without BOM, or no content at all; held back bytes are content
+            if add && !start.is_empty() {
+                let bom = Bom::Utf8.as_bytes();
+                normalize_into::<N>(bom, output, &mut write_pos, &mut state.inner, false)?;
+            }
+            normalize_into::<N>(
+                &held[..held_len],
+                output,
+                &mut write_pos,
+                &mut state.inner,
+                false,
+            )?;
+        }
+    }
+
+    normalize_into::<N>(
+        input,
+        output,
+        &mut write_pos,
+        &mut state.inner,
+        is_last_chunk,
+    )?;
+    Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+}
+
+// SAFETY: Only a complete BOM is dropped or inserted, and the UTF-8 BOM is valid UTF-8; all
+// other input passes through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for StripBom<N> {}
+
+// SAFETY: See `StripBom`.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for AddBom<N> {}
@@ -0,0 +1,240 @@
+//! Shared machinery for formats recognizing line breaks beyond `\r\n`, `\n` and `\r`, such as
+//! the Unicode separators.
+
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{normalize_into, NormalizeChunk},
+    types::CR,
+    types::LF,
+    Result,
+};
+
+/// The longest break sequence a [`BreakSet`] may contain.
+pub(crate) const MAX_SEQUENCE_LEN: usize = 3;
+
+/// A set of byte sequences that are recognized as line breaks.
+pub(crate) trait BreakSet {
+    /// The recognized sequences, each at most [`MAX_SEQUENCE_LEN`] bytes long. Empty entries
+    /// are ignored. Where sequences overlap, the longest one wins.
+    const SEQUENCES: &'static [&'static [u8]];
+
+    /// Whether a byte may start one of the sequences.
+    const STARTS: [bool; 256] = start_table(Self::SEQUENCES);
+
+    /// Whether a sequence is shorter than the `\r\n` it is passed on as.
+    const HAS_SINGLE_BYTE_SEQUENCE: bool = has_single_byte_sequence(Self::SEQUENCES);
+}
+
+const fn start_table(sequences: &[&[u8]]) -> [bool; 256] {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < sequences.len() {
+        if !sequences[i].is_empty() {
+            table[sequences[i][0] as usize] = true;
+        }
+        i += 1;
+    }
+    table
+}
+
+const fn has_single_byte_sequence(sequences: &[&[u8]]) -> bool {
+    let mut i = 0;
+    while i < sequences.len() {
+        if sequences[i].len() == 1 {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// The start of a break sequence held back at the end of a chunk, at most two bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Pending {
+    bytes: [u8; MAX_SEQUENCE_LEN - 1],
+    len: u8,
+}
+
+impl Pending {
+    fn new(bytes: &[u8]) -> Self {
+        let mut pending = Self::default();
+        pending.bytes[..bytes.len()].copy_from_slice(bytes);
+        pending.len = bytes.len() as u8;
+        pending
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..usize::from(self.len)]
+    }
+}
+
+/// A piece of the input as reported by [`scan`].
+pub(crate) enum Piece<'a> {
+    /// Bytes that are not part of a break sequence.
+    Text(&'a [u8]),
+    /// A complete break sequence.
+    Break,
+}
+
+struct Match {
+    /// The length of the longest sequence found, or zero.
+    len: usize,
+    /// Whether `buf` is a proper prefix of a sequence, i.e. a longer match might follow.
+    incomplete: bool,
+}
+
+fn match_at<B: BreakSet>(buf: &[u8]) -> Match {
+    let mut found = Match {
+        len: 0,
+        incomplete: false,
+    };
+    for sequence in B::SEQUENCES.iter().filter(|sequence| !sequence.is_empty()) {
+        if buf.starts_with(sequence) {
+            found.len = found.len.max(sequence.len());
+        } else if sequence.starts_with(buf) {
+            found.incomplete = true;
+        }
+    }
+    found
+}
+
+/// Split `input`, preceded by the `pending` bytes of the previous chunk, into text and breaks
+/// and pass them to `emit` in order. Text pieces are never empty.
+///
+/// Unless this is the last chunk, a possible start of a break sequence at the end of `input`
+/// is held back in `pending` instead of being passed on.
+pub(crate) fn scan<B: BreakSet>(
+    input: &[u8],
+    pending: &mut Pending,
+    is_last_chunk: bool,
+    mut emit: impl FnMut(Piece<'_>) -> Result<()>,
+) -> Result<()> {
+    let mut text_start = 0;
+
+    if pending.len > 0 {
+        // Resolve the held back bytes together with enough of the input to complete any
+        // sequence they might start.
+        let held = *pending;
+        *pending = Pending::default();
+        let taken = input.len().min(MAX_SEQUENCE_LEN - 1);
+        let mut bridge = [0u8; 2 * (MAX_SEQUENCE_LEN - 1)];
+        bridge[..held.as_slice().len()].copy_from_slice(held.as_slice());
+        bridge[held.as_slice().len()..][..taken].copy_from_slice(&input[..taken]);
+        let bridge = &bridge[..held.as_slice().len() + taken];
+
+        let mut pos = 0;
+        while pos < held.as_slice().len() {
+            let found = match_at::<B>(&bridge[pos..]);
+            if found.incomplete && !is_last_chunk {
+                // We found:
+                // - still only the start of a sequence, which means all of the input was
+                //   taken
+                *pending = Pending::new(&bridge[pos..]);
+                return Ok(());
+            }
+            if found.len > 0 {
+                emit(Piece::Break)?;
+                pos += found.len;
+            } else {
+                emit(Piece::Text(&bridge[pos..=pos]))?;
+                pos += 1;
+            }
+        }
+        text_start = pos - held.as_slice().len();
+    }
+
+    let mut scan_pos = text_start;
+    while let Some(i) = input[scan_pos..]
+        .iter()
+        .position(|&b| B::STARTS[usize::from(b)])
+        .map(|i| i + scan_pos)
+    {
+        let found = match_at::<B>(&input[i..]);
+        if found.incomplete && !is_last_chunk {
+            // We found:
+            // - the start of a sequence at the end of the chunk, hold it back until we know
+            //   what follows
+            if text_start < i {
+                emit(Piece::Text(&input[text_start..i]))?;
+            }
+            *pending = Pending::new(&input[i..]);
+            return Ok(());
+        }
+        if found.len > 0 {
+            if text_start < i {
+                emit(Piece::Text(&input[text_start..i]))?;
+            }
+            emit(Piece::Break)?;
+            text_start = i + found.len;
+            scan_pos = text_start;
+        } else {
+            scan_pos = i + 1;
+        }
+    }
+
+    if text_start < input.len() {
+        emit(Piece::Text(&input[text_start..]))?;
+    }
+    Ok(())
+}
+
+/// Resolve a `\r` held back in `pending` at a soft boundary, passing it to `emit` like [`scan`]
+/// does at the end of the stream.
+///
+/// The start of a longer sequence stays held back, since only the bytes that follow can tell
+/// what it is.
+pub(crate) fn resolve_cr<B: BreakSet>(
+    pending: &mut Pending,
+    emit: impl FnMut(Piece<'_>) -> Result<()>,
+) -> Result<()> {
+    if pending.as_slice() == [CR] {
+        scan::<B>(&[], pending, true, emit)?;
+    }
+    Ok(())
+}
+
+/// The worst-case output size of an adapter passing the text and breaks found by `B` on to
+/// `N`, with breaks replaced by `\r\n`.
+pub(crate) fn max_output_size_with<B: BreakSet, N: NormalizeChunk>(
+    chunk_size: usize,
+    inner: Option<&N::State>,
+    is_last_chunk: bool,
+) -> usize {
+    // Up to two held back bytes may be passed on along with this chunk, and a single byte
+    // sequence grows to two bytes.
+    let fed = chunk_size.saturating_add(MAX_SEQUENCE_LEN - 1);
+    let fed = if B::HAS_SINGLE_BYTE_SEQUENCE {
+        fed.saturating_mul(2)
+    } else {
+        fed
+    };
+    N::max_output_size_for_chunk(fed, inner, is_last_chunk)
+}
+
+/// Normalize `input` with `N`, additionally treating the sequences of `B` as line breaks.
+///
+/// Breaks are passed on to `N` as `\r\n`, which `N` sees as exactly one line ending whatever
+/// surrounds it: a `\r` before it forms a line ending of its own, as does an `\n` after it.
+/// Returns the number of bytes written to `output`.
+pub(crate) fn normalize_chunk_with<B: BreakSet, N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    inner: &mut Option<N::State>,
+    pending: &mut Pending,
+    is_last_chunk: bool,
+) -> Result<usize> {
+    let mut write_pos = 0;
+    scan::<B>(input, pending, is_last_chunk, |piece| {
+        let bytes = match piece {
+            Piece::Text(text) => text,
+            Piece::Break => &[CR, LF],
+        };
+        normalize_into::<N>(bytes, output, &mut write_pos, inner, false)
+    })?;
+    if is_last_chunk {
+        normalize_into::<N>(&[], output, &mut write_pos, inner, true)?;
+    }
+    Ok(write_pos)
+}
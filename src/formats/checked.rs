@@ -0,0 +1,124 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{ChunkEnd, NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    helpers::vec_to_uninit_mut,
+    Result,
+};
+
+/// Format adapter that verifies the wrapped format is idempotent while it normalizes.
+///
+/// In debug builds every normalized chunk is normalized a second time, continuing the state
+/// of the previous check, and the adapter panics if that changes the output. This catches
+/// state-machine bugs in custom formats during integration testing. In release builds the
+/// check is skipped and the adapter only forwards to the wrapped format.
+///
+/// The check assumes the wrapped format emits its output without holding back bytes for
+/// later chunks, which is true for all formats in this crate.
+///
+/// ```
+/// use std::io::Read;
+/// use eolify::{Checked, ReadExt, CRLF};
+///
+/// let mut out = String::new();
+/// b"a\nb\r\nc".as_ref()
+///     .normalize_newlines(Checked(CRLF))
+///     .read_to_string(&mut out)
+///     .unwrap();
+/// assert_eq!(out, "a\r\nb\r\nc");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Checked<N>(pub N);
+
+impl<N: NormalizeChunk> NormalizeChunk for Checked<N> {
+    /// The state of the wrapped format and the state of the verifying second pass.
+    type State = (Option<N::State>, Option<N::State>);
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        N::max_output_size_for_chunk(
+            chunk_size,
+            state.and_then(|(inner, _)| inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_checked::<N>(
+            input,
+            output,
+            state,
+            ChunkEnd::from_is_last_chunk(is_last_chunk),
+        )
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_checked::<N>(input, output, state, ChunkEnd::Boundary)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+/// Normalize a chunk ending at `end` with `N`, checking the output as described for [`Checked`].
+fn normalize_checked<N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    state: Option<&<Checked<N> as NormalizeChunk>::State>,
+    end: ChunkEnd,
+) -> Result<NormalizeChunkResult<<Checked<N> as NormalizeChunk>::State>> {
+    let (inner_state, mut check_state) = state.cloned().unwrap_or((None, None));
+    let status = end.normalize::<N>(input, output, inner_state.as_ref())?;
+
+    if cfg!(debug_assertions) {
+        // SAFETY: We trust that the implementation of normalize_chunk correctly reports the
+        // number of bytes it initialized.
+        let normalized = unsafe {
+            std::slice::from_raw_parts(output.as_ptr().cast::<u8>(), status.output_len())
+        };
+
+        let mut renormalized =
+            Vec::with_capacity(end.max_output_size::<N>(normalized.len(), check_state.as_ref()));
+        let check = end.normalize::<N>(
+            normalized,
+            vec_to_uninit_mut(&mut renormalized),
+            check_state.as_ref(),
+        )?;
+        // SAFETY: See above.
+        unsafe {
+            renormalized.set_len(check.output_len());
+        }
+        assert!(
+            renormalized == normalized,
+            "normalization is not idempotent: chunk \"{}\" normalized to \"{}\", which \
+             normalized again to \"{}\"",
+            input.escape_ascii(),
+            normalized.escape_ascii(),
+            renormalized.escape_ascii(),
+        );
+        check_state = check.state().cloned();
+    }
+
+    Ok(NormalizeChunkResult::new(
+        status.output_len(),
+        Some((status.state().cloned(), check_state)),
+    ))
+}
+
+// SAFETY: The output is the output of the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for Checked<N> {}
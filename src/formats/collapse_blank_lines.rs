@@ -0,0 +1,166 @@
+use std::mem::MaybeUninit;
+
+use memchr::memchr2_iter;
+
+use crate::{
+    formats::{
+        normalize_adapter_at_boundary, normalize_into, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Format adapter that limits runs of consecutive line endings to `MAX`, dropping the rest.
+///
+/// `MAX` counts line endings, not blank lines: with `MAX = 2` at most one blank line is left
+/// between two lines of text, with `MAX = 1` all blank lines are removed. Only empty lines are
+/// blank; a line containing whitespace ends a run. Runs straddling chunks are tracked in the
+/// state, and the line endings that are kept pass through the wrapped format unchanged.
+///
+/// ```
+/// use eolify::{CollapseBlankLines, Normalize, CRLF, LF};
+///
+/// assert_eq!(
+///     CollapseBlankLines::<LF, 2>::normalize(b"a\r\n\r\n\r\n\r\nb\n\nc"),
+///     b"a\n\nb\n\nc"
+/// );
+/// assert_eq!(CollapseBlankLines::<CRLF, 1>::normalize(b"a\n\n\rb"), b"a\r\nb");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CollapseBlankLines<N, const MAX: usize>(pub N);
+
+/// State carried between chunks by [`CollapseBlankLines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollapseBlankLinesState<S> {
+    inner: Option<S>,
+    /// The number of consecutive line endings at the end of the previous chunk.
+    run: usize,
+    /// Whether the previous chunk ended with a `\r`, which an `\n` may still complete.
+    ends_with_cr: bool,
+    /// Whether the last line ending was passed on to the wrapped format.
+    last_kept: bool,
+}
+
+impl<S> Default for CollapseBlankLinesState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            run: 0,
+            ends_with_cr: false,
+            last_kept: false,
+        }
+    }
+}
+
+impl<N: NormalizeChunk, const MAX: usize> NormalizeChunk for CollapseBlankLines<N, MAX> {
+    type State = CollapseBlankLinesState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // The kept parts of the chunk and the final call to the wrapped format are normalized
+        // separately.
+        let inner = state.and_then(|state| state.inner.as_ref());
+        N::max_output_size_for_chunk(chunk_size, inner, false)
+            .saturating_add(N::max_output_size_for_chunk(0, inner, is_last_chunk))
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+        // The start of the input not yet passed on, and the end of the last line ending seen.
+        let mut kept_start = 0;
+        let mut eol_end = 0;
+
+        for i in memchr2_iter(CR, LF, input) {
+            let completes_crlf = input[i] == LF
+                && if i == 0 {
+                    state.ends_with_cr
+                } else {
+                    input[i - 1] == CR
+                };
+            let keep = if completes_crlf {
+                // We found:
+                // - the LF of a CRLF, which goes wherever its CR went
+                state.last_kept
+            } else {
+                if i > eol_end {
+                    // Text since the last line ending ends the run.
+                    state.run = 0;
+                }
+                state.run = state.run.saturating_add(1);
+                state.last_kept = state.run <= MAX;
+                state.last_kept
+            };
+            if !keep {
+                normalize_into::<N>(
+                    &input[kept_start..i],
+                    output,
+                    &mut write_pos,
+                    &mut state.inner,
+                    false,
+                )?;
+                kept_start = i + 1;
+            }
+            eol_end = i + 1;
+        }
+        normalize_into::<N>(
+            &input[kept_start..],
+            output,
+            &mut write_pos,
+            &mut state.inner,
+            false,
+        )?;
+
+        if let Some(&last) = input.last() {
+            if eol_end < input.len() {
+                state.run = 0;
+            }
+            state.ends_with_cr = last == CR;
+        }
+        if is_last_chunk {
+            normalize_into::<N>(&[], output, &mut write_pos, &mut state.inner, true)?;
+        }
+
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| {
+            // A `\r` at the boundary is a complete line ending, so an `\n` starting the next
+            // chunk is a line ending of its own.
+            state.ends_with_cr = false;
+            &mut state.inner
+        })
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+// SAFETY: Only ASCII line endings are dropped; everything else passes through the wrapped
+// format.
+unsafe impl<N: PreservesUtf8, const MAX: usize> PreservesUtf8 for CollapseBlankLines<N, MAX> {}
@@ -0,0 +1,206 @@
+use std::mem::MaybeUninit;
+
+use memchr::memchr;
+
+use crate::{
+    formats::{
+        normalize_at_boundary_into, normalize_into, ChunkEnd, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Format adapter that repairs `\r\r\n` sequences, collapsing them into a single line ending.
+///
+/// A `\r\r\n` is the typical artifact of converting `\n` to `\r\n` twice. By default the
+/// formats treat the first `\r` as a line ending of its own, adding a blank line; wrapped in
+/// this adapter a `\r` directly followed by `\r\n` is dropped instead. Everything else is left
+/// to the wrapped format.
+///
+/// ```
+/// use eolify::{CollapseCrCrLf, Normalize, CRLF, LF};
+///
+/// assert_eq!(CRLF::normalize(b"a\r\r\nb"), b"a\r\n\r\nb");
+/// assert_eq!(CollapseCrCrLf::<CRLF>::normalize(b"a\r\r\nb"), b"a\r\nb");
+/// assert_eq!(CollapseCrCrLf::<LF>::normalize(b"a\r\r\nb\r\rc"), b"a\nb\n\nc");
+/// assert_eq!(CollapseCrCrLf::<LF>::normalize(b"a\r\r\r\nb"), b"a\n\nb");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CollapseCrCrLf<N>(pub N);
+
+/// State carried between chunks by [`CollapseCrCrLf`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollapseCrCrLfState<S> {
+    inner: Option<S>,
+    /// The number of `\r` bytes at the end of the previous chunks that were held back, at most
+    /// two.
+    pending_crs: usize,
+}
+
+impl<S> Default for CollapseCrCrLfState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            pending_crs: 0,
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for CollapseCrCrLf<N> {
+    type State = CollapseCrCrLfState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // Up to two held back `\r` bytes may be passed on along with this chunk.
+        N::max_output_size_for_chunk(
+            chunk_size.saturating_add(2),
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+        let mut segment_start = 0;
+
+        while state.pending_crs > 0 {
+            match input.get(segment_start) {
+                None if !is_last_chunk => {
+                    return Ok(NormalizeChunkResult::new(write_pos, Some(state)));
+                }
+                Some(&CR) => {
+                    if state.pending_crs == 2 {
+                        // We found:
+                        // - a third `\r`, so the first held back one is not part of a
+                        //   `\r\r\n`
+                        feed_crs::<N>(1, output, &mut write_pos, &mut state)?;
+                    }
+                    state.pending_crs = 2;
+                    segment_start += 1;
+                }
+                Some(&LF) => {
+                    // We found:
+                    // - the `\n` completing a `\r\n` or `\r\r\n` split across chunks, which
+                    //   leaves a single `\r` either way
+                    state.pending_crs = 0;
+                    feed_crs::<N>(1, output, &mut write_pos, &mut state)?;
+                }
+                _ => {
+                    // We found:
+                    // - held back `\r` bytes that are not followed by `\n`
+                    let pending_crs = std::mem::take(&mut state.pending_crs);
+                    feed_crs::<N>(pending_crs, output, &mut write_pos, &mut state)?;
+                }
+            }
+        }
+
+        let mut scan_pos = segment_start;
+        while let Some(i) = memchr(CR, &input[scan_pos..]).map(|i| i + scan_pos) {
+            match (input.get(i + 1), input.get(i + 2)) {
+                (Some(&CR), Some(&LF)) => {
+                    // We found:
+                    // - a `\r\r\n`, drop the first `\r`
+                    normalize_into::<N>(
+                        &input[segment_start..i],
+                        output,
+                        &mut write_pos,
+                        &mut state.inner,
+                        false,
+                    )?;
+                    segment_start = i + 1;
+                }
+                (None | Some(&CR), None) if !is_last_chunk => {
+                    // We found:
+                    // - one or two `\r` bytes at the end of the chunk, hold them back until we
+                    //   know what follows
+                    normalize_into::<N>(
+                        &input[segment_start..i],
+                        output,
+                        &mut write_pos,
+                        &mut state.inner,
+                        false,
+                    )?;
+                    state.pending_crs = input.len() - i;
+                    return Ok(NormalizeChunkResult::new(write_pos, Some(state)));
+                }
+                _ => {}
+            }
+            scan_pos = i + 1;
+        }
+
+        normalize_into::<N>(
+            &input[segment_start..],
+            output,
+            &mut write_pos,
+            &mut state.inner,
+            is_last_chunk,
+        )?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = ChunkEnd::Boundary.max_output_size::<Self>(input.len(), state);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let status = Self::normalize_chunk(input, output, state, false)?;
+        let mut state = status.state().cloned().unwrap_or_default();
+        let mut write_pos = status.output_len();
+        // We found:
+        // - held back `\r` bytes at the boundary, which no `\n` follows
+        let pending_crs = std::mem::take(&mut state.pending_crs);
+        feed_crs::<N>(pending_crs, output, &mut write_pos, &mut state)?;
+        normalize_at_boundary_into::<N>(output, &mut write_pos, &mut state.inner)?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+/// Pass `count` `\r` bytes to the wrapped format.
+fn feed_crs<N: NormalizeChunk>(
+    count: usize,
+    output: &mut [MaybeUninit<u8>],
+    write_pos: &mut usize,
+    state: &mut CollapseCrCrLfState<N::State>,
+) -> Result<()> {
+    normalize_into::<N>(
+        &[CR, CR][..count],
+        output,
+        write_pos,
+        &mut state.inner,
+        false,
+    )
+}
+
+// SAFETY: Only ASCII `\r` bytes are dropped; everything else passes through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for CollapseCrCrLf<N> {}
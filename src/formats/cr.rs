@@ -0,0 +1,148 @@
+use std::mem::MaybeUninit;
+
+use memchr::{memchr, memchr2, memchr_iter};
+
+use crate::{
+    formats::{copy, NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    types, Error, Result,
+};
+
+/// CR normalization format implementation, for classic Mac OS and devices that expect lines
+/// terminated by a lone `\r`.
+///
+/// Will convert all line endings that are not CR (i.e. CRLF or LF alone) into CR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CR;
+
+impl CR {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, _is_last_chunk: bool) -> usize {
+        chunk_size
+    }
+}
+
+impl NormalizeChunk for CR {
+    type State = bool;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Self::max_output_size(chunk_size, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        if input.is_empty() {
+            // A CR at the end of the previous chunk may still be followed by an LF, unless
+            // this is the last chunk.
+            return Ok(NormalizeChunkResult::new(
+                0,
+                Some(preceded_by_cr && !is_last_chunk),
+            ));
+        }
+
+        let mut read_pos = 0;
+        let mut write_pos = 0;
+
+        if input[0] == types::LF && preceded_by_cr {
+            // We found:
+            // - a LF preceded by a CR from the previous chunk
+            // The CR was already written, so this LF can be skipped.
+            read_pos = 1;
+        }
+
+        let mut scan_pos = read_pos;
+        while let Some(i) = memchr2(types::CR, types::LF, &input[scan_pos..]).map(|i| i + scan_pos)
+        {
+            if input[i] == types::LF {
+                // We found:
+                // - a lone LF, replace it with a CR
+                copy(&input[read_pos..i], output, &mut write_pos);
+                copy(&[types::CR], output, &mut write_pos);
+                read_pos = i + 1;
+                scan_pos = i + 1;
+            } else if input.get(i + 1) == Some(&types::LF) {
+                // We found:
+                // - a CRLF, keep the CR and drop the LF
+                copy(&input[read_pos..=i], output, &mut write_pos);
+                read_pos = i + 2;
+                scan_pos = i + 2;
+            } else {
+                // We found:
+                // - a lone CR, or a CR at the end of the chunk, which is already what we want
+                scan_pos = i + 1;
+            }
+        }
+
+        copy(&input[read_pos..], output, &mut write_pos);
+        let ends_with_cr = input.last() == Some(&types::CR);
+        Ok(NormalizeChunkResult::new(
+            write_pos,
+            Some(ends_with_cr && !is_last_chunk),
+        ))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        // Resolving a trailing `\r` is all the end of the stream does for this format.
+        Self::normalize_chunk(input, output, state, true)
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        memchr(types::LF, input).is_some()
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        // Mirrors normalize_chunk, but only counts the bytes it would drop.
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        let mut output_len = input.len();
+        if input.first() == Some(&types::LF) && preceded_by_cr {
+            output_len -= 1;
+        }
+
+        let crlf_count = memchr_iter(types::CR, input)
+            .filter(|&i| input.get(i + 1) == Some(&types::LF))
+            .count();
+        output_len -= crlf_count;
+
+        let ends_with_cr = match input.last() {
+            Some(&c) => c == types::CR,
+            None => preceded_by_cr,
+        };
+        NormalizeChunkResult::new(output_len, Some(ends_with_cr && !is_last_chunk))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+// SAFETY: Only the ASCII bytes `\r` and `\n` are removed or replaced.
+unsafe impl PreservesUtf8 for CR {}
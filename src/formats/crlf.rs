@@ -1,9 +1,9 @@
 use std::{mem::MaybeUninit, ptr};
 
-use memchr::memchr2;
+use memchr::{memchr2, memchr2_iter};
 
 use crate::{
-    formats::{NormalizeChunk, NormalizeChunkResult},
+    formats::{NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
     types::{CR, LF},
     Error, Result,
 };
@@ -11,8 +11,28 @@ use crate::{
 /// CRLF normalization format implementation.
 ///
 /// Will convert all line endings that are not CRLF (i.e. LF or CR alone) into CRLF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct CRLF;
 
+impl CRLF {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    ///
+    /// ```
+    /// use eolify::CRLF;
+    ///
+    /// const OUTPUT_SIZE: usize = CRLF::max_output_size(1024, true);
+    /// let output = [0u8; OUTPUT_SIZE];
+    /// assert_eq!(output.len(), 2049);
+    /// ```
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, is_last_chunk: bool) -> usize {
+        chunk_size
+            .saturating_mul(2)
+            .saturating_add(is_last_chunk as usize)
+    }
+}
+
 impl NormalizeChunk for CRLF {
     type State = bool;
 
@@ -37,7 +57,11 @@ impl NormalizeChunk for CRLF {
         // look at `is_last_chunk`. We could just require 2n + 1 bytes always, but that would be surprising for
         // callers that intuitively expect 2n to be sufficient in all cases, or at least when not processing the
         // last chunk.
-        chunk_size * 2 + usize::from(is_last_chunk)
+        //
+        // The arithmetic saturates so that absurdly large chunk sizes (only possible on 32-bit
+        // targets or when the size doesn't come from an actual slice) report `usize::MAX`, which
+        // no buffer can satisfy, instead of wrapping around to a small number.
+        Self::max_output_size(chunk_size, is_last_chunk)
     }
 
     fn normalize_chunk(
@@ -153,4 +177,70 @@ impl NormalizeChunk for CRLF {
             }
         }
     }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        // Resolving a trailing `\r` is all the end of the stream does for this format.
+        Self::normalize_chunk(input, output, state, true)
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        let mut eols = memchr2_iter(CR, LF, input);
+        while let Some(i) = eols.next() {
+            // Every LF has to be consumed as part of a CRLF below.
+            if input[i] == LF || input.get(i + 1) != Some(&LF) {
+                return true;
+            }
+            eols.next();
+        }
+        false
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        // Mirrors normalize_chunk, but only counts the bytes it would insert.
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        if input.is_empty() && !is_last_chunk {
+            return NormalizeChunkResult::new(0, Some(preceded_by_cr));
+        }
+
+        let mut output_len = input.len();
+        let mut scan_pos = 0;
+        if input.first() == Some(&LF) && preceded_by_cr {
+            scan_pos = 1;
+        } else if preceded_by_cr {
+            output_len += 1;
+        }
+
+        let mut ends_with_cr = false;
+        for i in memchr2_iter(CR, LF, &input[scan_pos..]).map(|i| i + scan_pos) {
+            match (input[i], input.get(i + 1).copied()) {
+                (CR, Some(LF)) => {}
+                (CR, None) => {
+                    ends_with_cr = true;
+                    output_len += usize::from(is_last_chunk);
+                }
+                (CR, Some(_)) => output_len += 1,
+                (_, _) => output_len += usize::from(i == 0 || input[i - 1] != CR),
+            }
+        }
+
+        NormalizeChunkResult::new(output_len, Some(ends_with_cr && !is_last_chunk))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
 }
+
+// SAFETY: Only the ASCII bytes `\r` and `\n` are inserted or replaced.
+unsafe impl PreservesUtf8 for CRLF {}
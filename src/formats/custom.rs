@@ -0,0 +1,197 @@
+use std::{marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    formats::{copy, NormalizeChunk, NormalizeChunkResult},
+    helpers::find_next_eol,
+    types, Error, Result,
+};
+
+/// The target sequence of a [`Custom`] format.
+///
+/// Implement this on a marker type to normalize line endings to an arbitrary byte string,
+/// such as the ASCII record separator or a sentinel token:
+///
+/// ```
+/// use eolify::{Custom, Delimiter, Normalize};
+///
+/// struct RecordSeparator;
+///
+/// impl Delimiter for RecordSeparator {
+///     const BYTES: &'static [u8] = b"\x1e";
+/// }
+///
+/// type Records = Custom<RecordSeparator>;
+///
+/// assert_eq!(Records::normalize(b"a\r\nb\nc\rd"), b"a\x1eb\x1ec\x1ed");
+/// ```
+///
+/// The `LF`, `CRLF` and `CR` formats implement it with their own line ending.
+pub trait Delimiter {
+    /// The bytes written in place of every line ending. May be empty to strip line endings.
+    const BYTES: &'static [u8];
+}
+
+impl Delimiter for crate::LF {
+    const BYTES: &'static [u8] = b"\n";
+}
+
+impl Delimiter for crate::CRLF {
+    const BYTES: &'static [u8] = b"\r\n";
+}
+
+impl Delimiter for crate::CR {
+    const BYTES: &'static [u8] = b"\r";
+}
+
+/// Normalization format writing the sequence of a [`Delimiter`] in place of every line ending.
+///
+/// `\r\n`, `\n` and lone `\r` are each replaced by `D::BYTES`. Everything else, including
+/// occurrences of the delimiter itself, passes through unchanged. When the delimiter contains
+/// `\r` or `\n` (e.g. `\r\r\n`) normalizing is therefore not idempotent: normalizing the
+/// output again replaces those line endings as well.
+///
+/// Since the output depends on `D`, `Custom` does not implement `PreservesUtf8`; use
+/// `normalize_str`, which validates the output, for text.
+pub struct Custom<D: Delimiter>(PhantomData<D>);
+
+impl<D: Delimiter> Custom<D> {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    ///
+    /// Every input byte may be a line ending of its own, so this is `chunk_size` times the
+    /// length of the delimiter, or `chunk_size` for delimiters shorter than two bytes.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, _is_last_chunk: bool) -> usize {
+        let expansion = if D::BYTES.len() > 1 {
+            D::BYTES.len()
+        } else {
+            1
+        };
+        chunk_size.saturating_mul(expansion)
+    }
+}
+
+// Implemented by hand so `D` doesn't need to implement the traits itself.
+impl<D: Delimiter> Clone for Custom<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D: Delimiter> Copy for Custom<D> {}
+
+impl<D: Delimiter> PartialEq for Custom<D> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<D: Delimiter> Eq for Custom<D> {}
+
+impl<D: Delimiter> Default for Custom<D> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: Delimiter> std::fmt::Debug for Custom<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Custom")
+            .field(&String::from_utf8_lossy(D::BYTES))
+            .finish()
+    }
+}
+
+impl<D: Delimiter> NormalizeChunk for Custom<D> {
+    type State = bool;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Self::max_output_size(chunk_size, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        if input.is_empty() {
+            // A CR at the end of the previous chunk may still be followed by an LF, unless
+            // this is the last chunk.
+            return Ok(NormalizeChunkResult::new(
+                0,
+                Some(preceded_by_cr && !is_last_chunk),
+            ));
+        }
+
+        let mut read_pos = 0;
+        let mut write_pos = 0;
+
+        if input[0] == types::LF && preceded_by_cr {
+            // We found:
+            // - a LF preceded by a CR from the previous chunk
+            // The delimiter was already written for the CR, so this LF can be skipped.
+            read_pos = 1;
+        }
+
+        // A CR at the end of the chunk is reported as a lone CR. Its delimiter is written right
+        // away and the state makes sure an LF starting the next chunk is skipped.
+        while let Some((i, terminator)) = find_next_eol(input, read_pos) {
+            copy(&input[read_pos..i], output, &mut write_pos);
+            copy(D::BYTES, output, &mut write_pos);
+            read_pos = i + terminator.len();
+        }
+
+        copy(&input[read_pos..], output, &mut write_pos);
+        let ends_with_cr = input.last() == Some(&types::CR);
+        Ok(NormalizeChunkResult::new(
+            write_pos,
+            Some(ends_with_cr && !is_last_chunk),
+        ))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        // Resolving a trailing `\r` is all the end of the stream does for this format.
+        Self::normalize_chunk(input, output, state, true)
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        // Mirrors normalize_chunk, but only counts the bytes it would write.
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        let mut read_pos = usize::from(input.first() == Some(&types::LF) && preceded_by_cr);
+        let mut output_len = 0;
+        while let Some((i, terminator)) = find_next_eol(input, read_pos) {
+            output_len += i - read_pos + D::BYTES.len();
+            read_pos = i + terminator.len();
+        }
+        output_len += input.len() - read_pos;
+
+        let ends_with_cr = match input.last() {
+            Some(&c) => c == types::CR,
+            None => preceded_by_cr,
+        };
+        NormalizeChunkResult::new(output_len, Some(ends_with_cr && !is_last_chunk))
+    }
+}
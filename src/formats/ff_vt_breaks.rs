@@ -0,0 +1,113 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        breaks::{self, BreakSet, Pending},
+        normalize_adapter_at_boundary, NormalizeChunk, NormalizeChunkResult, PreservesUtf8,
+    },
+    Error, Result,
+};
+
+/// Form feed.
+const FF: u8 = 0x0c;
+/// Vertical tab.
+const VT: u8 = 0x0b;
+
+struct FfVt;
+
+impl BreakSet for FfVt {
+    const SEQUENCES: &'static [&'static [u8]] = &[&[FF], &[VT]];
+}
+
+/// Format adapter that also treats form feed (`0x0C`) and vertical tab (`0x0B`) as line
+/// endings, converting them to the line ending of the wrapped format.
+///
+/// This matches the universal newlines of Python's `str.splitlines`, and suits text extracted
+/// from printer output and old reports, where a form feed starts a new page. Every form feed
+/// and vertical tab counts as exactly one line ending, also when directly preceded by `\r` or
+/// followed by `\n`.
+///
+/// ```
+/// use eolify::{FfVtBreaks, Normalize, CRLF, LF};
+///
+/// assert_eq!(FfVtBreaks::<LF>::normalize(b"page 1\x0cpage 2\x0b\r\n"), b"page 1\npage 2\n\n");
+/// assert_eq!(FfVtBreaks::<CRLF>::normalize(b"a\x0cb"), b"a\r\nb");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FfVtBreaks<N>(pub N);
+
+/// State carried between chunks by [`FfVtBreaks`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FfVtBreaksState<S> {
+    inner: Option<S>,
+    /// Always empty, as the breaks are single bytes; kept for the shared scanning machinery.
+    pending: Pending,
+}
+
+impl<S> Default for FfVtBreaksState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            pending: Pending::default(),
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for FfVtBreaks<N> {
+    type State = FfVtBreaksState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        breaks::max_output_size_with::<FfVt, N>(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let output_len = breaks::normalize_chunk_with::<FfVt, N>(
+            input,
+            output,
+            &mut state.inner,
+            &mut state.pending,
+            is_last_chunk,
+        )?;
+        Ok(NormalizeChunkResult::new(output_len, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+// SAFETY: Only the ASCII bytes FF and VT are replaced by ASCII bytes; everything else passes
+// through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for FfVtBreaks<N> {}
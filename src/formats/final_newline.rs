@@ -0,0 +1,116 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        normalize_adapter_at_boundary, normalize_into, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Format adapter that makes sure non-empty output ends with a line ending, appending the
+/// wrapped format's line ending at the end of the stream if the last line has none.
+///
+/// Only the end of the stream is looked at, so this adds no extra pass over the data. The
+/// appended line ending is produced by the wrapped format itself, so it is exactly one target
+/// line ending. Existing trailing line endings are left alone; use
+/// [`TrimTrailingNewlines`](crate::TrimTrailingNewlines) to limit them.
+///
+/// ```
+/// use eolify::{EnsureFinalNewline, Normalize, CRLF};
+///
+/// assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b"one\ntwo"), b"one\r\ntwo\r\n");
+/// assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b"one\r"), b"one\r\n");
+/// assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b""), b"");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EnsureFinalNewline<N>(pub N);
+
+/// State carried between chunks by [`EnsureFinalNewline`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnsureFinalNewlineState<S> {
+    inner: Option<S>,
+    /// Whether the stream has content that is not terminated by a line ending yet.
+    needs_line_ending: bool,
+}
+
+impl<S> Default for EnsureFinalNewlineState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            needs_line_ending: false,
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for EnsureFinalNewline<N> {
+    type State = EnsureFinalNewlineState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // The chunk and the final line ending are normalized separately.
+        let inner = state.and_then(|state| state.inner.as_ref());
+        N::max_output_size_for_chunk(chunk_size, inner, false)
+            .saturating_add(N::max_output_size_for_chunk(1, inner, is_last_chunk))
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+
+        if let Some(&last) = input.last() {
+            state.needs_line_ending = last != CR && last != LF;
+        }
+        if is_last_chunk && state.needs_line_ending {
+            // We found:
+            // - a last line without line ending
+            normalize_into::<N>(input, output, &mut write_pos, &mut state.inner, false)?;
+            normalize_into::<N>(&[LF], output, &mut write_pos, &mut state.inner, true)?;
+            state.needs_line_ending = false;
+        } else {
+            normalize_into::<N>(
+                input,
+                output,
+                &mut write_pos,
+                &mut state.inner,
+                is_last_chunk,
+            )?;
+        }
+
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+// SAFETY: Only an ASCII line ending is appended; all input passes through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for EnsureFinalNewline<N> {}
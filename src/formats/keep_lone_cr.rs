@@ -0,0 +1,178 @@
+use std::mem::MaybeUninit;
+
+use memchr::memchr;
+
+use crate::{
+    formats::{
+        copy, normalize_at_boundary_into, normalize_into, ChunkEnd, NormalizeChunk,
+        NormalizeChunkResult, PreservesUtf8,
+    },
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Format adapter that keeps lone `\r` bytes as data instead of treating them as line endings.
+///
+/// Only `\r\n` and `\n` are normalized by the wrapped format; a `\r` that is not followed by
+/// `\n` is copied unchanged. This suits inputs where a bare `\r` is content, such as progress
+/// bar output or spreadsheet exports with `\r` inside cells.
+///
+/// ```
+/// use eolify::{KeepLoneCr, Normalize, CRLF, LF};
+///
+/// assert_eq!(KeepLoneCr::<LF>::normalize(b"10%\r50%\r\ndone\r"), b"10%\r50%\ndone\r");
+/// assert_eq!(KeepLoneCr::<CRLF>::normalize(b"a\rb\nc"), b"a\rb\r\nc");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KeepLoneCr<N>(pub N);
+
+/// State carried between chunks by [`KeepLoneCr`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeepLoneCrState<S> {
+    inner: Option<S>,
+    /// Whether the previous chunk ended with a `\r` that was held back.
+    pending_cr: bool,
+}
+
+impl<S> Default for KeepLoneCrState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            pending_cr: false,
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for KeepLoneCr<N> {
+    type State = KeepLoneCrState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // Lone `\r` bytes are copied as-is and the rest goes through the wrapped format, which
+        // also gets to see a held back `\r`.
+        N::max_output_size_for_chunk(
+            chunk_size.saturating_add(1),
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+        .saturating_add(1)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+
+        if state.pending_cr {
+            if input.first() == Some(&LF) {
+                // We found:
+                // - the `\n` completing a `\r\n` split across chunks
+                normalize_into::<N>(b"\r", output, &mut write_pos, &mut state.inner, false)?;
+                state.pending_cr = false;
+            } else if !input.is_empty() || is_last_chunk {
+                // We found:
+                // - a lone `\r` at the end of the previous chunk
+                copy(&[CR], output, &mut write_pos);
+                state.pending_cr = false;
+            }
+        }
+
+        let mut segment_start = 0;
+        let mut scan_pos = 0;
+        while let Some(i) = memchr(CR, &input[scan_pos..]).map(|i| i + scan_pos) {
+            match input.get(i + 1) {
+                Some(&LF) => {
+                    // We found:
+                    // - a `\r\n`, which is left to the wrapped format
+                    scan_pos = i + 2;
+                }
+                None if !is_last_chunk => {
+                    // We found:
+                    // - a `\r` at the end of the chunk, hold it back until we know what follows
+                    normalize_into::<N>(
+                        &input[segment_start..i],
+                        output,
+                        &mut write_pos,
+                        &mut state.inner,
+                        false,
+                    )?;
+                    state.pending_cr = true;
+                    return Ok(NormalizeChunkResult::new(write_pos, Some(state)));
+                }
+                _ => {
+                    // We found:
+                    // - a lone `\r`, copy it as data
+                    normalize_into::<N>(
+                        &input[segment_start..i],
+                        output,
+                        &mut write_pos,
+                        &mut state.inner,
+                        false,
+                    )?;
+                    copy(&[CR], output, &mut write_pos);
+                    segment_start = i + 1;
+                    scan_pos = i + 1;
+                }
+            }
+        }
+
+        normalize_into::<N>(
+            &input[segment_start..],
+            output,
+            &mut write_pos,
+            &mut state.inner,
+            is_last_chunk,
+        )?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = ChunkEnd::Boundary.max_output_size::<Self>(input.len(), state);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let status = Self::normalize_chunk(input, output, state, false)?;
+        let mut state = status.state().cloned().unwrap_or_default();
+        let mut write_pos = status.output_len();
+        if state.pending_cr {
+            // We found:
+            // - a `\r` at the boundary, which no `\n` follows
+            copy(&[CR], output, &mut write_pos);
+            state.pending_cr = false;
+        }
+        normalize_at_boundary_into::<N>(output, &mut write_pos, &mut state.inner)?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+// SAFETY: Lone `\r` bytes are copied and the input between them, split only at those ASCII
+// bytes, passes through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for KeepLoneCr<N> {}
@@ -1,23 +1,33 @@
 use std::{mem::MaybeUninit, ptr};
 
-use memchr::memchr;
+use memchr::{memchr, memchr_iter};
 
-use crate::{types, NormalizeChunk, NormalizeChunkResult, Result};
+use crate::{types, NormalizeChunk, NormalizeChunkResult, PreservesUtf8, Result};
 
 /// LF normalization format implementation.
 ///
 /// Will convert all line endings that are not LF (i.e. CRLF or CR alone) into LF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct LF;
 
+impl LF {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, _is_last_chunk: bool) -> usize {
+        chunk_size
+    }
+}
+
 impl NormalizeChunk for LF {
     type State = bool;
 
     fn max_output_size_for_chunk(
         chunk_size: usize,
         _state: Option<&Self::State>,
-        _is_last_chunk: bool,
+        is_last_chunk: bool,
     ) -> usize {
-        chunk_size
+        Self::max_output_size(chunk_size, is_last_chunk)
     }
 
     fn normalize_chunk(
@@ -131,4 +141,51 @@ impl NormalizeChunk for LF {
             }
         }
     }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        // Resolving a trailing `\r` is all the end of the stream does for this format.
+        Self::normalize_chunk(input, output, state, true)
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        memchr(types::CR, input).is_some()
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        // Mirrors normalize_chunk, but only counts the bytes it would drop.
+        let preceded_by_cr = state.copied().unwrap_or(false);
+
+        let mut output_len = input.len();
+        if input.first() == Some(&types::LF) && preceded_by_cr {
+            output_len -= 1;
+        }
+
+        let crlf_count = memchr_iter(types::CR, input)
+            .filter(|&i| input.get(i + 1) == Some(&types::LF))
+            .count();
+        output_len -= crlf_count;
+
+        let ends_with_cr = match input.last() {
+            Some(&c) => c == types::CR,
+            None => preceded_by_cr,
+        };
+        NormalizeChunkResult::new(output_len, Some(ends_with_cr && !is_last_chunk))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
 }
+
+// SAFETY: Only the ASCII bytes `\r` and `\n` are removed or replaced.
+unsafe impl PreservesUtf8 for LF {}
@@ -1,15 +1,36 @@
 //! The `formats` module contains the core traits and types for normalization. The actual
 //! formats (like CRLF) are implemented in submodules.
 
-use std::mem::MaybeUninit;
+use std::{mem::MaybeUninit, ptr};
 
-use crate::{helpers::vec_to_uninit_mut, Result};
+use crate::{helpers::vec_to_uninit_mut, Error, Result};
 
+pub(crate) mod bom;
+mod breaks;
+pub(crate) mod checked;
+pub(crate) mod collapse_blank_lines;
+pub(crate) mod collapse_cr_crlf;
+pub(crate) mod cr;
 pub(crate) mod crlf;
+pub(crate) mod custom;
+pub(crate) mod ff_vt_breaks;
+pub(crate) mod final_newline;
+pub(crate) mod keep_lone_cr;
 pub(crate) mod lf;
+pub(crate) mod native;
+pub(crate) mod ndjson;
+pub(crate) mod nel;
+pub(crate) mod nul;
+pub(crate) mod preserve_lines;
+pub(crate) mod terminal;
+pub(crate) mod tidy;
+pub(crate) mod trim_trailing;
+pub(crate) mod unicode_separators;
+pub(crate) mod universal;
+pub(crate) mod visible;
 
 /// Result returned by `normalize_chunk` describing how many bytes were
-/// written and whether the chunk ended with a `\r`.
+/// written and the state to carry over to the next chunk.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NormalizeChunkResult<S: Sized> {
     output_len: usize,
@@ -30,10 +51,8 @@ impl<S> NormalizeChunkResult<S> {
         self.output_len
     }
 
-    /// Whether the input ended with an unpaired `\r`.
-    ///
-    /// If `true`, the next invocation of `normalize_chunk` should have `preceded_by_cr`
-    /// set to `true` to properly handle a possible leading `\n`.
+    /// The state to pass to the next invocation of `normalize_chunk`, e.g. whether the input
+    /// ended with an unpaired `\r` that might be followed by a `\n`.
     #[must_use]
     pub fn state(&self) -> Option<&S> {
         self.state.as_ref()
@@ -45,18 +64,28 @@ impl<S> NormalizeChunkResult<S> {
 /// Consumers will typically not use this trait directly, but rather the higher-level
 /// `Normalize` trait.
 pub trait NormalizeChunk {
+    /// State carried from one chunk to the next, such as whether the previous chunk ended with a
+    /// dangling `\r`.
+    ///
+    /// The state is plain data and fully describes the progress through the stream: passing a
+    /// stored state along with the input following the chunk it was returned for continues
+    /// normalization exactly where it left off, even in another process. With the `serde`
+    /// feature enabled the states of all formats in this crate implement `Serialize` and
+    /// `Deserialize`, so stream processors can checkpoint them together with their input
+    /// offset.
     type State: Clone + Sized;
 
     /// Normalize a single chunk of input to the required format into the provided `output` buffer.
     ///
     /// Parameters:
     /// - `input`: bytes to normalize
-    /// - `output`: destination buffer.
-    /// - `preceded_by_cr`: set to `true` if the previous chunk ended with a `\r`.
+    /// - `output`: destination buffer, at least
+    ///   [`max_output_size_for_chunk`](Self::max_output_size_for_chunk) bytes long.
+    /// - `state`: the state returned for the previous chunk, or `None` for the first chunk.
     /// - `is_last_chunk`: set to `true` if this is the final chunk of the stream.
     ///
     /// Returns a `NormalizeChunkResult` on success which tells how many bytes were
-    /// written and whether the chunk ended with a dangling `\r`.
+    /// written and the state to pass along with the next chunk.
     ///
     /// # Errors
     ///
@@ -72,24 +101,268 @@ pub trait NormalizeChunk {
     ) -> Result<NormalizeChunkResult<Self::State>>;
 
     /// Returns the worst-case required output buffer size for the given `chunk_size`.
+    ///
+    /// `state` and `is_last_chunk` must be the values that will be passed to `normalize_chunk`
+    /// for a chunk of `chunk_size` bytes. An output buffer of at least the returned size is
+    /// guaranteed to be large enough, so callers managing their own buffers can size them up
+    /// front instead of retrying on `Error::OutputBufferTooSmall`:
+    ///
+    /// ```
+    /// use eolify::{NormalizeChunk, CRLF};
+    ///
+    /// let input = b"one\ntwo\n";
+    /// let mut output = Vec::with_capacity(CRLF::max_output_size_for_chunk(input.len(), None, true));
+    /// let status = CRLF::normalize_chunk(input, output.spare_capacity_mut(), None, true).unwrap();
+    /// // SAFETY: normalize_chunk initialized the first `output_len` bytes.
+    /// unsafe { output.set_len(status.output_len()) };
+    /// assert_eq!(output, b"one\r\ntwo\r\n");
+    /// ```
+    ///
+    /// The result only depends on the arguments, never on the content of the chunk, and may
+    /// overestimate the size actually needed. Implementations must not overflow: if the
+    /// worst-case size doesn't fit in a `usize` they should return `usize::MAX`, so that
+    /// `normalize_chunk` fails with `Error::OutputBufferTooSmall` instead of writing out of
+    /// bounds.
     #[must_use]
     fn max_output_size_for_chunk(
         chunk_size: usize,
         state: Option<&Self::State>,
         is_last_chunk: bool,
     ) -> usize;
+
+    /// Normalize a chunk that ends at a soft boundary, such as a flush of an interactive writer:
+    /// more input may follow, but a `\r` at the end of the chunk is resolved as a complete line
+    /// ending right away, so an `\n` starting the next chunk is a line ending of its own.
+    ///
+    /// Unlike passing `is_last_chunk = true` to [`normalize_chunk`](Self::normalize_chunk), this
+    /// doesn't end the stream, so adapters acting at the end of the stream (such as
+    /// [`EnsureFinalNewline`](crate::EnsureFinalNewline)) keep waiting for it. `output` must be
+    /// at least `max_output_size_for_chunk(input.len(), state, false)` plus
+    /// `max_output_size_for_chunk(0, state, true)` bytes long.
+    ///
+    /// The default implementation resolves nothing and normalizes the chunk like any chunk in
+    /// the middle of the stream. Formats holding back a `\r` override it, and adapters forward
+    /// the boundary to the format they wrap.
+    ///
+    /// ```
+    /// use eolify::{EnsureFinalNewline, NormalizeChunk, CRLF};
+    ///
+    /// let mut output = Vec::with_capacity(16);
+    /// let status = EnsureFinalNewline::<CRLF>::normalize_chunk_at_boundary(
+    ///     b"one\r",
+    ///     output.spare_capacity_mut(),
+    ///     None,
+    /// )
+    /// .unwrap();
+    /// // SAFETY: normalize_chunk_at_boundary initialized the first `output_len` bytes.
+    /// unsafe { output.set_len(status.output_len()) };
+    /// assert_eq!(output, b"one\r\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(crate::Error::OutputBufferTooSmall { required })` if `output` is too small.
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        Self::normalize_chunk(input, output, state, false)
+    }
+
+    /// Normalize a single chunk like [`normalize_chunk`](Self::normalize_chunk), appending the
+    /// output to `output` after reserving the worst-case size.
+    ///
+    /// The returned `NormalizeChunkResult` reports the number of bytes appended.
+    ///
+    /// ```
+    /// use eolify::{NormalizeChunk, CRLF};
+    ///
+    /// let mut output = Vec::new();
+    /// let status = CRLF::normalize_chunk_into(b"one\r", &mut output, None, false).unwrap();
+    /// CRLF::normalize_chunk_into(b"\ntwo", &mut output, status.state(), true).unwrap();
+    /// assert_eq!(output, b"one\r\ntwo");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by `normalize_chunk`, which can't be
+    /// `Error::OutputBufferTooSmall` for a correct implementation.
+    fn normalize_chunk_into(
+        input: &[u8],
+        output: &mut Vec<u8>,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        output.reserve(Self::max_output_size_for_chunk(
+            input.len(),
+            state,
+            is_last_chunk,
+        ));
+        let status =
+            Self::normalize_chunk(input, output.spare_capacity_mut(), state, is_last_chunk)?;
+        // SAFETY: We trust that the implementation of normalize_chunk correctly reports the
+        // number of bytes it initialized.
+        unsafe {
+            output.set_len(output.len() + status.output_len());
+        }
+        Ok(status)
+    }
+
+    /// Returns the result `normalize_chunk` would return for `input`, without producing the
+    /// normalized output.
+    ///
+    /// This is useful to learn the exact normalized length up front, e.g. to set a
+    /// `Content-Length` header before streaming the normalized data. The default implementation
+    /// normalizes into a scratch buffer piece by piece; formats are encouraged to provide a
+    /// cheaper counting implementation.
+    #[must_use]
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        const PIECE_SIZE: usize = 4096;
+
+        let mut scratch = Vec::new();
+        let mut state = state.cloned();
+        let mut output_len = 0;
+        let mut pieces = input.chunks(PIECE_SIZE).peekable();
+        loop {
+            let piece = pieces.next().unwrap_or_default();
+            let is_last_piece = pieces.peek().is_none();
+            // The worst case depends on the state, e.g. on line endings held back so far.
+            let is_last_chunk = is_last_chunk && is_last_piece;
+            scratch.reserve(Self::max_output_size_for_chunk(
+                piece.len(),
+                state.as_ref(),
+                is_last_chunk,
+            ));
+            let status = Self::normalize_chunk(
+                piece,
+                vec_to_uninit_mut(&mut scratch),
+                state.as_ref(),
+                is_last_chunk,
+            )
+            .unwrap_or_else(|err| unreachable!("{err} (should be impossible)",));
+            output_len += status.output_len();
+            state = status.state().cloned();
+            if is_last_piece {
+                break NormalizeChunkResult::new(output_len, state);
+            }
+        }
+    }
+
+    /// Returns `true` if normalizing `input` as a complete stream would change it, i.e. it
+    /// contains line endings foreign to this format.
+    ///
+    /// The default implementation compares the normalized output against the input piece by
+    /// piece and stops at the first difference; formats are encouraged to provide a cheaper
+    /// scanning implementation.
+    #[must_use]
+    fn contains_foreign_eol(input: &[u8]) -> bool
+    where
+        Self: Sized,
+    {
+        let mut chunks = <Self as Normalize>::normalize_chunks(input, 4096);
+        let mut remaining = input;
+        while let Some(chunk) = chunks.next_chunk() {
+            match remaining.strip_prefix(chunk) {
+                Some(rest) => remaining = rest,
+                None => return true,
+            }
+        }
+        !remaining.is_empty()
+    }
+
+    /// Turn the output of normalizing valid UTF-8 into a `String`, for
+    /// [`Normalize::normalize_str`].
+    ///
+    /// The default implementation validates the output. Formats implementing [`PreservesUtf8`]
+    /// override it to skip the validation, and adapters that preserve UTF-8 whenever the format
+    /// they wrap does leave the decision to that format.
+    ///
+    /// # Safety
+    ///
+    /// `output` must be the output of normalizing valid UTF-8 with this format, or with an
+    /// adapter that preserves UTF-8 whenever this format does.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics if `output` is not valid UTF-8.
+    #[must_use]
+    unsafe fn output_to_string(output: Vec<u8>) -> String
+    where
+        Self: Sized,
+    {
+        String::from_utf8(output)
+            .unwrap_or_else(|err| panic!("normalization produced invalid UTF-8: {err}"))
+    }
+}
+
+/// Marker for formats that are guaranteed to turn valid UTF-8 into valid UTF-8, enabling
+/// [`normalize_utf8`](PreservesUtf8::normalize_utf8) to skip validating the output.
+///
+/// All formats in this crate implement it, and the adapters do whenever the format they wrap
+/// does.
+///
+/// # Safety
+///
+/// For any sequence of chunks whose concatenation is valid UTF-8, the concatenated output
+/// must be valid UTF-8 as well. Formats that only insert, remove or replace ASCII bytes, or
+/// replace ASCII bytes with complete UTF-8 sequences, satisfy this. Formats that match
+/// non-ASCII line separators (such as NEL, U+0085) must take care never to split or drop
+/// part of a multi-byte sequence.
+pub unsafe trait PreservesUtf8: NormalizeChunk {
+    /// Normalize the entire input string and return a newly allocated `String` with the
+    /// result, without validating it.
+    #[must_use]
+    fn normalize_utf8(input: &str) -> String
+    where
+        Self: Sized,
+    {
+        // SAFETY: The implementor guarantees that normalizing valid UTF-8 produces valid UTF-8.
+        unsafe { String::from_utf8_unchecked(Self::normalize(input.as_bytes())) }
+    }
 }
 
 /// This is the trait that consumers will typically use to normalize vectors or
 /// string slices to a specific format.
 pub trait Normalize {
     /// Normalize the entire input buffer and return a newly allocated `Vec<u8>` with the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the worst-case output size exceeds `isize::MAX` bytes, like any other
+    /// allocation of that size.
     #[must_use]
     fn normalize(input: &[u8]) -> Vec<u8>;
 
     /// Normalize the entire input string and return a newly allocated `String` with the result.
+    ///
+    /// The formats in this crate implementing [`PreservesUtf8`], which are all of them apart
+    /// from `Custom`, `Universal` and `NelLatin1`, return their output without validating it.
+    /// The output of other formats is validated, unless they override
+    /// [`output_to_string`](NormalizeChunk::output_to_string).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a format validating its output turned the input into invalid UTF-8.
     #[must_use]
     fn normalize_str(input: &str) -> String;
+
+    /// Returns the exact length `normalize` would produce for `input`, without producing it.
+    #[must_use]
+    fn normalized_len(input: &[u8]) -> usize;
+
+    /// Normalize `input` lazily, `chunk_size` input bytes at a time.
+    ///
+    /// The returned `NormalizedChunks` reuses a single output buffer, so the whole normalized
+    /// copy never has to be held in memory.
+    #[must_use]
+    fn normalize_chunks(input: &[u8], chunk_size: usize) -> NormalizedChunks<'_, Self>
+    where
+        Self: NormalizeChunk + Sized;
 }
 
 impl<N: NormalizeChunk> Normalize for N {
@@ -108,8 +381,204 @@ impl<N: NormalizeChunk> Normalize for N {
     }
 
     fn normalize_str(input: &str) -> String {
-        // SAFETY: normalize returns valid UTF-8 when given valid UTF-8 input because we only
-        // insert ASCII CR/LF bytes.
-        unsafe { String::from_utf8_unchecked(Self::normalize(input.as_bytes())) }
+        // SAFETY: The output is that of normalizing `input`, which is valid UTF-8.
+        unsafe { Self::output_to_string(Self::normalize(input.as_bytes())) }
+    }
+
+    fn normalized_len(input: &[u8]) -> usize {
+        Self::normalized_len_for_chunk(input, None, true).output_len()
+    }
+
+    fn normalize_chunks(input: &[u8], chunk_size: usize) -> NormalizedChunks<'_, Self> {
+        NormalizedChunks {
+            input,
+            chunk_size: chunk_size.max(1),
+            output: Vec::new(),
+            state: None,
+            done: false,
+        }
+    }
+}
+
+/// Normalized output of an input buffer, produced chunk by chunk by
+/// [`Normalize::normalize_chunks`].
+///
+/// The chunks borrow from an internal buffer that is reused for the next chunk, so this is not
+/// an `Iterator`; use [`next_chunk`](Self::next_chunk) in a loop instead:
+///
+/// ```
+/// use eolify::{Normalize, CRLF};
+///
+/// let mut chunks = CRLF::normalize_chunks(b"one\ntwo\n", 4);
+/// let mut out = Vec::new();
+/// while let Some(chunk) = chunks.next_chunk() {
+///     out.extend_from_slice(chunk);
+/// }
+/// assert_eq!(out, b"one\r\ntwo\r\n");
+/// ```
+pub struct NormalizedChunks<'a, N: NormalizeChunk> {
+    input: &'a [u8],
+    chunk_size: usize,
+    output: Vec<u8>,
+    state: Option<N::State>,
+    done: bool,
+}
+
+impl<N: NormalizeChunk> NormalizedChunks<'_, N> {
+    /// Normalize the next chunk of input and return the output, or `None` when all input has
+    /// been normalized. Chunks that normalize to nothing are skipped.
+    pub fn next_chunk(&mut self) -> Option<&[u8]> {
+        while !self.done {
+            let (chunk, rest) = self.input.split_at(self.chunk_size.min(self.input.len()));
+            let is_last_chunk = rest.is_empty();
+
+            self.output.clear();
+            let status = N::normalize_chunk_into(
+                chunk,
+                &mut self.output,
+                self.state.as_ref(),
+                is_last_chunk,
+            )
+            .unwrap_or_else(|err| unreachable!("{err} (should be impossible)",));
+
+            self.state = status.state().cloned();
+            self.input = rest;
+            self.done = is_last_chunk;
+            if !self.output.is_empty() {
+                return Some(&self.output);
+            }
+        }
+        None
+    }
+}
+
+/// Where a chunk handed to a format ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkEnd {
+    /// More input follows.
+    More,
+    /// More input may follow, but a trailing `\r` is resolved, see
+    /// [`NormalizeChunk::normalize_chunk_at_boundary`].
+    Boundary,
+    /// The chunk ends the stream.
+    Last,
+}
+
+impl ChunkEnd {
+    /// The end of a chunk passed to `normalize_chunk` with `is_last_chunk`.
+    pub(crate) fn from_is_last_chunk(is_last_chunk: bool) -> Self {
+        if is_last_chunk {
+            Self::Last
+        } else {
+            Self::More
+        }
+    }
+
+    /// The `is_last_chunk` flag for a chunk ending like this.
+    pub(crate) fn is_last_chunk(self) -> bool {
+        self == Self::Last
+    }
+
+    /// The worst-case output size `normalize` needs for a chunk of `chunk_size` bytes.
+    pub(crate) fn max_output_size<N: NormalizeChunk>(
+        self,
+        chunk_size: usize,
+        state: Option<&N::State>,
+    ) -> usize {
+        match self {
+            Self::More => N::max_output_size_for_chunk(chunk_size, state, false),
+            Self::Boundary => N::max_output_size_for_chunk(chunk_size, state, false)
+                .saturating_add(N::max_output_size_for_chunk(0, state, true)),
+            Self::Last => N::max_output_size_for_chunk(chunk_size, state, true),
+        }
+    }
+
+    /// Normalize a chunk ending like this with `N`.
+    pub(crate) fn normalize<N: NormalizeChunk>(
+        self,
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&N::State>,
+    ) -> Result<NormalizeChunkResult<N::State>> {
+        match self {
+            Self::More => N::normalize_chunk(input, output, state, false),
+            Self::Boundary => N::normalize_chunk_at_boundary(input, output, state),
+            Self::Last => N::normalize_chunk(input, output, state, true),
+        }
+    }
+}
+
+/// Normalize a chunk ending at a soft boundary with the adapter `A` wrapping `N`.
+///
+/// The chunk is normalized by `A` as usual, after which `N` resolves the boundary; `inner`
+/// selects the state of `N` within the state of `A`. Adapters that hold back input of their own
+/// until the end of the stream keep holding it.
+pub(crate) fn normalize_adapter_at_boundary<A: NormalizeChunk, N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    state: Option<&A::State>,
+    inner: impl FnOnce(&mut A::State) -> &mut Option<N::State>,
+) -> Result<NormalizeChunkResult<A::State>> {
+    let output_required = ChunkEnd::Boundary.max_output_size::<A>(input.len(), state);
+    if output.len() < output_required {
+        return Err(Error::OutputBufferTooSmall {
+            required: output_required,
+        });
+    }
+
+    let status = A::normalize_chunk(input, output, state, false)?;
+    let Some(mut state) = status.state().cloned() else {
+        return Ok(status);
+    };
+    let mut write_pos = status.output_len();
+    normalize_at_boundary_into::<N>(output, &mut write_pos, inner(&mut state))?;
+    Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+}
+
+/// Normalize `input` with `N` into `output` at `write_pos`, advancing `write_pos` and the inner
+/// state.
+pub(crate) fn normalize_into<N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    write_pos: &mut usize,
+    inner: &mut Option<N::State>,
+    is_last_chunk: bool,
+) -> Result<()> {
+    let status = N::normalize_chunk(
+        input,
+        &mut output[*write_pos..],
+        inner.as_ref(),
+        is_last_chunk,
+    )?;
+    *write_pos += status.output_len();
+    *inner = status.state().cloned();
+    Ok(())
+}
+
+/// Let `N` resolve a soft boundary, writing into `output` at `write_pos` and advancing
+/// `write_pos` and the state of `N`.
+pub(crate) fn normalize_at_boundary_into<N: NormalizeChunk>(
+    output: &mut [MaybeUninit<u8>],
+    write_pos: &mut usize,
+    inner: &mut Option<N::State>,
+) -> Result<()> {
+    let status = N::normalize_chunk_at_boundary(&[], &mut output[*write_pos..], inner.as_ref())?;
+    *write_pos += status.output_len();
+    *inner = status.state().cloned();
+    Ok(())
+}
+
+/// Copy `src` to `output` at `write_pos` and advance `write_pos`.
+pub(crate) fn copy(src: &[u8], output: &mut [MaybeUninit<u8>], write_pos: &mut usize) {
+    assert!(*write_pos + src.len() <= output.len());
+    // SAFETY: The range was checked to be in-bounds above, and `src` can't overlap `output`
+    // because `output` is borrowed mutably.
+    unsafe {
+        ptr::copy_nonoverlapping(
+            src.as_ptr(),
+            output.as_mut_ptr().add(*write_pos).cast::<u8>(),
+            src.len(),
+        );
     }
+    *write_pos += src.len();
 }
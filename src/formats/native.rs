@@ -0,0 +1,85 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    LineTerminator, Result,
+};
+
+#[cfg(windows)]
+type Platform = crate::CRLF;
+#[cfg(not(windows))]
+type Platform = crate::LF;
+
+/// Normalization to the line ending of the platform the code is compiled for: CRLF on Windows
+/// and LF elsewhere.
+///
+/// The choice is made at compile time and every call is delegated to [`CRLF`](crate::CRLF) or
+/// [`LF`](crate::LF), so there is no runtime overhead.
+///
+/// ```
+/// use eolify::{Native, Normalize};
+///
+/// let expected = if cfg!(windows) { "a\r\nb\r\n" } else { "a\nb\n" };
+/// assert_eq!(Native::normalize_str("a\nb\r\n"), expected);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Native;
+
+impl Native {
+    /// The line ending this format normalizes to.
+    pub const LINE_TERMINATOR: LineTerminator = if cfg!(windows) {
+        LineTerminator::CrLf
+    } else {
+        LineTerminator::Lf
+    };
+}
+
+impl NormalizeChunk for Native {
+    type State = <Platform as NormalizeChunk>::State;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Platform::max_output_size_for_chunk(chunk_size, state, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        Platform::normalize_chunk(input, output, state, is_last_chunk)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        Platform::normalize_chunk_at_boundary(input, output, state)
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        Platform::normalized_len_for_chunk(input, state, is_last_chunk)
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        Platform::contains_foreign_eol(input)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+// SAFETY: The output is the output of the platform format, which preserves UTF-8.
+unsafe impl PreservesUtf8 for Native {}
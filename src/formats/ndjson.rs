@@ -0,0 +1,139 @@
+use std::mem::MaybeUninit;
+
+use memchr::{memchr2, memchr3};
+
+use crate::{
+    formats::{copy, NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    types::{CR, LF},
+    Error, Result,
+};
+
+const QUOTE: u8 = b'"';
+const BACKSLASH: u8 = b'\\';
+
+/// Newline-delimited JSON normalization format implementation.
+///
+/// Guarantees every record is terminated by exactly one LF: CRLF and CR become LF, runs of
+/// line endings between records (i.e. empty lines) are collapsed, and a missing LF after the
+/// last record is added. Bytes inside JSON strings are never touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NDJSON;
+
+/// State carried between chunks by [`NDJSON`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NdjsonState {
+    in_string: bool,
+    escaped: bool,
+    at_record_start: bool,
+}
+
+impl Default for NdjsonState {
+    fn default() -> Self {
+        Self {
+            in_string: false,
+            escaped: false,
+            at_record_start: true,
+        }
+    }
+}
+
+impl NormalizeChunk for NDJSON {
+    type State = NdjsonState;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // Line endings are only ever replaced by a single LF or dropped, the only byte that can
+        // be added is the LF terminating the last record.
+        chunk_size.saturating_add(usize::from(is_last_chunk))
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.copied().unwrap_or_default();
+        let mut read_pos = 0;
+        let mut write_pos = 0;
+
+        while read_pos < input.len() {
+            let rest = &input[read_pos..];
+            if state.escaped {
+                // We found:
+                // - the byte following a backslash inside a string
+                copy(&input[read_pos..=read_pos], output, &mut write_pos);
+                state.escaped = false;
+                read_pos += 1;
+            } else if state.in_string {
+                let Some(i) = memchr2(QUOTE, BACKSLASH, rest) else {
+                    copy(rest, output, &mut write_pos);
+                    break;
+                };
+                // We found:
+                // - the end of a string, or
+                // - a backslash escaping the next byte
+                copy(&rest[..=i], output, &mut write_pos);
+                if rest[i] == QUOTE {
+                    state.in_string = false;
+                } else {
+                    state.escaped = true;
+                }
+                read_pos += i + 1;
+            } else {
+                let Some(i) = memchr3(QUOTE, CR, LF, rest) else {
+                    copy(rest, output, &mut write_pos);
+                    state.at_record_start &= rest.is_empty();
+                    break;
+                };
+                copy(&rest[..i], output, &mut write_pos);
+                state.at_record_start &= i == 0;
+                if rest[i] == QUOTE {
+                    // We found:
+                    // - the start of a string
+                    copy(&rest[i..=i], output, &mut write_pos);
+                    state.in_string = true;
+                    state.at_record_start = false;
+                } else if !state.at_record_start {
+                    // We found:
+                    // - the line ending terminating a record
+                    copy(&[LF], output, &mut write_pos);
+                    state.at_record_start = true;
+                }
+                // Any other line ending is part of a run and is dropped.
+                read_pos += i + 1;
+            }
+        }
+
+        if is_last_chunk && !state.at_record_start {
+            // We found:
+            // - a last record without line ending
+            copy(&[LF], output, &mut write_pos);
+            state.at_record_start = true;
+        }
+
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+// SAFETY: Only the ASCII bytes `\r` and `\n` are touched: line endings are either dropped or
+// replaced by a single `\n`, and a `\n` may be appended after the last record. All other bytes
+// are copied unchanged.
+unsafe impl PreservesUtf8 for NDJSON {}
@@ -0,0 +1,178 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        breaks::{self, BreakSet, Pending},
+        normalize_adapter_at_boundary, NormalizeChunk, NormalizeChunkResult, PreservesUtf8,
+    },
+    Error, Result,
+};
+
+/// U+0085 NEXT LINE encoded as UTF-8.
+const NEL_UTF8: &[u8] = "\u{85}".as_bytes();
+/// U+0085 NEXT LINE encoded as Latin-1 (or EBCDIC converted to Latin-1).
+const NEL_LATIN1: u8 = 0x85;
+
+struct Utf8Nel;
+
+impl BreakSet for Utf8Nel {
+    const SEQUENCES: &'static [&'static [u8]] = &[NEL_UTF8];
+}
+
+struct Latin1Nel;
+
+impl BreakSet for Latin1Nel {
+    const SEQUENCES: &'static [&'static [u8]] = &[&[NEL_LATIN1]];
+}
+
+/// Format adapter that also treats NEL (U+0085, encoded as UTF-8) as a line ending, converting
+/// it to the line ending of the wrapped format.
+///
+/// NEL is used by text exported from mainframes. A NEL split across chunks is held back until
+/// the chunk completing it arrives, and every NEL counts as exactly one line ending. For
+/// Latin-1 input, where NEL is the single byte `0x85`, use [`NelLatin1`] instead.
+///
+/// ```
+/// use eolify::{Nel, Normalize, CRLF};
+///
+/// assert_eq!(Nel::<CRLF>::normalize_str("a\u{85}b\nc"), "a\r\nb\r\nc");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Nel<N>(pub N);
+
+/// Format adapter that also treats the byte `0x85` as a line ending, which is how NEL is
+/// encoded in Latin-1, converting it to the line ending of the wrapped format.
+///
+/// Only use this for Latin-1 input: in UTF-8 `0x85` also occurs inside other characters, which
+/// would be corrupted. For UTF-8 input use [`Nel`].
+///
+/// ```
+/// use eolify::{NelLatin1, Normalize, LF};
+///
+/// assert_eq!(NelLatin1::<LF>::normalize(b"a\x85b\r\n"), b"a\nb\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NelLatin1<N>(pub N);
+
+/// State carried between chunks by [`Nel`] and [`NelLatin1`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NelState<S> {
+    inner: Option<S>,
+    /// The start of a NEL at the end of the previous chunk that was held back.
+    pending: Pending,
+}
+
+impl<S> Default for NelState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            pending: Pending::default(),
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for Nel<N> {
+    type State = NelState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        breaks::max_output_size_with::<Utf8Nel, N>(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_chunk::<Utf8Nel, N>(input, output, state, is_last_chunk)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for NelLatin1<N> {
+    type State = NelState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        breaks::max_output_size_with::<Latin1Nel, N>(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_chunk::<Latin1Nel, N>(input, output, state, is_last_chunk)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+}
+
+fn normalize_chunk<B: BreakSet, N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    state: Option<&NelState<N::State>>,
+    is_last_chunk: bool,
+) -> Result<NormalizeChunkResult<NelState<N::State>>> {
+    let output_required = breaks::max_output_size_with::<B, N>(
+        input.len(),
+        state.and_then(|state| state.inner.as_ref()),
+        is_last_chunk,
+    );
+    if output.len() < output_required {
+        return Err(Error::OutputBufferTooSmall {
+            required: output_required,
+        });
+    }
+
+    let mut state = state.cloned().unwrap_or_default();
+    let output_len = breaks::normalize_chunk_with::<B, N>(
+        input,
+        output,
+        &mut state.inner,
+        &mut state.pending,
+        is_last_chunk,
+    )?;
+    Ok(NormalizeChunkResult::new(output_len, Some(state)))
+}
+
+// SAFETY: Only complete NEL characters, which are valid UTF-8 on their own, are replaced by
+// ASCII bytes; everything else passes through the wrapped format. `NelLatin1` doesn't
+// implement the marker since it replaces bytes inside multi-byte characters.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for Nel<N> {}
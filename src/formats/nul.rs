@@ -0,0 +1,173 @@
+use std::mem::MaybeUninit;
+
+use memchr::{memchr, memchr2, memchr_iter};
+
+use crate::{
+    formats::{
+        copy,
+        custom::{Custom, Delimiter},
+        NormalizeChunk, NormalizeChunkResult, PreservesUtf8,
+    },
+    types, Error, Result,
+};
+
+const NUL_BYTE: u8 = 0;
+
+struct NulByte;
+
+impl Delimiter for NulByte {
+    const BYTES: &'static [u8] = &[NUL_BYTE];
+}
+
+/// NUL-separated records, as produced by `find -print0` and consumed by `xargs -0`.
+///
+/// Will convert all line endings (CRLF, LF or CR alone) into a NUL byte. Every call is delegated
+/// to [`Custom`] with a single NUL byte as delimiter. Use [`NulToLf`] for the opposite
+/// direction.
+///
+/// ```
+/// use eolify::{Normalize, NUL};
+///
+/// assert_eq!(NUL::normalize(b"a.txt\r\nb.txt\n"), b"a.txt\0b.txt\0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NUL;
+
+impl NUL {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, is_last_chunk: bool) -> usize {
+        Custom::<NulByte>::max_output_size(chunk_size, is_last_chunk)
+    }
+}
+
+impl NormalizeChunk for NUL {
+    type State = bool;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Self::max_output_size(chunk_size, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        Custom::<NulByte>::normalize_chunk(input, output, state, is_last_chunk)
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        Custom::<NulByte>::normalize_chunk_at_boundary(input, output, state)
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        Custom::<NulByte>::normalized_len_for_chunk(input, state, is_last_chunk)
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        memchr2(types::CR, types::LF, input).is_some()
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+// SAFETY: Only the ASCII bytes `\r` and `\n` are removed or replaced, by the ASCII NUL byte.
+unsafe impl PreservesUtf8 for NUL {}
+
+/// Turns NUL-separated records, as produced by `find -print0`, into LF-terminated lines.
+///
+/// Every NUL byte is replaced by `\n`; all other bytes, including existing `\r` and `\n`, pass
+/// through unchanged. Since a single byte is replaced by a single byte, no state needs to be
+/// carried between chunks and the output is exactly as long as the input.
+///
+/// ```
+/// use eolify::{Normalize, NulToLf};
+///
+/// assert_eq!(NulToLf::normalize(b"a.txt\0b.txt\0"), b"a.txt\nb.txt\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NulToLf;
+
+impl NulToLf {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, _is_last_chunk: bool) -> usize {
+        chunk_size
+    }
+}
+
+impl NormalizeChunk for NulToLf {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Self::max_output_size(chunk_size, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut read_pos = 0;
+        let mut write_pos = 0;
+        for i in memchr_iter(NUL_BYTE, input) {
+            copy(&input[read_pos..i], output, &mut write_pos);
+            copy(&[types::LF], output, &mut write_pos);
+            read_pos = i + 1;
+        }
+        copy(&input[read_pos..], output, &mut write_pos);
+        Ok(NormalizeChunkResult::new(write_pos, Some(())))
+    }
+
+    fn normalized_len_for_chunk(
+        input: &[u8],
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> NormalizeChunkResult<Self::State> {
+        NormalizeChunkResult::new(input.len(), Some(()))
+    }
+
+    fn contains_foreign_eol(input: &[u8]) -> bool {
+        memchr(NUL_BYTE, input).is_some()
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+// SAFETY: Only the ASCII NUL byte is replaced, by the ASCII byte `\n`.
+unsafe impl PreservesUtf8 for NulToLf {}
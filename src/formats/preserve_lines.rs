@@ -0,0 +1,132 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{ChunkEnd, NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    helpers::LineEndingCounter,
+    Result,
+};
+
+/// Format adapter that verifies the wrapped format keeps the number of lines intact.
+///
+/// In debug builds the line endings of the input and of the normalized output are counted while
+/// normalizing, and the adapter panics at the end of the stream if the counts differ, i.e. if
+/// the format merged or split lines. This is a safety net for composed or custom formats that
+/// are meant to only change how lines end. In release builds the check is skipped and the
+/// adapter only forwards to the wrapped format.
+///
+/// ```
+/// use eolify::{Normalize, PreserveLines, CRLF};
+///
+/// assert_eq!(PreserveLines::<CRLF>::normalize(b"a\nb\r\nc\r"), b"a\r\nb\r\nc\r\n");
+/// ```
+///
+/// ```should_panic
+/// use eolify::{Normalize, PreserveLines, NDJSON};
+///
+/// // NDJSON collapses empty lines.
+/// PreserveLines::<NDJSON>::normalize(b"{}\n\n{}\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PreserveLines<N>(pub N);
+
+/// State carried between chunks by [`PreserveLines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreserveLinesState<S> {
+    inner: Option<S>,
+    input_counter: LineEndingCounter,
+    output_counter: LineEndingCounter,
+    input_lines: u64,
+    output_lines: u64,
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for PreserveLines<N> {
+    type State = PreserveLinesState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        N::max_output_size_for_chunk(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_counted::<N>(
+            input,
+            output,
+            state,
+            ChunkEnd::from_is_last_chunk(is_last_chunk),
+        )
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_counted::<N>(input, output, state, ChunkEnd::Boundary)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+/// Normalize a chunk ending at `end` with `N`, counting lines as described for
+/// [`PreserveLines`].
+fn normalize_counted<N: NormalizeChunk>(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    state: Option<&PreserveLinesState<N::State>>,
+    end: ChunkEnd,
+) -> Result<NormalizeChunkResult<PreserveLinesState<N::State>>> {
+    let mut state = state.cloned().unwrap_or(PreserveLinesState {
+        inner: None,
+        input_counter: LineEndingCounter::default(),
+        output_counter: LineEndingCounter::default(),
+        input_lines: 0,
+        output_lines: 0,
+    });
+    let status = end.normalize::<N>(input, output, state.inner.as_ref())?;
+    state.inner = status.state().cloned();
+
+    if cfg!(debug_assertions) {
+        // SAFETY: We trust that the implementation of normalize_chunk correctly reports the
+        // number of bytes it initialized.
+        let normalized = unsafe {
+            std::slice::from_raw_parts(output.as_ptr().cast::<u8>(), status.output_len())
+        };
+        // A trailing `\r` is a complete line ending at a boundary as well.
+        let resolves_cr = end != ChunkEnd::More;
+        let (lf, crlf, cr) = state.input_counter.count(input, resolves_cr);
+        state.input_lines += lf + crlf + cr;
+        let (lf, crlf, cr) = state.output_counter.count(normalized, resolves_cr);
+        state.output_lines += lf + crlf + cr;
+
+        if end.is_last_chunk() {
+            assert!(
+                state.input_lines == state.output_lines,
+                "normalization changed the line count: {} lines in, {} lines out",
+                state.input_lines,
+                state.output_lines,
+            );
+        }
+    }
+
+    Ok(NormalizeChunkResult::new(status.output_len(), Some(state)))
+}
+
+// SAFETY: The output is the output of the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for PreserveLines<N> {}
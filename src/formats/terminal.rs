@@ -0,0 +1,219 @@
+use std::mem::MaybeUninit;
+
+use memchr::memchr;
+
+use crate::{
+    formats::{
+        collapse_cr_crlf::{CollapseCrCrLf, CollapseCrCrLfState},
+        copy,
+        keep_lone_cr::{KeepLoneCr, KeepLoneCrState},
+        normalize_adapter_at_boundary, normalize_into, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    Error, Result,
+};
+
+/// The adapters [`Terminal`] passes everything but escape strings through.
+type Inner<N> = CollapseCrCrLf<KeepLoneCr<N>>;
+
+const ESC: u8 = 0x1B;
+const BEL: u8 = 0x07;
+
+/// Format adapter for terminal output, such as the output of a CI job recorded through a PTY.
+///
+/// Line endings are normalized by the wrapped format, with the habits of terminals in mind:
+///
+/// - A lone `\r` moves the cursor back to redraw the line, as progress bars do, so it is kept
+///   as is, see [`KeepLoneCr`].
+/// - A `\r\r\n`, which a PTY makes of a `\r\n` written by the program, is a single line ending,
+///   see [`CollapseCrCrLf`].
+/// - ANSI escape sequences are left intact. Control sequences such as colors and cursor
+///   movement contain no line endings, but the strings of OSC, DCS, SOS, PM and APC sequences
+///   (window titles, hyperlinks, ...) may, so they are copied unchanged up to their terminator,
+///   `BEL` or `ESC \`.
+///
+/// An escape string is only followed for `WINDOW` bytes. If it is not terminated by then, the
+/// rest is normalized as ordinary output, so a stray escape can't keep the remainder of the log
+/// from being normalized.
+///
+/// ```
+/// use eolify::{Normalize, Terminal, LF};
+///
+/// assert_eq!(
+///     Terminal::<LF>::normalize(b"10%\r50%\r\r\ndone\r\n"),
+///     b"10%\r50%\ndone\n"
+/// );
+/// assert_eq!(
+///     Terminal::<LF>::normalize(b"\x1b]0;a\r\nb\x07\x1b[1mc\x1b[0m\r\n"),
+///     b"\x1b]0;a\r\nb\x07\x1b[1mc\x1b[0m\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Terminal<N, const WINDOW: usize = 1024>(pub N);
+
+/// State carried between chunks by [`Terminal`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerminalState<S> {
+    inner: Option<CollapseCrCrLfState<KeepLoneCrState<S>>>,
+    escape: Escape,
+}
+
+impl<S> Default for TerminalState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            escape: Escape::None,
+        }
+    }
+}
+
+/// Where the previous chunk ended relative to an escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Escape {
+    /// Outside of escape strings.
+    None,
+    /// Right after an `ESC` outside of escape strings.
+    Esc,
+    /// In an escape string, `len` bytes after its start; `esc` is set after an `ESC`, which may
+    /// start the terminator.
+    String { len: usize, esc: bool },
+}
+
+impl<N: NormalizeChunk, const WINDOW: usize> NormalizeChunk for Terminal<N, WINDOW> {
+    type State = TerminalState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // Escape strings are copied as is and the rest goes through the wrapped formats.
+        Inner::<N>::max_output_size_for_chunk(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+        .saturating_add(chunk_size)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+
+        let mut pos = 0;
+        while pos < input.len() {
+            if let Escape::String { mut len, mut esc } = state.escape {
+                let mut end = pos;
+                let mut terminated = false;
+                while end < input.len() && len < WINDOW {
+                    let b = input[end];
+                    end += 1;
+                    len += 1;
+                    if b == BEL || (esc && b == b'\\') {
+                        // We found:
+                        // - the terminator of the escape string
+                        terminated = true;
+                        break;
+                    }
+                    esc = b == ESC;
+                }
+                copy(&input[pos..end], output, &mut write_pos);
+                state.escape = if terminated || len >= WINDOW {
+                    Escape::None
+                } else {
+                    Escape::String { len, esc }
+                };
+                pos = end;
+                continue;
+            }
+
+            // Everything up to and including the start of the next escape string goes through
+            // the wrapped formats.
+            let mut end = input.len();
+            let mut escape = Escape::None;
+            let mut scan_pos = pos;
+            if state.escape == Escape::Esc && starts_string(input[pos]) {
+                // We found:
+                // - an escape string started by an `ESC` at the end of the previous chunk
+                end = pos + 1;
+                escape = Escape::String { len: 0, esc: false };
+            } else {
+                while let Some(i) = memchr(ESC, &input[scan_pos..]).map(|i| i + scan_pos) {
+                    match input.get(i + 1) {
+                        Some(&b) if starts_string(b) => {
+                            // We found:
+                            // - the start of an escape string
+                            end = i + 2;
+                            escape = Escape::String { len: 0, esc: false };
+                            break;
+                        }
+                        Some(_) => scan_pos = i + 1,
+                        None => {
+                            // We found:
+                            // - an `ESC` at the end of the chunk, which may start an escape
+                            //   string in the next one
+                            escape = Escape::Esc;
+                            break;
+                        }
+                    }
+                }
+            }
+            normalize_into::<Inner<N>>(
+                &input[pos..end],
+                output,
+                &mut write_pos,
+                &mut state.inner,
+                false,
+            )?;
+            state.escape = escape;
+            pos = end;
+        }
+
+        if is_last_chunk {
+            normalize_into::<Inner<N>>(&[], output, &mut write_pos, &mut state.inner, true)?;
+            state.escape = Escape::None;
+        }
+
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, Inner<N>>(input, output, state, |state| {
+            &mut state.inner
+        })
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+/// Whether `b` following an `ESC` starts an escape string: OSC, DCS, SOS, PM or APC.
+fn starts_string(b: u8) -> bool {
+    matches!(b, b']' | b'P' | b'X' | b'^' | b'_')
+}
+
+// SAFETY: Escape strings are copied unchanged and the rest of the input passes through
+// `CollapseCrCrLf` and `KeepLoneCr` around the wrapped format, which preserve UTF-8 however the
+// input is split when the wrapped format does.
+unsafe impl<N: PreservesUtf8, const WINDOW: usize> PreservesUtf8 for Terminal<N, WINDOW> {}
@@ -0,0 +1,98 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        bom::{BomState, StripBom},
+        final_newline::{EnsureFinalNewline, EnsureFinalNewlineState},
+        NormalizeChunk, NormalizeChunkResult, PreservesUtf8,
+    },
+    Result,
+};
+
+/// The adapters [`Tidy`] is made of.
+type Inner<N> = StripBom<EnsureFinalNewline<N>>;
+
+/// Format adapter that strips a leading byte order mark, normalizes line endings with the
+/// wrapped format and makes sure non-empty output ends with a line ending, all in a single pass.
+///
+/// This is [`StripBom`] around [`EnsureFinalNewline`]: both only look at the start and end of
+/// the stream, so the wrapped format's scan is the only pass over the data and no extra buffers
+/// are involved. The final line ending is produced by the wrapped format itself, so it matches
+/// the target. Use it through [`NormalizerBuilder::tidy`](crate::NormalizerBuilder::tidy) or
+/// directly as a format:
+///
+/// ```
+/// use eolify::{Normalize, Tidy, CRLF};
+///
+/// assert_eq!(Tidy::<CRLF>::normalize(b"\xEF\xBB\xBFone\ntwo"), b"one\r\ntwo\r\n");
+/// assert_eq!(Tidy::<CRLF>::normalize(b""), b"");
+/// ```
+///
+/// Up to two bytes at the very start of the stream are held back until it is clear whether they
+/// start a BOM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Tidy<N>(pub N);
+
+/// State carried between chunks by [`Tidy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TidyState<S>(BomState<EnsureFinalNewlineState<S>>);
+
+impl<S> Default for TidyState<S> {
+    fn default() -> Self {
+        Self(BomState::default())
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for Tidy<N> {
+    type State = TidyState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Inner::<N>::max_output_size_for_chunk(
+            chunk_size,
+            state.map(|state| &state.0),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let status =
+            Inner::<N>::normalize_chunk(input, output, state.map(|state| &state.0), is_last_chunk)?;
+        Ok(wrap_state(status))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let status =
+            Inner::<N>::normalize_chunk_at_boundary(input, output, state.map(|state| &state.0))?;
+        Ok(wrap_state(status))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+fn wrap_state<S: Clone>(
+    status: NormalizeChunkResult<BomState<EnsureFinalNewlineState<S>>>,
+) -> NormalizeChunkResult<TidyState<S>> {
+    NormalizeChunkResult::new(status.output_len(), status.state().cloned().map(TidyState))
+}
+
+// SAFETY: The output is the output of `StripBom` and `EnsureFinalNewline`, which both preserve
+// UTF-8 around a wrapped format that does.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for Tidy<N> {}
@@ -0,0 +1,258 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        normalize_adapter_at_boundary, normalize_into, NormalizeChunk, NormalizeChunkResult,
+        PreservesUtf8,
+    },
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Line endings are passed on to the wrapped format in pieces of at most this many `\n` bytes.
+const LINE_FEEDS: [u8; 64] = [LF; 64];
+
+/// Format adapter that removes the line endings at the end of the stream, keeping at most
+/// `KEEP` of them.
+///
+/// A run of line endings is held back until it is known whether anything follows: if more
+/// text arrives the run is passed on in full, if the stream ends only `KEEP` line endings are
+/// written. With `KEEP = 1` the output ends with at most one line ending, handy when embedding
+/// the text in a template. Held back line endings are passed on to the wrapped format as `\n`.
+///
+/// Only the number of held back line endings is stored in the state. Once text follows them
+/// they are written in pieces of bounded size: a chunk writes as many as fit in the output
+/// buffer, and the input after them waits in the state until the rest are out. The wrappers pass these on
+/// with chunks of empty input before normalizing more, so their buffers don't grow however long
+/// the run is.
+///
+/// ```
+/// use eolify::{Normalize, TrimTrailingNewlines, CRLF, LF};
+///
+/// assert_eq!(TrimTrailingNewlines::<LF>::normalize(b"a\r\n\r\nb\r\n\r\n"), b"a\n\nb");
+/// assert_eq!(
+///     TrimTrailingNewlines::<CRLF, 1>::normalize(b"a\n\n\r"),
+///     b"a\r\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TrimTrailingNewlines<N, const KEEP: usize = 0>(pub N);
+
+/// State carried between chunks by [`TrimTrailingNewlines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrimTrailingNewlinesState<S> {
+    inner: Option<S>,
+    /// The number of line endings held back since the last text.
+    held: usize,
+    /// Whether the held back line endings end with a `\r`, which an `\n` may still complete.
+    ends_with_cr: bool,
+    /// The number of line endings followed by text that are not written yet.
+    released: usize,
+    /// Input following the released line endings, normalized once they are all written.
+    deferred: Vec<u8>,
+}
+
+impl<S> Default for TrimTrailingNewlinesState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            held: 0,
+            ends_with_cr: false,
+            released: 0,
+            deferred: Vec::new(),
+        }
+    }
+}
+
+impl<N: NormalizeChunk, const KEEP: usize> NormalizeChunk for TrimTrailingNewlines<N, KEEP> {
+    type State = TrimTrailingNewlinesState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        // At least one piece of line endings followed by text is written per chunk, all of them
+        // at the end of the stream, including the held back ones if text follows.
+        let inner = state.and_then(|state| state.inner.as_ref());
+        let (held, released, deferred) = state.map_or((0, 0, 0), |state| {
+            (state.held, state.released, state.deferred.len())
+        });
+        let chunk_size = chunk_size.saturating_add(deferred);
+        let pieces = if is_last_chunk {
+            let followed = if chunk_size > 0 { held } else { 0 };
+            released
+                .saturating_add(followed)
+                .div_ceil(LINE_FEEDS.len())
+                .max(1)
+        } else {
+            1
+        };
+        N::max_output_size_for_chunk(LINE_FEEDS.len(), inner, false)
+            .saturating_mul(pieces)
+            .saturating_add(rest_size::<N, KEEP>(chunk_size, inner, is_last_chunk))
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+
+        let mut deferred = std::mem::take(&mut state.deferred);
+        let pending = deferred.len() + input.len();
+        if !release::<N, KEEP>(output, &mut write_pos, &mut state, pending, is_last_chunk)? {
+            deferred.extend_from_slice(input);
+            state.deferred = deferred;
+            return Ok(NormalizeChunkResult::new(write_pos, Some(state)));
+        }
+
+        let segments = [deferred.as_slice(), input];
+        for (i, &segment) in segments.iter().enumerate() {
+            let mut rest = segment;
+            if state.ends_with_cr && rest.first() == Some(&LF) {
+                // We found:
+                // - a LF completing a held back CR from the previous chunk
+                rest = &rest[1..];
+            }
+
+            // Everything after the last text is held back.
+            let run_start = rest
+                .iter()
+                .rposition(|&b| b != CR && b != LF)
+                .map_or(0, |i| i + 1);
+            if run_start > 0 {
+                state.released = std::mem::take(&mut state.held);
+                let later = &segments[i + 1..];
+                let pending = rest.len() + later.iter().map(|s| s.len()).sum::<usize>();
+                if !release::<N, KEEP>(output, &mut write_pos, &mut state, pending, is_last_chunk)?
+                {
+                    // The rest of the input waits for the remaining line endings.
+                    state.ends_with_cr = false;
+                    state.deferred = rest.to_vec();
+                    for segment in later {
+                        state.deferred.extend_from_slice(segment);
+                    }
+                    return Ok(NormalizeChunkResult::new(write_pos, Some(state)));
+                }
+                normalize_into::<N>(
+                    &rest[..run_start],
+                    output,
+                    &mut write_pos,
+                    &mut state.inner,
+                    false,
+                )?;
+            }
+            let run = &rest[run_start..];
+            state.held = state.held.saturating_add(count_line_endings(run));
+            if !segment.is_empty() {
+                state.ends_with_cr = run.last() == Some(&CR);
+            }
+        }
+
+        if is_last_chunk {
+            state.released = state.held.min(KEEP);
+            release::<N, KEEP>(output, &mut write_pos, &mut state, 0, true)?;
+            normalize_into::<N>(&[], output, &mut write_pos, &mut state.inner, true)?;
+            state.held = 0;
+            state.ends_with_cr = false;
+        }
+
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| {
+            // A `\r` at the boundary is a complete line ending, so an `\n` starting the next
+            // chunk is a line ending of its own.
+            state.ends_with_cr = false;
+            &mut state.inner
+        })
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+/// The number of line endings in `run`, which consists of `\r` and `\n` bytes only.
+fn count_line_endings(run: &[u8]) -> usize {
+    let mut count = 0;
+    let mut prev = 0;
+    for &b in run {
+        if !(b == LF && prev == CR) {
+            count += 1;
+        }
+        prev = b;
+    }
+    count
+}
+
+/// The worst-case output size for a chunk of `chunk_size` bytes, apart from the line endings
+/// followed by text: the chunk itself, the kept line endings and the final call to the wrapped
+/// format.
+fn rest_size<N: NormalizeChunk, const KEEP: usize>(
+    chunk_size: usize,
+    inner: Option<&N::State>,
+    is_last_chunk: bool,
+) -> usize {
+    N::max_output_size_for_chunk(LINE_FEEDS.len(), inner, false)
+        .saturating_mul(KEEP.div_ceil(LINE_FEEDS.len()))
+        .saturating_add(N::max_output_size_for_chunk(chunk_size, inner, false))
+        .saturating_add(N::max_output_size_for_chunk(0, inner, is_last_chunk))
+}
+
+/// Write the released line endings with `N` into `output` at `write_pos`, as many as fit while
+/// leaving room for `pending` more bytes of input, or all of them at the end of the stream.
+/// Returns whether all of them were written.
+fn release<N: NormalizeChunk, const KEEP: usize>(
+    output: &mut [MaybeUninit<u8>],
+    write_pos: &mut usize,
+    state: &mut TrimTrailingNewlinesState<N::State>,
+    pending: usize,
+    is_last_chunk: bool,
+) -> Result<bool> {
+    while state.released > 0 {
+        let piece = state.released.min(LINE_FEEDS.len());
+        let inner = state.inner.as_ref();
+        let room =
+            N::max_output_size_for_chunk(piece, inner, false).saturating_add(rest_size::<N, KEEP>(
+                pending,
+                inner,
+                is_last_chunk,
+            ));
+        if !is_last_chunk && output.len() - *write_pos < room {
+            break;
+        }
+        normalize_into::<N>(
+            &LINE_FEEDS[..piece],
+            output,
+            write_pos,
+            &mut state.inner,
+            false,
+        )?;
+        state.released -= piece;
+    }
+    Ok(state.released == 0)
+}
+
+// SAFETY: Only ASCII line endings are held back or replaced by `\n`; all other input passes
+// through the wrapped format.
+unsafe impl<N: PreservesUtf8, const KEEP: usize> PreservesUtf8 for TrimTrailingNewlines<N, KEEP> {}
@@ -0,0 +1,119 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        breaks::{self, BreakSet, Pending},
+        normalize_adapter_at_boundary, NormalizeChunk, NormalizeChunkResult, PreservesUtf8,
+    },
+    Error, Result,
+};
+
+/// U+2028 LINE SEPARATOR encoded as UTF-8.
+const LS: &[u8] = "\u{2028}".as_bytes();
+/// U+2029 PARAGRAPH SEPARATOR encoded as UTF-8.
+const PS: &[u8] = "\u{2029}".as_bytes();
+
+struct Separators;
+
+impl BreakSet for Separators {
+    const SEQUENCES: &'static [&'static [u8]] = &[LS, PS];
+}
+
+/// Format adapter that also treats the Unicode line and paragraph separators (U+2028 and
+/// U+2029) as line endings, converting them to the line ending of the wrapped format.
+///
+/// The input is expected to be UTF-8. A separator split across chunks is held back until the
+/// chunk completing it arrives. Everything else is left to the wrapped format, and every
+/// separator counts as exactly one line ending, also when directly preceded by `\r` or followed
+/// by `\n`.
+///
+/// ```
+/// use eolify::{Normalize, UnicodeSeparators, CRLF, LF};
+///
+/// assert_eq!(
+///     UnicodeSeparators::<LF>::normalize_str("a\u{2028}b\u{2029}c\r\n"),
+///     "a\nb\nc\n"
+/// );
+/// assert_eq!(
+///     UnicodeSeparators::<CRLF>::normalize_str("a\r\u{2028}b"),
+///     "a\r\n\r\nb"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UnicodeSeparators<N>(pub N);
+
+/// State carried between chunks by [`UnicodeSeparators`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnicodeSeparatorsState<S> {
+    inner: Option<S>,
+    /// The start of a separator at the end of the previous chunk that was held back.
+    pending: Pending,
+}
+
+impl<S> Default for UnicodeSeparatorsState<S> {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            pending: Pending::default(),
+        }
+    }
+}
+
+impl<N: NormalizeChunk> NormalizeChunk for UnicodeSeparators<N> {
+    type State = UnicodeSeparatorsState<N::State>;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        breaks::max_output_size_with::<Separators, N>(
+            chunk_size,
+            state.and_then(|state| state.inner.as_ref()),
+            is_last_chunk,
+        )
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let output_len = breaks::normalize_chunk_with::<Separators, N>(
+            input,
+            output,
+            &mut state.inner,
+            &mut state.pending,
+            is_last_chunk,
+        )?;
+        Ok(NormalizeChunkResult::new(output_len, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        normalize_adapter_at_boundary::<Self, N>(input, output, state, |state| &mut state.inner)
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The adapter preserves UTF-8 whenever `N` does, so `N` decides whether the
+        // output needs validating.
+        unsafe { N::output_to_string(output) }
+    }
+}
+
+// SAFETY: Only complete separators, which are valid UTF-8 on their own, are replaced by ASCII
+// bytes; everything else passes through the wrapped format.
+unsafe impl<N: PreservesUtf8> PreservesUtf8 for UnicodeSeparators<N> {}
@@ -0,0 +1,223 @@
+use std::mem::MaybeUninit;
+
+use crate::{
+    formats::{
+        breaks::{self, BreakSet, Pending, Piece},
+        copy,
+        custom::Delimiter,
+        NormalizeChunk, NormalizeChunkResult,
+    },
+    Error, Result,
+};
+
+/// Bit flags selecting the break sequences recognized by [`Universal`]. Combine them with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Breaks;
+
+impl Breaks {
+    /// `\r\n`.
+    pub const CRLF: u16 = 1 << 0;
+    /// `\n`.
+    pub const LF: u16 = 1 << 1;
+    /// `\r` alone.
+    pub const CR: u16 = 1 << 2;
+    /// NEL (U+0085) encoded as UTF-8.
+    pub const NEL: u16 = 1 << 3;
+    /// NEL encoded as the single Latin-1 byte `0x85`. Don't use this for UTF-8 input.
+    pub const NEL_LATIN1: u16 = 1 << 4;
+    /// LINE SEPARATOR (U+2028) encoded as UTF-8.
+    pub const LS: u16 = 1 << 5;
+    /// PARAGRAPH SEPARATOR (U+2029) encoded as UTF-8.
+    pub const PS: u16 = 1 << 6;
+    /// Form feed (`0x0C`).
+    pub const FF: u16 = 1 << 7;
+    /// Vertical tab (`0x0B`).
+    pub const VT: u16 = 1 << 8;
+
+    /// The line endings recognized by the `CRLF`, `LF` and `CR` formats.
+    pub const ASCII: u16 = Self::CRLF | Self::LF | Self::CR;
+    /// Everything Python's `str.splitlines` treats as a line break in UTF-8 text, apart from
+    /// the information separators `0x1C`-`0x1E`.
+    pub const UNICODE: u16 = Self::ASCII | Self::NEL | Self::LS | Self::PS | Self::FF | Self::VT;
+}
+
+/// The sequence of every flag of [`Breaks`], in order of the bits.
+const SEQUENCES: [&[u8]; 9] = [
+    b"\r\n",
+    b"\n",
+    b"\r",
+    "\u{85}".as_bytes(),
+    &[0x85],
+    "\u{2028}".as_bytes(),
+    "\u{2029}".as_bytes(),
+    &[0x0c],
+    &[0x0b],
+];
+
+const fn select(breaks: u16) -> [&'static [u8]; 9] {
+    let mut selected: [&[u8]; 9] = [&[]; 9];
+    let mut i = 0;
+    while i < SEQUENCES.len() {
+        if breaks & (1 << i) != 0 {
+            selected[i] = SEQUENCES[i];
+        }
+        i += 1;
+    }
+    selected
+}
+
+struct Selected<const BREAKS: u16>;
+
+impl<const BREAKS: u16> BreakSet for Selected<BREAKS> {
+    const SEQUENCES: &'static [&'static [u8]] = &select(BREAKS);
+}
+
+/// Normalization format with a configurable set of recognized line breaks, all written as the
+/// sequence of a single [`Delimiter`].
+///
+/// `BREAKS` combines the flags of [`Breaks`]; bytes not part of a selected sequence are copied
+/// unchanged. Where sequences overlap the longest one wins, so with both `CRLF` and `CR`
+/// selected, `\r\n` is a single break. `LF`, `CRLF` and `CR` implement `Delimiter` and can be
+/// used as target:
+///
+/// ```
+/// use eolify::{Breaks, Normalize, Universal, CRLF, LF};
+///
+/// type SplitLines = Universal<{ Breaks::UNICODE }, LF>;
+/// assert_eq!(SplitLines::normalize_str("a\r\nb\u{2028}c\x0cd"), "a\nb\nc\nd");
+///
+/// // Only `\n` and NEL are breaks, a lone `\r` stays.
+/// type Mainframe = Universal<{ Breaks::LF | Breaks::NEL }, CRLF>;
+/// assert_eq!(Mainframe::normalize_str("a\u{85}b\rc\n"), "a\r\nb\rc\r\n");
+/// ```
+///
+/// Since the output depends on the selected breaks and on `D`, `Universal` does not implement
+/// `PreservesUtf8`; use `normalize_str`, which validates the output, for text.
+pub struct Universal<const BREAKS: u16, D: Delimiter>(std::marker::PhantomData<D>);
+
+// Implemented by hand so `D` doesn't need to implement the traits itself.
+impl<const BREAKS: u16, D: Delimiter> Clone for Universal<BREAKS, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const BREAKS: u16, D: Delimiter> Copy for Universal<BREAKS, D> {}
+
+impl<const BREAKS: u16, D: Delimiter> PartialEq for Universal<BREAKS, D> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<const BREAKS: u16, D: Delimiter> Eq for Universal<BREAKS, D> {}
+
+impl<const BREAKS: u16, D: Delimiter> Default for Universal<BREAKS, D> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<const BREAKS: u16, D: Delimiter> std::fmt::Debug for Universal<BREAKS, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Universal")
+            .field("breaks", &format_args!("{BREAKS:#06x}"))
+            .field("delimiter", &String::from_utf8_lossy(D::BYTES))
+            .finish()
+    }
+}
+
+impl<const BREAKS: u16, D: Delimiter> Universal<BREAKS, D> {
+    /// The worst-case output size for a chunk of `chunk_size` bytes, usable in const contexts
+    /// such as sizing a stack buffer. Equal to `max_output_size_for_chunk` for any state.
+    #[must_use]
+    pub const fn max_output_size(chunk_size: usize, _is_last_chunk: bool) -> usize {
+        // Up to two held back bytes may be written along with this chunk, and every byte may
+        // be a break of its own.
+        let expansion = if D::BYTES.len() > 1 {
+            D::BYTES.len()
+        } else {
+            1
+        };
+        chunk_size
+            .saturating_add(breaks::MAX_SEQUENCE_LEN - 1)
+            .saturating_mul(expansion)
+    }
+}
+
+/// State carried between chunks by [`Universal`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniversalState {
+    /// The start of a break at the end of the previous chunk that was held back.
+    pending: Pending,
+}
+
+impl<const BREAKS: u16, D: Delimiter> NormalizeChunk for Universal<BREAKS, D> {
+    type State = UniversalState;
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> usize {
+        Self::max_output_size(chunk_size, is_last_chunk)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let mut state = state.cloned().unwrap_or_default();
+        let mut write_pos = 0;
+        breaks::scan::<Selected<BREAKS>>(input, &mut state.pending, is_last_chunk, |piece| {
+            write_piece::<D>(piece, output, &mut write_pos);
+            Ok(())
+        })?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+
+    fn normalize_chunk_at_boundary(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, false)
+            .saturating_add(Self::max_output_size_for_chunk(0, state, true));
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+
+        let status = Self::normalize_chunk(input, output, state, false)?;
+        let mut state = status.state().cloned().unwrap_or_default();
+        let mut write_pos = status.output_len();
+        breaks::resolve_cr::<Selected<BREAKS>>(&mut state.pending, |piece| {
+            write_piece::<D>(piece, output, &mut write_pos);
+            Ok(())
+        })?;
+        Ok(NormalizeChunkResult::new(write_pos, Some(state)))
+    }
+}
+
+/// Write a piece found by the scan to `output` at `write_pos`, with breaks replaced by `D`.
+fn write_piece<D: Delimiter>(
+    piece: Piece<'_>,
+    output: &mut [MaybeUninit<u8>],
+    write_pos: &mut usize,
+) {
+    match piece {
+        Piece::Text(text) => copy(text, output, write_pos),
+        Piece::Break => copy(D::BYTES, output, write_pos),
+    }
+}
@@ -0,0 +1,129 @@
+use std::mem::MaybeUninit;
+
+use memchr::memchr2;
+
+use crate::{
+    formats::{copy, NormalizeChunk, NormalizeChunkResult, PreservesUtf8},
+    types::{CR, LF},
+    Error, Result,
+};
+
+/// Diagnostic format that makes line endings visible instead of converting them, like
+/// `cat -A`.
+///
+/// Every CR is rendered as `^M` and every LF as `$` followed by the LF itself, so a CRLF line
+/// ends in `^M$` and a lone CR stays on the same line. All other bytes are copied unchanged.
+///
+/// ```
+/// use eolify::{Normalize, Visible};
+///
+/// assert_eq!(Visible::normalize(b"a\r\nb\rc\n"), b"a^M$\nb^Mc$\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Visible;
+
+/// Diagnostic format that makes line endings visible with the Unicode control pictures
+/// `␍` (U+240D) and `␊` (U+240A) instead of converting them.
+///
+/// Every LF is rendered as `␊` followed by the LF itself, every CR as `␍`. All other bytes are
+/// copied unchanged.
+///
+/// ```
+/// use eolify::{Normalize, VisibleSymbols};
+///
+/// assert_eq!(VisibleSymbols::normalize_str("a\r\nb\n"), "a␍␊\nb␊\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct VisibleSymbols;
+
+impl NormalizeChunk for Visible {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> usize {
+        chunk_size.saturating_mul(2)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+        Ok(render(input, output, b"^M", b"$\n"))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+impl NormalizeChunk for VisibleSymbols {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> usize {
+        chunk_size.saturating_mul(4)
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        state: Option<&Self::State>,
+        is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let output_required = Self::max_output_size_for_chunk(input.len(), state, is_last_chunk);
+        if output.len() < output_required {
+            return Err(Error::OutputBufferTooSmall {
+                required: output_required,
+            });
+        }
+        Ok(render(input, output, "␍".as_bytes(), "␊\n".as_bytes()))
+    }
+
+    unsafe fn output_to_string(output: Vec<u8>) -> String {
+        // SAFETY: The format preserves UTF-8, see its `PreservesUtf8` impl, and the caller
+        // passes the output of normalizing valid UTF-8.
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+}
+
+/// Copy `input` to `output`, replacing every CR with `cr_marker` and every LF with `lf_marker`.
+/// Each byte is rendered on its own, so no state has to be carried between chunks.
+fn render(
+    input: &[u8],
+    output: &mut [MaybeUninit<u8>],
+    cr_marker: &[u8],
+    lf_marker: &[u8],
+) -> NormalizeChunkResult<()> {
+    let mut read_pos = 0;
+    let mut write_pos = 0;
+    while let Some(i) = memchr2(CR, LF, &input[read_pos..]).map(|i| i + read_pos) {
+        copy(&input[read_pos..i], output, &mut write_pos);
+        let marker = if input[i] == CR { cr_marker } else { lf_marker };
+        copy(marker, output, &mut write_pos);
+        read_pos = i + 1;
+    }
+    copy(&input[read_pos..], output, &mut write_pos);
+    NormalizeChunkResult::new(write_pos, None)
+}
+
+// SAFETY: The ASCII bytes `\r` and `\n` are replaced by ASCII markers.
+unsafe impl PreservesUtf8 for Visible {}
+
+// SAFETY: The ASCII bytes `\r` and `\n` are replaced by complete UTF-8 sequences.
+unsafe impl PreservesUtf8 for VisibleSymbols {}
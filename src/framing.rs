@@ -0,0 +1,171 @@
+//! The `framing` module slices normalized text into fixed-size records, such as the 80-byte
+//! card images used for mainframe interchange.
+//!
+//! Each line becomes exactly one record of `width` bytes with its line terminator removed. The
+//! input is expected to be normalized already, for example with [`LF`](crate::LF) or
+//! [`CRLF`](crate::CRLF); both `\n` and `\r\n` are accepted as line terminators.
+//!
+//! ```
+//! use eolify::{framing::FixedFrames, Normalize, LF};
+//!
+//! let normalized = LF::normalize(b"PUNCH\r\nCARDS\r\n");
+//! let frames = FixedFrames::new(8).frame(&normalized).unwrap();
+//! assert_eq!(frames, b"PUNCH   CARDS   ");
+//! ```
+
+use core::fmt;
+
+use crate::types::{CR, LF};
+
+/// How the unused space at the end of a record is filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameFill {
+    /// Fill the record with the given byte.
+    Pad(u8),
+    /// Write `terminator` directly after the line, then fill the rest of the record with `pad`.
+    /// Lines must leave room for the terminator.
+    Terminated { terminator: u8, pad: u8 },
+}
+
+/// What happens to lines that do not fit in a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FrameOverflow {
+    /// Fail with [`FrameError::LineTooLong`].
+    #[default]
+    Error,
+    /// Cut the line off at the record width.
+    Truncate,
+    /// Continue the line in as many records as needed.
+    Wrap,
+}
+
+/// Error returned when framing fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    /// Line `line` (1-based) is `len` bytes long, which does not fit in a record.
+    LineTooLong { line: usize, len: usize },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::LineTooLong { line, len } => {
+                write!(
+                    f,
+                    "line {line} is {len} bytes long and does not fit in a frame"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+/// Configuration for slicing lines into fixed-size records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedFrames {
+    width: usize,
+    fill: FrameFill,
+    overflow: FrameOverflow,
+}
+
+impl FixedFrames {
+    /// Create a framing of `width` bytes per record, padded with spaces and rejecting lines
+    /// that are too long.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    #[must_use]
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "frame width must be non-zero");
+        Self {
+            width,
+            fill: FrameFill::Pad(b' '),
+            overflow: FrameOverflow::Error,
+        }
+    }
+
+    /// Set how the unused space at the end of a record is filled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is [`FrameFill::Terminated`] and the width leaves no room for content.
+    #[must_use]
+    pub fn fill(mut self, fill: FrameFill) -> Self {
+        assert!(
+            !matches!(fill, FrameFill::Terminated { .. }) || self.width > 1,
+            "terminated frames need a width of at least two bytes"
+        );
+        self.fill = fill;
+        self
+    }
+
+    /// Set what happens to lines that do not fit in a record.
+    #[must_use]
+    pub fn overflow(mut self, overflow: FrameOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns the number of bytes per record.
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Slice the lines of `normalized` into records.
+    ///
+    /// A final line without a line terminator still produces a record; empty input produces
+    /// no records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FrameError::LineTooLong`] if a line does not fit and the overflow policy is
+    /// [`FrameOverflow::Error`].
+    pub fn frame(&self, normalized: &[u8]) -> Result<Vec<u8>, FrameError> {
+        let capacity = match self.fill {
+            FrameFill::Pad(_) => self.width,
+            FrameFill::Terminated { .. } => self.width - 1,
+        };
+
+        let mut output = Vec::with_capacity(normalized.len() + self.width);
+        for (idx, line) in normalized.split_inclusive(|&b| b == LF).enumerate() {
+            let line = line.strip_suffix(&[LF]).unwrap_or(line);
+            let line = line.strip_suffix(&[CR]).unwrap_or(line);
+
+            if line.len() <= capacity {
+                self.push_record(&mut output, line);
+                continue;
+            }
+            match self.overflow {
+                FrameOverflow::Error => {
+                    return Err(FrameError::LineTooLong {
+                        line: idx + 1,
+                        len: line.len(),
+                    })
+                }
+                FrameOverflow::Truncate => self.push_record(&mut output, &line[..capacity]),
+                FrameOverflow::Wrap => {
+                    for part in line.chunks(capacity) {
+                        self.push_record(&mut output, part);
+                    }
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    fn push_record(&self, output: &mut Vec<u8>, content: &[u8]) {
+        let record_end = output.len() + self.width;
+        output.extend_from_slice(content);
+        let pad = match self.fill {
+            FrameFill::Pad(pad) => pad,
+            FrameFill::Terminated { terminator, pad } => {
+                output.push(terminator);
+                pad
+            }
+        };
+        output.resize(record_end, pad);
+    }
+}
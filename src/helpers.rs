@@ -1,5 +1,9 @@
 use std::mem::MaybeUninit;
 
+use memchr::{memchr2, memchr2_iter};
+
+use crate::types::{LineTerminator, CR, LF};
+
 pub fn vec_to_uninit_mut(vec: &mut Vec<u8>) -> &mut [MaybeUninit<u8>] {
     unsafe {
         std::slice::from_raw_parts_mut(vec.as_mut_ptr().cast::<MaybeUninit<u8>>(), vec.capacity())
@@ -9,3 +13,88 @@ pub fn vec_to_uninit_mut(vec: &mut Vec<u8>) -> &mut [MaybeUninit<u8>] {
 pub fn slice_to_uninit_mut(slice: &mut [u8]) -> &mut [MaybeUninit<u8>] {
     unsafe { &mut *(std::ptr::from_mut::<[u8]>(slice) as *mut [MaybeUninit<u8>]) }
 }
+
+/// Find the first line ending in `buf` at or after `from`, returning its position and kind.
+///
+/// To visit all line endings, continue searching at the returned position plus the length of
+/// the terminator. Like [`LineTerminator::classify_at`], a `\r` at the very end of `buf` is
+/// reported as `Cr`; streaming callers should hold it back until more data arrives.
+///
+/// ```
+/// use eolify::{helpers::find_next_eol, LineTerminator};
+///
+/// let buf = b"one\r\ntwo\nthree";
+/// assert_eq!(find_next_eol(buf, 0), Some((3, LineTerminator::CrLf)));
+/// assert_eq!(find_next_eol(buf, 5), Some((8, LineTerminator::Lf)));
+/// assert_eq!(find_next_eol(buf, 9), None);
+/// ```
+#[must_use]
+pub fn find_next_eol(buf: &[u8], from: usize) -> Option<(usize, LineTerminator)> {
+    let pos = from + memchr2(CR, LF, buf.get(from..)?)?;
+    LineTerminator::classify_at(buf, pos).map(|terminator| (pos, terminator))
+}
+
+/// Split `input` at `approx_pos`, moving the split point one byte forward if it would fall
+/// between the `\r` and `\n` of a CRLF. `approx_pos` is clamped to the length of `input`.
+///
+/// Both halves can then be normalized independently, as complete streams, to any of the
+/// line-ending formats, and the concatenated results equal normalizing `input` at once. This
+/// allows sharding input for parallel normalization without carrying state between shards.
+#[must_use]
+pub fn split_at_safe_boundary(input: &[u8], approx_pos: usize) -> (&[u8], &[u8]) {
+    let mut pos = approx_pos.min(input.len());
+    if pos > 0 && input[pos - 1] == CR && input.get(pos) == Some(&LF) {
+        pos += 1;
+    }
+    input.split_at(pos)
+}
+
+/// Counts the `\n`, `\r\n` and lone `\r` line endings of a stream, chunk by chunk.
+///
+/// A `\r\n` split across two chunks is counted with the chunk holding the `\n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct LineEndingCounter {
+    pending_cr: bool,
+}
+
+impl LineEndingCounter {
+    /// Whether the last chunk ended with a `\r` that was not counted yet.
+    pub(crate) fn has_pending_cr(&self) -> bool {
+        self.pending_cr
+    }
+
+    /// Count the line endings completed by `chunk`, returned as `(lf, crlf, cr)`.
+    ///
+    /// An empty chunk that doesn't end the stream leaves a held back `\r` pending, as the next
+    /// chunk may still start with its `\n`.
+    pub(crate) fn count(&mut self, chunk: &[u8], is_last_chunk: bool) -> (u64, u64, u64) {
+        if chunk.is_empty() && !is_last_chunk {
+            return (0, 0, 0);
+        }
+        let (mut lf, mut crlf, mut cr) = (0, 0, 0);
+        let mut scan = chunk;
+        if self.pending_cr {
+            if scan.first() == Some(&LF) {
+                crlf += 1;
+                scan = &scan[1..];
+            } else {
+                cr += 1;
+            }
+            self.pending_cr = false;
+        }
+        for i in memchr2_iter(CR, LF, scan) {
+            match LineTerminator::classify_at(scan, i) {
+                Some(LineTerminator::Lf) if i > 0 && scan[i - 1] == CR => {}
+                Some(LineTerminator::Lf) => lf += 1,
+                Some(LineTerminator::CrLf) => crlf += 1,
+                Some(LineTerminator::Cr) if i + 1 == scan.len() && !is_last_chunk => {
+                    self.pending_cr = true;
+                }
+                Some(LineTerminator::Cr) => cr += 1,
+                None => unreachable!("memchr2 only finds line ending bytes"),
+            }
+        }
+        (lf, crlf, cr)
+    }
+}
@@ -2,18 +2,97 @@
 
 mod types;
 
-pub use types::{Error, Result};
+pub use types::{Error, LineTerminator, Result};
 
 mod formats;
-pub use formats::{crlf::CRLF, lf::LF, Normalize, NormalizeChunk, NormalizeChunkResult};
+pub use formats::{
+    bom::{AddBom, Bom, BomState, StripBom},
+    checked::Checked,
+    collapse_blank_lines::{CollapseBlankLines, CollapseBlankLinesState},
+    collapse_cr_crlf::{CollapseCrCrLf, CollapseCrCrLfState},
+    cr::CR,
+    crlf::CRLF,
+    custom::{Custom, Delimiter},
+    ff_vt_breaks::{FfVtBreaks, FfVtBreaksState},
+    final_newline::{EnsureFinalNewline, EnsureFinalNewlineState},
+    keep_lone_cr::{KeepLoneCr, KeepLoneCrState},
+    lf::LF,
+    native::Native,
+    ndjson::{NdjsonState, NDJSON},
+    nel::{Nel, NelLatin1, NelState},
+    nul::{NulToLf, NUL},
+    preserve_lines::{PreserveLines, PreserveLinesState},
+    terminal::{Terminal, TerminalState},
+    tidy::{Tidy, TidyState},
+    trim_trailing::{TrimTrailingNewlines, TrimTrailingNewlinesState},
+    unicode_separators::{UnicodeSeparators, UnicodeSeparatorsState},
+    universal::{Breaks, Universal, UniversalState},
+    visible::{Visible, VisibleSymbols},
+    Normalize, NormalizeChunk, NormalizeChunkResult, NormalizedChunks, PreservesUtf8,
+};
 
 mod wrappers;
-pub use wrappers::io::{IoExt, ReadExt, WriteExt};
+pub use wrappers::chunks::{ChunkReader, ChunksExt};
+pub use wrappers::commit::CommitWriter;
+pub use wrappers::console::{ConsoleWriter, PipeEol};
+pub use wrappers::eol::{EolDispatch, EolFormat, EolReader, EolWriter, ParseEolFormatError};
+pub use wrappers::fmt::{FmtWriteExt, FmtWriter};
+pub use wrappers::io::{IoExt, ReadExt, Reader, WriteExt, Writer};
+pub use wrappers::spool::SpooledOutput;
+pub use wrappers::stats::{ChunkStats, EolStats, WindowedStats};
+pub use wrappers::tee::{HashWriter, TeeExt, TeeReader, TeeWriter};
+pub use wrappers::{BufferSizeHint, FlushPolicy};
 
 #[cfg(feature = "futures-io")]
-pub use wrappers::futures_io::{FuturesIoAsyncReadExt, FuturesIoAsyncWriteExt, FuturesIoExt};
+pub use wrappers::futures_io::{
+    AsyncReader as FuturesIoAsyncReader, AsyncWriter as FuturesIoAsyncWriter,
+    EolAsyncReader as FuturesIoEolAsyncReader, EolAsyncWriter as FuturesIoEolAsyncWriter,
+    FuturesIoAsyncReadExt, FuturesIoAsyncWriteExt, FuturesIoExt,
+};
 
 #[cfg(feature = "tokio")]
-pub use wrappers::tokio::{TokioAsyncReadExt, TokioAsyncWriteExt, TokioExt};
+pub use wrappers::tokio::{
+    AsyncReader as TokioAsyncReader, AsyncWriter as TokioAsyncWriter,
+    EolAsyncReader as TokioEolAsyncReader, EolAsyncWriter as TokioEolAsyncWriter, FinishTimeout,
+    TokioAsyncReadExt, TokioAsyncStreamExt, TokioAsyncWriteExt, TokioExt,
+};
+
+mod capabilities;
+pub use capabilities::{capabilities, Capabilities, Features, Limits};
+
+mod convert;
+pub use convert::{convert, convert_with, ConvertError, RepairPolicy};
+
+mod edit;
+pub use edit::{NormalizeEdit, NormalizedEdit};
+
+mod multi;
+pub use multi::MultiNormalizer;
+
+mod builder;
+pub use builder::{CopyOutcome, Normalizer, NormalizerBuilder};
+
+pub mod check;
+
+pub mod classify;
+
+pub mod codegen;
+
+pub mod framing;
 
 pub mod helpers;
+
+pub mod registry;
+
+pub mod sample;
+
+pub mod trace;
+
+#[doc(hidden)]
+pub mod assert;
+
+#[cfg(feature = "bench-util")]
+pub mod bench;
+
+#[cfg(feature = "bench-util")]
+pub mod compare;
@@ -0,0 +1,105 @@
+//! The `multi` module provides normalization of many interleaved streams through a single
+//! context, keeping the state of each stream under a key.
+
+use std::{borrow::Borrow, collections::HashMap, hash::Hash, marker::PhantomData};
+
+use crate::NormalizeChunk;
+
+/// Normalizes many independent streams, such as connections of an event-driven server, in one
+/// processing loop.
+///
+/// Only the chunk state of each stream is kept, keyed by `K`; the output buffer is shared, so
+/// no wrapper has to be allocated per stream. The returned output borrows that buffer and has
+/// to be consumed before the next call.
+///
+/// ```
+/// use eolify::{MultiNormalizer, CRLF};
+///
+/// let mut streams = MultiNormalizer::<u32, CRLF>::new();
+/// assert_eq!(streams.normalize_for(1, b"a\r"), b"a\r");
+/// assert_eq!(streams.normalize_for(2, b"\nb"), b"\r\nb");
+/// // The `\n` completes the `\r` seen earlier on stream 1.
+/// assert_eq!(streams.normalize_for(1, b"\nc"), b"\nc");
+/// assert_eq!(streams.finish(&1), b"");
+/// ```
+pub struct MultiNormalizer<K, N: NormalizeChunk> {
+    _phantom: PhantomData<N>,
+    states: HashMap<K, Option<N::State>>,
+    output: Vec<u8>,
+}
+
+impl<K: Eq + Hash, N: NormalizeChunk> MultiNormalizer<K, N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+            states: HashMap::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Normalize the next `chunk` of the stream identified by `key` and return the output.
+    ///
+    /// A stream is started implicitly by the first chunk for a new key.
+    pub fn normalize_for(&mut self, key: K, chunk: &[u8]) -> &[u8] {
+        let state = self.states.entry(key).or_default();
+        self.output.clear();
+        let status = N::normalize_chunk_into(chunk, &mut self.output, state.as_ref(), false)
+            .unwrap_or_else(|err| unreachable!("{err} (should be impossible)",));
+        *state = status.state().cloned();
+        &self.output
+    }
+
+    /// End the stream identified by `key` and return any output still pending, such as a
+    /// resolved trailing `\r`. The state of the stream is dropped.
+    ///
+    /// Finishing a key without state (unknown or already finished) finishes an empty stream.
+    pub fn finish<Q>(&mut self, key: &Q) -> &[u8]
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let state = self.states.remove(key).flatten();
+        self.output.clear();
+        N::normalize_chunk_into(&[], &mut self.output, state.as_ref(), true)
+            .unwrap_or_else(|err| unreachable!("{err} (should be impossible)",));
+        &self.output
+    }
+
+    /// Drop the state of the stream identified by `key` without finishing it, e.g. when a
+    /// connection was aborted. Returns `true` if the stream was known.
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.states.remove(key).is_some()
+    }
+
+    /// Returns `true` if a stream is known for `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.states.contains_key(key)
+    }
+
+    /// Returns the number of streams currently tracked.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns `true` if no streams are tracked.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+impl<K: Eq + Hash, N: NormalizeChunk> Default for MultiNormalizer<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,98 @@
+//! The `registry` module maps format names to normalizers, so formats can be selected from
+//! configuration and contributed by third parties at runtime.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use crate::{
+    wrappers::{io::Reader, DEFAULT_BUFFER_SIZE},
+    Normalize, NormalizeChunk, CR, CRLF, LF, NDJSON,
+};
+
+/// Object-safe view on a normalization format, as stored in the registry.
+///
+/// This is implemented for every [`NormalizeChunk`] that is `Send + Sync + 'static`.
+pub trait DynFormat: Send + Sync {
+    /// Normalize a complete buffer at once.
+    fn normalize(&self, input: &[u8]) -> Vec<u8>;
+
+    /// Wrap a reader with a newline-normalizing reader.
+    fn reader<'a>(&self, reader: Box<dyn Read + 'a>) -> Box<dyn Read + 'a>;
+
+    /// Copy all data from `reader` to `writer`, normalizing it on the way.
+    ///
+    /// Returns the number of bytes written to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader` or writing to `writer`.
+    fn copy(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> std::io::Result<u64>;
+}
+
+impl<N: NormalizeChunk + Send + Sync + 'static> DynFormat for N {
+    fn normalize(&self, input: &[u8]) -> Vec<u8> {
+        <N as Normalize>::normalize(input)
+    }
+
+    fn reader<'a>(&self, reader: Box<dyn Read + 'a>) -> Box<dyn Read + 'a> {
+        Box::new(Reader::<_, N>::new(reader, DEFAULT_BUFFER_SIZE))
+    }
+
+    fn copy(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> std::io::Result<u64> {
+        std::io::copy(
+            &mut Reader::<_, N>::new(reader, DEFAULT_BUFFER_SIZE),
+            writer,
+        )
+    }
+}
+
+/// Constructs a fresh instance of a registered format.
+pub type FormatFactory = fn() -> Box<dyn DynFormat>;
+
+fn registry() -> &'static RwLock<HashMap<String, FormatFactory>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, FormatFactory>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut formats = HashMap::new();
+        formats.insert("cr".to_owned(), (|| Box::new(CR)) as FormatFactory);
+        formats.insert("crlf".to_owned(), (|| Box::new(CRLF)) as FormatFactory);
+        formats.insert("lf".to_owned(), (|| Box::new(LF)) as FormatFactory);
+        formats.insert("ndjson".to_owned(), (|| Box::new(NDJSON)) as FormatFactory);
+        RwLock::new(formats)
+    })
+}
+
+/// Register `factory` under `name`, replacing and returning any factory registered before.
+///
+/// The built-in formats are registered as `"cr"`, `"crlf"`, `"lf"` and `"ndjson"`.
+pub fn register_format(name: impl Into<String>, factory: FormatFactory) -> Option<FormatFactory> {
+    registry()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.into(), factory)
+}
+
+/// Construct the format registered under `name`, if any.
+#[must_use]
+pub fn format(name: &str) -> Option<Box<dyn DynFormat>> {
+    let factory = *registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name)?;
+    Some(factory())
+}
+
+/// Returns the names of all registered formats in alphabetical order.
+#[must_use]
+pub fn format_names() -> Vec<String> {
+    let mut names: Vec<String> = registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
@@ -0,0 +1,162 @@
+//! The `sample` module extracts a few evenly spaced lines from large inputs, normalized, for
+//! quick previews. Only the sampled lines are read, so memory use is bounded by the number of
+//! samples regardless of the size of the input.
+//!
+//! ```
+//! use std::io::Cursor;
+//! use eolify::{sample::sample_lines, LF};
+//!
+//! let input = Cursor::new(b"one\r\ntwo\r\nthree\r\nfour\r\n");
+//! let samples = sample_lines::<LF, _>(input, 2)?;
+//! assert_eq!(samples[0].normalized, b"one\n");
+//! assert_eq!(samples[1].raw, b"four\r\n");
+//! assert!(samples[1].needs_normalization());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{helpers::find_next_eol, types::LineTerminator, Normalize, NormalizeChunk};
+
+/// The longest line that is sampled in full; longer lines are truncated.
+pub const MAX_SAMPLE_LINE_LEN: usize = 64 * 1024;
+
+/// A line sampled by [`sample_lines`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SampledLine {
+    /// Offset of the start of the line in the input.
+    pub offset: u64,
+    /// The line as found in the input, including its line ending if it has one.
+    pub raw: Vec<u8>,
+    /// The line normalized on its own, as a complete stream.
+    pub normalized: Vec<u8>,
+    /// Whether the line was longer than [`MAX_SAMPLE_LINE_LEN`] and was cut off.
+    pub truncated: bool,
+}
+
+impl SampledLine {
+    /// Returns `true` if normalizing changed the line.
+    #[must_use]
+    pub fn needs_normalization(&self) -> bool {
+        self.raw != self.normalized
+    }
+}
+
+/// Sample up to `n` evenly spaced lines from `reader`, normalized to `N`.
+///
+/// The input is divided into `n` equally sized byte ranges and the first line starting in each
+/// range is sampled, seeking past everything else. Fewer lines are returned if the input has
+/// fewer lines than ranges.
+///
+/// # Errors
+///
+/// Returns any error from seeking or reading `reader`.
+pub fn sample_lines<N: NormalizeChunk, R: Read + Seek>(
+    reader: R,
+    n: usize,
+) -> std::io::Result<Vec<SampledLine>> {
+    let mut reader = BufReader::new(reader);
+    let len = reader.seek(SeekFrom::End(0))?;
+    let mut samples = Vec::with_capacity(n);
+    let mut next_start = 0;
+
+    for i in 0..n as u64 {
+        // The `as` conversion is lossless: the quotient is below `len`.
+        #[allow(clippy::cast_possible_truncation)]
+        let target = (u128::from(len) * u128::from(i) / n as u128) as u64;
+        let mut offset = next_start;
+        if target > next_start {
+            // Start at the first line that begins after the byte before `target`, so a target
+            // right at the start of a line selects that line.
+            reader.seek(SeekFrom::Start(target - 1))?;
+            offset = target - 1 + read_line(&mut reader, &mut None)?;
+        } else {
+            reader.seek(SeekFrom::Start(offset))?;
+        }
+        if offset >= len {
+            break;
+        }
+
+        let mut raw = Some(Vec::new());
+        next_start = offset + read_line(&mut reader, &mut raw)?;
+        let raw = raw.unwrap_or_default();
+        samples.push(SampledLine {
+            offset,
+            normalized: N::normalize(&raw),
+            truncated: raw.len() < usize::try_from(next_start - offset).unwrap_or(usize::MAX),
+            raw,
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Sample up to `n` evenly spaced lines from the file at `path`, see [`sample_lines`].
+///
+/// # Errors
+///
+/// Returns any error from opening, seeking or reading the file.
+pub fn sample_lines_path<N: NormalizeChunk>(
+    path: impl AsRef<Path>,
+    n: usize,
+) -> std::io::Result<Vec<SampledLine>> {
+    sample_lines::<N, _>(File::open(path)?, n)
+}
+
+/// Read one line including its line ending, returning the number of bytes consumed. If `line`
+/// is given, up to [`MAX_SAMPLE_LINE_LEN`] bytes of the line are collected into it.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut Option<Vec<u8>>) -> std::io::Result<u64> {
+    let mut consumed = 0;
+    loop {
+        let buf = match reader.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf.is_empty() {
+            return Ok(consumed);
+        }
+
+        let (used, done) = match find_next_eol(buf, 0) {
+            // A `\r` at the end of the buffer might be followed by a `\n` in the next one.
+            Some((pos, LineTerminator::Cr)) if pos + 1 == buf.len() => (buf.len(), false),
+            Some((pos, terminator)) => (pos + terminator.len(), true),
+            None => (buf.len(), false),
+        };
+        let ends_with_cr = buf[used - 1] == b'\r' && !done;
+        if let Some(line) = line {
+            let room = MAX_SAMPLE_LINE_LEN.saturating_sub(line.len());
+            line.extend_from_slice(&buf[..used.min(room)]);
+        }
+        reader.consume(used);
+        consumed += used as u64;
+
+        if ends_with_cr {
+            // Complete the line ending: either a `\n` follows, or the `\r` stands alone.
+            let next = loop {
+                match reader.fill_buf() {
+                    Ok(buf) => break buf.first().copied(),
+                    Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err),
+                }
+            };
+            if next == Some(b'\n') {
+                if let Some(line) = line {
+                    if line.len() < MAX_SAMPLE_LINE_LEN {
+                        line.push(b'\n');
+                    }
+                }
+                reader.consume(1);
+                consumed += 1;
+            }
+            return Ok(consumed);
+        }
+        if done {
+            return Ok(consumed);
+        }
+    }
+}
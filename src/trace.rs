@@ -0,0 +1,154 @@
+//! The `trace` module records the sequence of chunk calls made by the wrappers and replays it
+//! against a format, so a problem seen on a production stream can be reproduced exactly.
+//!
+//! A [`ChunkTrace`] only holds the size of every chunk and whether it was the last one; the
+//! state passed between the calls follows from replaying them in order. Traces have a compact
+//! text form, a comma separated list of chunk sizes with `!` marking the last chunk:
+//!
+//! ```
+//! use eolify::{trace::{replay, ChunkTrace}, CRLF};
+//!
+//! let trace: ChunkTrace = "3,2,0!".parse().unwrap();
+//! assert_eq!(replay::<CRLF>(&trace, b"ab\r\nc").unwrap(), b"ab\r\nc");
+//! ```
+//!
+//! The wrappers record traces when the `trace` feature is enabled, see `with_trace` on
+//! [`Reader`](crate::Reader) and [`Writer`](crate::Writer).
+
+use core::fmt;
+use std::str::FromStr;
+
+use crate::NormalizeChunk;
+
+/// A single recorded call of `normalize_chunk`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TracedCall {
+    /// The number of input bytes in the chunk.
+    pub len: usize,
+    /// Whether the chunk was passed as the last chunk.
+    pub is_last_chunk: bool,
+}
+
+/// A recorded sequence of chunk calls.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ChunkTrace {
+    calls: Vec<TracedCall>,
+}
+
+impl ChunkTrace {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a call to the trace.
+    pub fn push(&mut self, len: usize, is_last_chunk: bool) {
+        self.calls.push(TracedCall { len, is_last_chunk });
+    }
+
+    /// Returns the recorded calls in order.
+    #[must_use]
+    pub fn calls(&self) -> &[TracedCall] {
+        &self.calls
+    }
+
+    /// Returns the total number of input bytes covered by the trace.
+    #[must_use]
+    pub fn input_len(&self) -> usize {
+        self.calls.iter().map(|call| call.len).sum()
+    }
+}
+
+impl fmt::Display for ChunkTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, call) in self.calls.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", call.len)?;
+            if call.is_last_chunk {
+                f.write_str("!")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a trace can't be parsed or doesn't match the replayed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceError {
+    /// The text form contains an invalid chunk size.
+    InvalidCall(String),
+    /// The trace covers `trace_len` bytes, but the input is `input_len` bytes long.
+    LengthMismatch { trace_len: usize, input_len: usize },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::InvalidCall(call) => write!(f, "invalid chunk call `{call}` in trace"),
+            TraceError::LengthMismatch {
+                trace_len,
+                input_len,
+            } => write!(
+                f,
+                "trace covers {trace_len} bytes but the input is {input_len} bytes long"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl FromStr for ChunkTrace {
+    type Err = TraceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut trace = ChunkTrace::new();
+        for call in s.split(',').map(str::trim).filter(|call| !call.is_empty()) {
+            let (len, is_last_chunk) = match call.strip_suffix('!') {
+                Some(len) => (len, true),
+                None => (call, false),
+            };
+            let len = len
+                .parse()
+                .map_err(|_| TraceError::InvalidCall(call.to_string()))?;
+            trace.push(len, is_last_chunk);
+        }
+        Ok(trace)
+    }
+}
+
+/// Replay the chunk calls of `trace` over `input` with format `N` and return the concatenated
+/// output.
+///
+/// The state returned by every call is passed on to the next one, exactly like the wrappers do.
+///
+/// # Errors
+///
+/// Returns [`TraceError::LengthMismatch`] if the trace doesn't cover exactly `input`.
+///
+/// # Panics
+///
+/// Panics if `N` reports an error for a correctly sized output buffer.
+pub fn replay<N: NormalizeChunk>(trace: &ChunkTrace, input: &[u8]) -> Result<Vec<u8>, TraceError> {
+    if trace.input_len() != input.len() {
+        return Err(TraceError::LengthMismatch {
+            trace_len: trace.input_len(),
+            input_len: input.len(),
+        });
+    }
+
+    let mut output = Vec::new();
+    let mut state = None;
+    let mut rest = input;
+    for call in &trace.calls {
+        let (chunk, tail) = rest.split_at(call.len);
+        let status =
+            N::normalize_chunk_into(chunk, &mut output, state.as_ref(), call.is_last_chunk)
+                .unwrap_or_else(|err| panic!("{err}"));
+        state = status.state().cloned();
+        rest = tail;
+    }
+    Ok(output)
+}
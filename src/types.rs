@@ -3,6 +3,52 @@ use core::fmt;
 pub const CR: u8 = b'\r';
 pub const LF: u8 = b'\n';
 
+/// A line terminator as found in text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineTerminator {
+    /// `\n`, used on Unix-like systems.
+    Lf,
+    /// `\r\n`, used on Windows and in many network protocols.
+    CrLf,
+    /// `\r` alone, used on classic Mac OS.
+    Cr,
+}
+
+impl LineTerminator {
+    /// Classify the line terminator starting at `idx` in `buf`, or return `None` if there is
+    /// none (or `idx` is out of bounds).
+    ///
+    /// A `\r` at the very end of `buf` is classified as `Cr`, even though it might turn out to
+    /// be the start of a `CrLf` when more data follows.
+    #[must_use]
+    pub fn classify_at(buf: &[u8], idx: usize) -> Option<Self> {
+        match (buf.get(idx)?, buf.get(idx + 1)) {
+            (&LF, _) => Some(Self::Lf),
+            (&CR, Some(&LF)) => Some(Self::CrLf),
+            (&CR, _) => Some(Self::Cr),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the line terminator in bytes.
+    #[must_use]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(self) -> usize {
+        self.as_bytes().len()
+    }
+
+    /// Returns the bytes making up the line terminator.
+    #[must_use]
+    pub fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Lf => b"\n",
+            Self::CrLf => b"\r\n",
+            Self::Cr => b"\r",
+        }
+    }
+}
+
 /// Error type for normalize operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
@@ -1,10 +1,16 @@
 use std::{
     marker::PhantomData,
+    mem::MaybeUninit,
     pin::Pin,
     task::{Context, Poll},
 };
 
-use crate::{helpers::slice_to_uninit_mut, NormalizeChunk};
+use crate::{
+    formats::ChunkEnd,
+    helpers::slice_to_uninit_mut,
+    wrappers::{clamp_buffer_size, drain, fits, normalize_staged, output_buffer_size, FlushPolicy},
+    NormalizeChunk,
+};
 
 pub trait AsyncReadCompat {
     fn poll_read(
@@ -22,84 +28,168 @@ pub struct ReadBuffer<N: NormalizeChunk> {
     output_size: usize,
     state: Option<N::State>,
     end_of_stream: bool,
+    bytes_consumed: u64,
+    bytes_produced: u64,
 }
 
 impl<N: NormalizeChunk> ReadBuffer<N> {
     #[must_use]
     pub fn new(buf_size: usize) -> Self {
+        let buf_size = clamp_buffer_size(buf_size);
         let input_buf = vec![0; buf_size].into_boxed_slice();
-        let required = N::max_output_size_for_chunk(buf_size, None, false);
+        let output_buf = vec![0; output_buffer_size::<N>(buf_size)].into_boxed_slice();
         Self {
             _phantom: PhantomData,
             input_buf,
-            output_buf: vec![0; required].into_boxed_slice(),
+            output_buf,
             output_pos: 0,
             output_size: 0,
             state: None,
             end_of_stream: false,
+            bytes_consumed: 0,
+            bytes_produced: 0,
         }
     }
 
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    pub fn bytes_produced(&self) -> u64 {
+        self.bytes_produced
+    }
+
+    /// The number of bytes allocated for the input and output buffers. This does not change
+    /// over the lifetime of the buffer.
+    pub fn buffer_capacity(&self) -> usize {
+        self.input_buf.len() + self.output_buf.len()
+    }
+
+    /// Whether all normalized output was handed out, so nothing consumed is still buffered.
+    #[cfg(feature = "tokio")]
+    pub fn is_drained(&self) -> bool {
+        self.output_pos >= self.output_size
+    }
+
+    /// Discard buffered output and the normalization state, e.g. after the inner reader was
+    /// repositioned. The byte counters keep counting.
+    pub fn reset(&mut self) {
+        self.output_pos = 0;
+        self.output_size = 0;
+        self.state = None;
+        self.end_of_stream = false;
+    }
+
+    #[cfg(feature = "futures-io")]
     pub fn poll_read<R: AsyncReadCompat>(
         &mut self,
         cx: &mut Context<'_>,
         inner: Pin<&mut R>,
         buf: &mut [u8],
     ) -> Poll<std::io::Result<usize>> {
-        if self.output_pos >= self.output_size {
-            match self.poll_fill_buf(cx, inner) {
-                Poll::Ready(Ok(())) => {}
+        self.poll_read_uninit(cx, inner, slice_to_uninit_mut(buf))
+    }
+
+    /// Like `poll_read`, but reads into possibly uninitialized memory. Returns the number of
+    /// bytes initialized at the start of `buf`.
+    ///
+    /// When there is no buffered output and `buf` can hold the worst-case output of a full
+    /// chunk, the chunk is normalized straight into `buf`, skipping the internal output buffer.
+    pub fn poll_read_uninit<R: AsyncReadCompat>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut inner: Pin<&mut R>,
+        buf: &mut [MaybeUninit<u8>],
+    ) -> Poll<std::io::Result<usize>> {
+        // A chunk can normalize to nothing (e.g. a lone `\n` completing a `\r\n`), so keep
+        // going until there is output or the stream has ended.
+        while self.output_pos >= self.output_size {
+            if self.end_of_stream {
+                return Poll::Ready(Ok(0));
+            }
+            let state = self.state.as_ref();
+            if !fits::<N>(
+                &self.output_buf,
+                self.input_buf.len(),
+                state,
+                ChunkEnd::More,
+            ) || !fits::<N>(&self.output_buf, 0, state, ChunkEnd::Last)
+            {
+                // Pass on data held back by the format before reading the next chunk, so its
+                // output fits.
+                self.output_pos = 0;
+                self.output_size = drain::<N>(&mut self.output_buf, &mut self.state)?;
+                continue;
+            }
+            let direct = buf.len()
+                >= N::max_output_size_for_chunk(self.input_buf.len(), self.state.as_ref(), true);
+            let output = if direct {
+                &mut *buf
+            } else {
+                slice_to_uninit_mut(&mut self.output_buf)
+            };
+            let output_len = match Self::poll_normalize(
+                cx,
+                inner.as_mut(),
+                &mut self.input_buf,
+                output,
+                &mut self.state,
+                &mut self.end_of_stream,
+                &mut self.bytes_consumed,
+            ) {
+                Poll::Ready(Ok(n)) => n,
                 Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
                 Poll::Pending => return Poll::Pending,
+            };
+            if direct {
+                if output_len > 0 || self.end_of_stream {
+                    self.bytes_produced += output_len as u64;
+                    return Poll::Ready(Ok(output_len));
+                }
+            } else {
+                self.output_pos = 0;
+                self.output_size = output_len;
             }
         }
 
-        if self.output_size == 0 {
-            return Poll::Ready(Ok(0));
-        }
-
         let bytes_now = buf.len().min(self.output_size - self.output_pos);
-        buf[..bytes_now]
-            .copy_from_slice(&self.output_buf[self.output_pos..self.output_pos + bytes_now]);
+        buf[..bytes_now].copy_from_slice(slice_to_uninit_mut(
+            &mut self.output_buf[self.output_pos..self.output_pos + bytes_now],
+        ));
         self.output_pos += bytes_now;
+        self.bytes_produced += bytes_now as u64;
         Poll::Ready(Ok(bytes_now))
     }
 
-    pub fn poll_fill_buf<R: AsyncReadCompat>(
-        &mut self,
+    /// Read a chunk from `inner` and normalize it into `output`, returning the output length.
+    fn poll_normalize<R: AsyncReadCompat>(
         cx: &mut Context<'_>,
         inner: Pin<&mut R>,
-    ) -> Poll<std::io::Result<()>> {
-        self.output_pos = 0;
-        self.output_size = 0;
-
-        if self.end_of_stream {
-            return Poll::Ready(Ok(()));
-        }
-
-        let bytes_read = match inner.poll_read(cx, &mut self.input_buf) {
+        input_buf: &mut [u8],
+        output: &mut [MaybeUninit<u8>],
+        state: &mut Option<N::State>,
+        end_of_stream: &mut bool,
+        bytes_consumed: &mut u64,
+    ) -> Poll<std::io::Result<usize>> {
+        let bytes_read = match inner.poll_read(cx, input_buf) {
             Poll::Ready(Ok(n)) => n,
             Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
             Poll::Pending => return Poll::Pending,
         };
-        let is_last_chunk = if bytes_read == 0 {
-            self.end_of_stream = true;
-            true
-        } else {
-            false
-        };
+        *bytes_consumed += bytes_read as u64;
+        let is_last_chunk = bytes_read == 0;
+        *end_of_stream = is_last_chunk;
 
         let status = N::normalize_chunk(
-            &self.input_buf[..bytes_read],
-            slice_to_uninit_mut(&mut self.output_buf),
-            self.state.as_ref(),
+            &input_buf[..bytes_read],
+            output,
+            state.as_ref(),
             is_last_chunk,
         )
         .map_err(std::io::Error::other)?;
 
-        self.output_size = status.output_len();
-        self.state = status.state().cloned();
-        Poll::Ready(Ok(()))
+        *state = status.state().cloned();
+        Poll::Ready(Ok(status.output_len()))
     }
 }
 
@@ -124,6 +214,9 @@ pub struct WriteBuffer<N: NormalizeChunk> {
     output_size: usize,
     state: Option<N::State>,
     stream_state: State,
+    flush_policy: FlushPolicy,
+    bytes_consumed: u64,
+    bytes_produced: u64,
 }
 
 pub enum State {
@@ -135,25 +228,73 @@ pub enum State {
 impl<N: NormalizeChunk> WriteBuffer<N> {
     #[must_use]
     pub fn new(buf_size: usize) -> Self {
+        let buf_size = clamp_buffer_size(buf_size);
         let input_buf = vec![0; buf_size].into_boxed_slice();
-        let required = N::max_output_size_for_chunk(buf_size, None, false);
+        let output_buf = vec![0; output_buffer_size::<N>(buf_size)].into_boxed_slice();
         Self {
             _phantom: PhantomData,
             input_buf,
-            output_buf: vec![0; required].into_boxed_slice(),
+            output_buf,
             input_pos: 0,
             output_pos: 0,
             output_size: 0,
             state: None,
             stream_state: State::Writing,
+            flush_policy: FlushPolicy::default(),
+            bytes_consumed: 0,
+            bytes_produced: 0,
         }
     }
 
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    pub fn bytes_produced(&self) -> u64 {
+        self.bytes_produced
+    }
+
+    /// The number of bytes allocated for the input and output buffers. This does not change
+    /// over the lifetime of the buffer.
+    pub fn buffer_capacity(&self) -> usize {
+        self.input_buf.len() + self.output_buf.len()
+    }
+
+    /// Whether `poll_finish` completed, i.e. the inner writer was shut down.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.stream_state, State::Finished)
+    }
+
+    pub fn set_flush_policy(&mut self, flush_policy: FlushPolicy) {
+        self.flush_policy = flush_policy;
+    }
+
     pub fn poll_write<W: AsyncWriteCompat>(
+        &mut self,
+        cx: &mut Context<'_>,
+        inner: Pin<&mut W>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.poll_write_chunks(cx, inner, buf, false)
+    }
+
+    /// Like `poll_write`, but `buf` is the rest of the stream: its last bytes stay in the input
+    /// buffer, so `poll_flush` with `finish` normalizes them as the end of the stream.
+    pub fn poll_write_last<W: AsyncWriteCompat>(
+        &mut self,
+        cx: &mut Context<'_>,
+        inner: Pin<&mut W>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.poll_write_chunks(cx, inner, buf, true)
+    }
+
+    fn poll_write_chunks<W: AsyncWriteCompat>(
         &mut self,
         cx: &mut Context<'_>,
         mut inner: Pin<&mut W>,
         buf: &[u8],
+        is_last: bool,
     ) -> Poll<std::io::Result<usize>> {
         let mut source_buf = buf;
         let mut total_bytes = 0;
@@ -167,7 +308,10 @@ impl<N: NormalizeChunk> WriteBuffer<N> {
                 {
                     Poll::Ready(Ok(n)) => {
                         self.output_pos += n;
+                        self.bytes_produced += n as u64;
                     }
+                    // Bytes taken from `buf` are already buffered, so they must be reported.
+                    Poll::Pending if total_bytes > 0 => return Poll::Ready(Ok(total_bytes)),
                     other => return other,
                 }
             } else {
@@ -177,28 +321,32 @@ impl<N: NormalizeChunk> WriteBuffer<N> {
 
                 let bytes_now = source_buf.len().min(self.input_buf.len() - self.input_pos);
                 total_bytes += bytes_now;
+                self.bytes_consumed += bytes_now as u64;
 
                 self.input_buf[self.input_pos..self.input_pos + bytes_now]
                     .copy_from_slice(&source_buf[..bytes_now]);
                 self.input_pos += bytes_now;
                 source_buf = &source_buf[bytes_now..];
 
-                if self.input_pos < self.input_buf.len() {
+                if self.input_pos < self.input_buf.len()
+                    && (!self.flush_policy.eager || self.input_pos == 0)
+                {
                     // Not enough data yet to process a full chunk.
                     return Poll::Ready(Ok(total_bytes));
                 }
+                if is_last && source_buf.is_empty() {
+                    // The last chunk is left for `poll_flush` to normalize.
+                    return Poll::Ready(Ok(total_bytes));
+                }
 
-                let status = N::normalize_chunk(
-                    &self.input_buf[..self.input_pos],
-                    slice_to_uninit_mut(&mut self.output_buf),
-                    self.state.as_ref(),
-                    false,
-                )
-                .map_err(std::io::Error::other)?;
-
-                self.state = status.state().cloned();
-                self.output_size = status.output_len();
-                self.input_pos = 0;
+                // The input stays staged while data held back by the format is passed on.
+                (self.output_size, _) = normalize_staged::<N>(
+                    &self.input_buf,
+                    &mut self.input_pos,
+                    &mut self.output_buf,
+                    &mut self.state,
+                    ChunkEnd::More,
+                )?;
             }
         }
     }
@@ -212,19 +360,25 @@ impl<N: NormalizeChunk> WriteBuffer<N> {
         loop {
             if self.output_size == 0 {
                 // Output buffer is empty, try to fill it
-                let status = N::normalize_chunk(
-                    &self.input_buf[..self.input_pos],
-                    slice_to_uninit_mut(&mut self.output_buf),
-                    self.state.as_ref(),
-                    finish,
-                )
-                .map_err(std::io::Error::other)?;
-
-                self.state = status.state().cloned();
-                self.output_size = status.output_len();
-                self.input_pos = 0;
-
-                if self.output_size == 0 {
+                // Flush is not neccesarily the end of stream, at most a boundary if the policy
+                // says so.
+                let end = if finish {
+                    ChunkEnd::Last
+                } else if self.flush_policy.resolve_dangling_cr {
+                    ChunkEnd::Boundary
+                } else {
+                    ChunkEnd::More
+                };
+                let done;
+                (self.output_size, done) = normalize_staged::<N>(
+                    &self.input_buf,
+                    &mut self.input_pos,
+                    &mut self.output_buf,
+                    &mut self.state,
+                    end,
+                )?;
+
+                if self.output_size == 0 && done {
                     // Nothing more to write
                     return Poll::Ready(Ok(()));
                 }
@@ -236,6 +390,7 @@ impl<N: NormalizeChunk> WriteBuffer<N> {
                 {
                     Poll::Ready(Ok(n)) => {
                         self.output_pos += n;
+                        self.bytes_produced += n as u64;
                     }
                     Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
                     Poll::Pending => return Poll::Pending,
@@ -252,6 +407,36 @@ impl<N: NormalizeChunk> WriteBuffer<N> {
         }
     }
 
+    /// Take all data that was accepted but not yet written to the inner writer, normalized as
+    /// if the stream ended here.
+    #[cfg(feature = "tokio")]
+    pub fn take_unflushed(&mut self) -> Vec<u8> {
+        let mut unflushed = self.output_buf[self.output_pos..self.output_size].to_vec();
+        self.output_pos = 0;
+        self.output_size = 0;
+
+        unflushed.reserve(N::max_output_size_for_chunk(
+            self.input_pos,
+            self.state.as_ref(),
+            true,
+        ));
+        let status = N::normalize_chunk(
+            &self.input_buf[..self.input_pos],
+            unflushed.spare_capacity_mut(),
+            self.state.as_ref(),
+            true,
+        )
+        .unwrap_or_else(|err| unreachable!("{err} (should be impossible)"));
+        // SAFETY: We trust that the implementation of normalize_chunk correctly reports the
+        // number of bytes it initialized.
+        unsafe {
+            unflushed.set_len(unflushed.len() + status.output_len());
+        }
+        self.state = status.state().cloned();
+        self.input_pos = 0;
+        unflushed
+    }
+
     pub fn poll_finish<W: AsyncWriteCompat>(
         &mut self,
         cx: &mut Context<'_>,
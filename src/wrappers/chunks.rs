@@ -0,0 +1,88 @@
+//! The `chunks` module provides a `std::io::Read` adapter over iterators producing chunks of
+//! bytes, so chunked sources can be normalized like any other reader.
+
+use std::io::Read;
+
+use crate::{wrappers::io::Reader, IoExt, NormalizeChunk};
+
+/// A `std::io::Read` implementation reading the chunks produced by an iterator in order.
+///
+/// Empty chunks are skipped, the reader only reports end of stream once the iterator is
+/// exhausted. Errors produced by the iterator are returned from `read` as-is.
+pub struct ChunkReader<I: Iterator> {
+    chunks: I,
+    current: Option<I::Item>,
+    pos: usize,
+}
+
+impl<I, B> ChunkReader<I>
+where
+    I: Iterator<Item = std::io::Result<B>>,
+    B: AsRef<[u8]>,
+{
+    pub fn new(chunks: I) -> Self {
+        Self {
+            chunks,
+            current: None,
+            pos: 0,
+        }
+    }
+
+    /// Returns the underlying iterator. Any unread part of the current chunk is lost.
+    pub fn into_inner(self) -> I {
+        self.chunks
+    }
+}
+
+impl<I, B> Read for ChunkReader<I>
+where
+    I: Iterator<Item = std::io::Result<B>>,
+    B: AsRef<[u8]>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            if let Some(Ok(chunk)) = &self.current {
+                let remaining = &chunk.as_ref()[self.pos..];
+                if !remaining.is_empty() {
+                    let n = remaining.len().min(buf.len());
+                    buf[..n].copy_from_slice(&remaining[..n]);
+                    self.pos += n;
+                    return Ok(n);
+                }
+            }
+
+            self.pos = 0;
+            match self.chunks.next() {
+                Some(Ok(chunk)) => self.current = Some(Ok(chunk)),
+                Some(Err(err)) => {
+                    self.current = None;
+                    return Err(err);
+                }
+                None => {
+                    self.current = None;
+                    return Ok(0);
+                }
+            }
+        }
+    }
+}
+
+/// Extension trait to provide convenient methods on iterators producing chunks of bytes.
+pub trait ChunksExt: IntoIterator + Sized {
+    /// Wrap the chunks with a newline-normalizing `Reader`.
+    fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> Reader<ChunkReader<Self::IntoIter>, N>;
+}
+
+impl<T, B> ChunksExt for T
+where
+    T: IntoIterator<Item = std::io::Result<B>>,
+    B: AsRef<[u8]>,
+{
+    fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> Reader<ChunkReader<Self::IntoIter>, N> {
+        N::wrap_reader(ChunkReader::new(self.into_iter()))
+    }
+}
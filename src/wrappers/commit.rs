@@ -0,0 +1,90 @@
+//! The `commit` module provides a writer that only passes normalized output on to its sink
+//! once it is explicitly committed.
+
+use std::io::Write;
+
+use crate::{
+    wrappers::{io::Writer, spool::Spool, FlushPolicy},
+    NormalizeChunk,
+};
+
+/// A `std::io::Write` implementation that normalizes newlines into a buffer, and only writes
+/// the result to the inner sink on [`commit`](CommitWriter::commit).
+///
+/// Nothing reaches the sink if the writer is [aborted](CommitWriter::abort) or dropped, so
+/// failures halfway through never leave a partially written upload behind. The buffered output
+/// is kept in memory, or in a temporary file once it exceeds the threshold set with
+/// [`with_spill_threshold`](CommitWriter::with_spill_threshold).
+pub struct CommitWriter<W: Write, N: NormalizeChunk> {
+    normalizer: Writer<Spool, N>,
+    sink: W,
+}
+
+impl<W: Write, N: NormalizeChunk> CommitWriter<W, N> {
+    /// Wrap `sink`, normalizing through an internal buffer of `buf_size` bytes.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
+    pub fn new(sink: W, buf_size: usize) -> Self {
+        Self {
+            normalizer: Writer::new(Spool::new(None), buf_size),
+            sink,
+        }
+    }
+
+    /// Spill buffered output to a temporary file once it exceeds `threshold` bytes.
+    #[must_use]
+    pub fn with_spill_threshold(mut self, threshold: usize) -> Self {
+        self.normalizer.get_mut().set_limit(Some(threshold));
+        self
+    }
+
+    /// Set the `FlushPolicy` used while buffering.
+    #[must_use]
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.normalizer = self.normalizer.with_flush_policy(flush_policy);
+        self
+    }
+
+    /// Gets a reference to the inner sink.
+    pub fn get_ref(&self) -> &W {
+        &self.sink
+    }
+
+    /// Whether the buffered output was spilled to a temporary file.
+    pub fn is_spilled(&self) -> bool {
+        self.normalizer.get_ref().is_spilled()
+    }
+
+    /// Finish normalization and write all buffered output to the sink, then flush it.
+    ///
+    /// Returns the sink on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced while spilling, or by writing to or flushing the sink. In that
+    /// case the sink may have received part of the output.
+    pub fn commit(self) -> std::io::Result<W> {
+        let mut spool = self.normalizer.finish()?;
+        let mut sink = self.sink;
+        spool.copy_to(&mut sink)?;
+        sink.flush()?;
+        Ok(sink)
+    }
+
+    /// Discard all buffered output and return the untouched sink.
+    pub fn abort(self) -> W {
+        self.sink
+    }
+}
+
+impl<W: Write, N: NormalizeChunk> Write for CommitWriter<W, N> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.normalizer.write(buf)
+    }
+
+    /// Flushes the normalizer into the buffer. The sink is not touched before `commit`.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.normalizer.flush()
+    }
+}
@@ -0,0 +1,123 @@
+//! The `console` module provides a writer for standard output and standard error that picks
+//! the line endings depending on whether the stream is a console or redirected.
+
+use std::io::{IsTerminal, Write};
+
+use crate::{
+    wrappers::{io::Writer, FlushPolicy, DEFAULT_BUFFER_SIZE},
+    CRLF, LF,
+};
+
+/// Line endings written by a [`ConsoleWriter`] when its stream is not a console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PipeEol {
+    /// Normalize to `\n`. This is the default.
+    #[default]
+    Lf,
+    /// Normalize to `\r\n`.
+    CrLf,
+    /// Pass the output through unchanged.
+    Unchanged,
+}
+
+/// A `std::io::Write` implementation for console streams such as `std::io::stdout()`.
+///
+/// When the stream is a console, output is normalized to the platform's native line endings
+/// (CRLF on Windows, LF elsewhere). When it is redirected to a file or pipe, output is
+/// normalized according to the configured [`PipeEol`], so redirected output does not end up
+/// with stray `\r` characters.
+///
+/// Every write is normalized and passed on immediately; call [`finish`](Self::finish) at the
+/// end to resolve a trailing `\r`.
+///
+/// ```no_run
+/// use std::io::Write;
+/// use eolify::{ConsoleWriter, PipeEol};
+///
+/// let mut out = ConsoleWriter::new(std::io::stdout(), PipeEol::Lf);
+/// writeln!(out, "hello").unwrap();
+/// out.finish().unwrap();
+/// ```
+pub struct ConsoleWriter<W: Write> {
+    inner: ConsoleInner<W>,
+}
+
+enum ConsoleInner<W: Write> {
+    Lf(Writer<W, LF>),
+    CrLf(Writer<W, CRLF>),
+    Unchanged(W),
+}
+
+impl<W: Write + IsTerminal> ConsoleWriter<W> {
+    /// Wrap `stream`, checking once whether it is a console.
+    pub fn new(stream: W, pipe_eol: PipeEol) -> Self {
+        let eol = if stream.is_terminal() {
+            if cfg!(windows) {
+                PipeEol::CrLf
+            } else {
+                PipeEol::Lf
+            }
+        } else {
+            pipe_eol
+        };
+        Self::with_eol(stream, eol)
+    }
+}
+
+impl<W: Write> ConsoleWriter<W> {
+    fn with_eol(stream: W, eol: PipeEol) -> Self {
+        let policy = FlushPolicy::batch().eager(true);
+        let inner = match eol {
+            PipeEol::Lf => {
+                ConsoleInner::Lf(Writer::new(stream, DEFAULT_BUFFER_SIZE).with_flush_policy(policy))
+            }
+            PipeEol::CrLf => ConsoleInner::CrLf(
+                Writer::new(stream, DEFAULT_BUFFER_SIZE).with_flush_policy(policy),
+            ),
+            PipeEol::Unchanged => ConsoleInner::Unchanged(stream),
+        };
+        Self { inner }
+    }
+
+    /// Gets a reference to the wrapped stream.
+    pub fn get_ref(&self) -> &W {
+        match &self.inner {
+            ConsoleInner::Lf(writer) => writer.get_ref(),
+            ConsoleInner::CrLf(writer) => writer.get_ref(),
+            ConsoleInner::Unchanged(stream) => stream,
+        }
+    }
+
+    /// Finish normalization, flush the stream and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or flushing the stream.
+    pub fn finish(self) -> std::io::Result<W> {
+        let mut stream = match self.inner {
+            ConsoleInner::Lf(writer) => writer.finish()?,
+            ConsoleInner::CrLf(writer) => writer.finish()?,
+            ConsoleInner::Unchanged(stream) => stream,
+        };
+        stream.flush()?;
+        Ok(stream)
+    }
+}
+
+impl<W: Write> Write for ConsoleWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            ConsoleInner::Lf(writer) => writer.write(buf),
+            ConsoleInner::CrLf(writer) => writer.write(buf),
+            ConsoleInner::Unchanged(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.inner {
+            ConsoleInner::Lf(writer) => writer.flush(),
+            ConsoleInner::CrLf(writer) => writer.flush(),
+            ConsoleInner::Unchanged(stream) => stream.flush(),
+        }
+    }
+}
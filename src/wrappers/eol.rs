@@ -0,0 +1,311 @@
+//! The `eol` module provides [`EolFormat`], a line ending chosen at runtime, and wrappers that
+//! dispatch to the matching `LF`, `CRLF` or `CR` normalizer.
+
+use std::{
+    fmt,
+    io::{BufRead, Read, Write},
+    str::FromStr,
+};
+
+use crate::{
+    wrappers::{
+        io::{Reader, Writer},
+        DEFAULT_BUFFER_SIZE,
+    },
+    LineTerminator, CR, CRLF, LF,
+};
+
+/// The line ending to normalize to, selected at runtime.
+///
+/// Use this instead of the `LF`, `CRLF` and `CR` type parameters when the target is only known
+/// at runtime, e.g. from a command line flag. The names match the format registry.
+///
+/// ```
+/// use std::io::Read;
+/// use eolify::EolFormat;
+///
+/// let format: EolFormat = "crlf".parse().unwrap();
+/// let mut output = String::new();
+/// format
+///     .wrap_reader(b"one\ntwo\r".as_ref())
+///     .read_to_string(&mut output)
+///     .unwrap();
+/// assert_eq!(output, "one\r\ntwo\r\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EolFormat {
+    /// Normalize to `\n`, like [`LF`]. This is the default.
+    #[default]
+    Lf,
+    /// Normalize to `\r\n`, like [`CRLF`].
+    CrLf,
+    /// Normalize to `\r`, like [`CR`].
+    Cr,
+}
+
+impl EolFormat {
+    /// All formats, in the order `lf`, `crlf`, `cr`.
+    pub const ALL: [EolFormat; 3] = [EolFormat::Lf, EolFormat::CrLf, EolFormat::Cr];
+
+    /// Returns the line terminator this format normalizes to.
+    #[must_use]
+    pub fn line_terminator(self) -> LineTerminator {
+        match self {
+            Self::Lf => LineTerminator::Lf,
+            Self::CrLf => LineTerminator::CrLf,
+            Self::Cr => LineTerminator::Cr,
+        }
+    }
+
+    /// Returns the name of the format as used by the registry and `FromStr`.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Lf => "lf",
+            Self::CrLf => "crlf",
+            Self::Cr => "cr",
+        }
+    }
+
+    /// Normalize a complete buffer at once.
+    #[must_use]
+    pub fn normalize(self, input: &[u8]) -> Vec<u8> {
+        use crate::Normalize;
+
+        match self {
+            Self::Lf => LF::normalize(input),
+            Self::CrLf => CRLF::normalize(input),
+            Self::Cr => CR::normalize(input),
+        }
+    }
+
+    /// Wrap a reader with a newline-normalizing [`EolReader`].
+    pub fn wrap_reader<R: Read>(self, reader: R) -> EolReader<R> {
+        self.wrap_reader_with_buffer_size(reader, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Wrap a reader with a newline-normalizing [`EolReader`] and specify the internal buffer
+    /// size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
+    pub fn wrap_reader_with_buffer_size<R: Read>(self, reader: R, buf_size: usize) -> EolReader<R> {
+        match self {
+            Self::Lf => EolDispatch::Lf(Reader::new(reader, buf_size)),
+            Self::CrLf => EolDispatch::CrLf(Reader::new(reader, buf_size)),
+            Self::Cr => EolDispatch::Cr(Reader::new(reader, buf_size)),
+        }
+    }
+
+    /// Wrap a writer with a newline-normalizing [`EolWriter`].
+    pub fn wrap_writer<W: Write>(self, writer: W) -> EolWriter<W> {
+        self.wrap_writer_with_buffer_size(writer, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Wrap a writer with a newline-normalizing [`EolWriter`] and specify the internal buffer
+    /// size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
+    pub fn wrap_writer_with_buffer_size<W: Write>(
+        self,
+        writer: W,
+        buf_size: usize,
+    ) -> EolWriter<W> {
+        match self {
+            Self::Lf => EolDispatch::Lf(Writer::new(writer, buf_size)),
+            Self::CrLf => EolDispatch::CrLf(Writer::new(writer, buf_size)),
+            Self::Cr => EolDispatch::Cr(Writer::new(writer, buf_size)),
+        }
+    }
+}
+
+impl From<LineTerminator> for EolFormat {
+    fn from(terminator: LineTerminator) -> Self {
+        match terminator {
+            LineTerminator::Lf => Self::Lf,
+            LineTerminator::CrLf => Self::CrLf,
+            LineTerminator::Cr => Self::Cr,
+        }
+    }
+}
+
+impl From<EolFormat> for LineTerminator {
+    fn from(format: EolFormat) -> Self {
+        format.line_terminator()
+    }
+}
+
+impl fmt::Display for EolFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for EolFormat {
+    type Err = ParseEolFormatError;
+
+    /// Parse a format name, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseEolFormatError(s.to_string()))
+    }
+}
+
+/// Error returned when parsing an unknown [`EolFormat`] name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEolFormatError(String);
+
+impl fmt::Display for ParseEolFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown line ending format `{}`, expected one of lf, crlf, cr",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseEolFormatError {}
+
+/// A wrapper holding one of three normalizers, as selected by an [`EolFormat`].
+///
+/// Reading, writing and the async traits are forwarded to whichever variant is present. Use
+/// the aliases [`EolReader`] and [`EolWriter`] (and their async counterparts) rather than
+/// naming this type directly; match on it to get at the statically typed wrapper.
+#[derive(Debug, Clone)]
+pub enum EolDispatch<L, C, R> {
+    /// The wrapper normalizing to `\n`.
+    Lf(L),
+    /// The wrapper normalizing to `\r\n`.
+    CrLf(C),
+    /// The wrapper normalizing to `\r`.
+    Cr(R),
+}
+
+/// Forward `$body` to whichever variant `$value` holds, binding it to `$inner`.
+macro_rules! dispatch {
+    ($value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            EolDispatch::Lf($inner) => $body,
+            EolDispatch::CrLf($inner) => $body,
+            EolDispatch::Cr($inner) => $body,
+        }
+    };
+}
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub(crate) use dispatch;
+
+impl<L, C, R> EolDispatch<L, C, R> {
+    /// Returns the format of the wrapper held.
+    #[must_use]
+    pub fn format(&self) -> EolFormat {
+        match self {
+            Self::Lf(_) => EolFormat::Lf,
+            Self::CrLf(_) => EolFormat::CrLf,
+            Self::Cr(_) => EolFormat::Cr,
+        }
+    }
+
+    /// Project a pinned `EolDispatch` onto its pinned variant.
+    #[cfg(any(feature = "tokio", feature = "futures-io"))]
+    pub(crate) fn as_pin_mut(
+        self: std::pin::Pin<&mut Self>,
+    ) -> EolDispatch<std::pin::Pin<&mut L>, std::pin::Pin<&mut C>, std::pin::Pin<&mut R>> {
+        use std::pin::Pin;
+
+        // SAFETY: The variant is never moved out of a pinned `EolDispatch`, and `EolDispatch`
+        // is only `Unpin` when all variants are.
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::Lf(inner) => EolDispatch::Lf(Pin::new_unchecked(inner)),
+                Self::CrLf(inner) => EolDispatch::CrLf(Pin::new_unchecked(inner)),
+                Self::Cr(inner) => EolDispatch::Cr(Pin::new_unchecked(inner)),
+            }
+        }
+    }
+}
+
+/// A `std::io::Read` wrapper normalizing to the line ending chosen by an [`EolFormat`].
+pub type EolReader<R> = EolDispatch<Reader<R, LF>, Reader<R, CRLF>, Reader<R, CR>>;
+
+/// A `std::io::Write` wrapper normalizing to the line ending chosen by an [`EolFormat`].
+pub type EolWriter<W> = EolDispatch<Writer<W, LF>, Writer<W, CRLF>, Writer<W, CR>>;
+
+impl<R: Read> EolReader<R> {
+    /// Returns the number of bytes read from the inner reader so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        dispatch!(self, reader => reader.bytes_consumed())
+    }
+
+    /// Returns the number of normalized bytes returned to the caller so far.
+    pub fn bytes_produced(&self) -> u64 {
+        dispatch!(self, reader => reader.bytes_produced())
+    }
+
+    /// Unwraps this `EolReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        dispatch!(self, reader => reader.into_inner())
+    }
+}
+
+impl<W: Write> EolWriter<W> {
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        dispatch!(self, writer => writer.get_ref())
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        dispatch!(self, writer => writer.get_mut())
+    }
+
+    /// Returns the number of bytes accepted from the caller so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        dispatch!(self, writer => writer.bytes_consumed())
+    }
+
+    /// Returns the number of normalized bytes written to the inner writer so far.
+    pub fn bytes_produced(&self) -> u64 {
+        dispatch!(self, writer => writer.bytes_produced())
+    }
+
+    /// Finish normalization, flush the inner writer and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or flushing the inner writer.
+    pub fn finish(self) -> std::io::Result<W> {
+        dispatch!(self, writer => writer.finish())
+    }
+}
+
+impl<L: Read, C: Read, R: Read> Read for EolDispatch<L, C, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        dispatch!(self, inner => inner.read(buf))
+    }
+}
+
+impl<L: BufRead, C: BufRead, R: BufRead> BufRead for EolDispatch<L, C, R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        dispatch!(self, inner => inner.fill_buf())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        dispatch!(self, inner => inner.consume(amt));
+    }
+}
+
+impl<L: Write, C: Write, R: Write> Write for EolDispatch<L, C, R> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        dispatch!(self, inner => inner.write(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        dispatch!(self, inner => inner.flush())
+    }
+}
@@ -0,0 +1,100 @@
+//! The `fmt` module provides a wrapper for `std::fmt::Write` sinks, such as `String` or
+//! `std::fmt::Formatter`, to perform newline normalization on-the-fly.
+
+use std::{fmt, marker::PhantomData};
+
+use crate::NormalizeChunk;
+
+/// A `std::fmt::Write` wrapper and implementation that normalizes newlines on-the-fly.
+///
+/// Every `write_str` is normalized and passed on immediately, only the chunk state is carried
+/// between calls. This makes it suitable for `Display` implementations:
+///
+/// ```
+/// use std::fmt::{self, Write};
+/// use eolify::{FmtWriteExt, CRLF};
+///
+/// struct Report;
+///
+/// impl fmt::Display for Report {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         let mut f = f.normalize_newlines(CRLF);
+///         writeln!(f, "first line")?;
+///         writeln!(f, "second line")?;
+///         f.finish().map(|_| ())
+///     }
+/// }
+///
+/// assert_eq!(Report.to_string(), "first line\r\nsecond line\r\n");
+/// ```
+pub struct FmtWriter<W, N: NormalizeChunk> {
+    _phantom: PhantomData<N>,
+    inner: W,
+    output_buf: Vec<u8>,
+    state: Option<N::State>,
+}
+
+impl<W: fmt::Write, N: NormalizeChunk> FmtWriter<W, N> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            _phantom: PhantomData,
+            inner,
+            output_buf: Vec::new(),
+            state: None,
+        }
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Finish normalization, writing anything still pending to the inner writer, and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> Result<W, fmt::Error> {
+        self.write_chunk("", true)?;
+        Ok(self.inner)
+    }
+
+    fn write_chunk(&mut self, s: &str, is_last_chunk: bool) -> fmt::Result {
+        self.output_buf.clear();
+        let status = N::normalize_chunk_into(
+            s.as_bytes(),
+            &mut self.output_buf,
+            self.state.as_ref(),
+            is_last_chunk,
+        )
+        .map_err(|_| fmt::Error)?;
+        self.state = status.state().cloned();
+
+        // The formats in this crate only touch ASCII line endings, so valid UTF-8 stays valid.
+        let normalized = std::str::from_utf8(&self.output_buf).map_err(|_| fmt::Error)?;
+        self.inner.write_str(normalized)
+    }
+}
+
+impl<W: fmt::Write, N: NormalizeChunk> fmt::Write for FmtWriter<W, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_chunk(s, false)
+    }
+}
+
+/// Extension trait to provide convenient methods on `std::fmt::Write`.
+pub trait FmtWriteExt {
+    /// Wrap the writer with a newline-normalizing `FmtWriter`.
+    fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> FmtWriter<Self, N>
+    where
+        Self: Sized;
+}
+
+impl<W: fmt::Write> FmtWriteExt for W {
+    fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> FmtWriter<Self, N>
+    where
+        Self: Sized,
+    {
+        FmtWriter::new(self)
+    }
+}
@@ -7,12 +7,16 @@ use std::{
     task::{Context, Poll},
 };
 
-use futures_io::{AsyncRead, AsyncWrite};
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite};
 use pin_project_lite::pin_project;
 
 use crate::{
-    wrappers::async_core::{AsyncReadCompat, AsyncWriteCompat, ReadBuffer, WriteBuffer},
-    NormalizeChunk,
+    wrappers::{
+        async_core::{AsyncReadCompat, AsyncWriteCompat, ReadBuffer, WriteBuffer},
+        eol::{dispatch, EolDispatch, EolFormat},
+        FlushPolicy, DEFAULT_BUFFER_SIZE,
+    },
+    NormalizeChunk, CR, CRLF, LF,
 };
 
 pin_project! {
@@ -35,6 +39,22 @@ impl<R, N: NormalizeChunk> AsyncReader<R, N> {
     pub fn into_inner(self) -> R {
         self.reader
     }
+
+    /// Returns the number of bytes read from the inner reader so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.buf.bytes_consumed()
+    }
+
+    /// Returns the number of normalized bytes returned to the caller so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.buf.bytes_produced()
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.buffer_capacity()
+    }
 }
 
 struct FuturesIoReader<R: futures_io::AsyncRead>(R);
@@ -62,6 +82,25 @@ impl<R: AsyncRead, N: NormalizeChunk> AsyncRead for AsyncReader<R, N> {
     }
 }
 
+/// Seeks the inner reader. Positions refer to the inner, un-normalized stream, and a
+/// successful seek discards any buffered output and the normalization state, so reading
+/// resumes as if the stream started at the new position. Relative seeks are relative to the
+/// position of the inner reader, which may be ahead of the data returned so far.
+impl<R: AsyncSeek, N: NormalizeChunk> AsyncSeek for AsyncReader<R, N> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: std::io::SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.project();
+        let result = this.reader.poll_seek(cx, pos);
+        if let Poll::Ready(Ok(_)) = result {
+            this.buf.reset();
+        }
+        result
+    }
+}
+
 pin_project! {
     /// An `future::io::AsyncWrite` wrapper and implementation that normalizes newlines on-the-fly.
     pub struct AsyncWriter<W, N: NormalizeChunk> {
@@ -78,6 +117,34 @@ impl<W, N: NormalizeChunk> AsyncWriter<W, N> {
             buf: WriteBuffer::new(buf_size),
         }
     }
+
+    /// Set the `FlushPolicy` that controls when buffered data is passed on to the inner writer.
+    #[must_use]
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.buf.set_flush_policy(flush_policy);
+        self
+    }
+
+    /// Returns the number of bytes accepted from the caller so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.buf.bytes_consumed()
+    }
+
+    /// Returns the number of normalized bytes written to the inner writer so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.buf.bytes_produced()
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.buffer_capacity()
+    }
+
+    /// Returns `true` once the writer was shut down. Shutting down again is a no-op.
+    pub fn is_finished(&self) -> bool {
+        self.buf.is_finished()
+    }
 }
 
 impl<W: AsyncWrite + Unpin, N: NormalizeChunk> AsyncWriter<W, N> {
@@ -87,6 +154,29 @@ impl<W: AsyncWrite + Unpin, N: NormalizeChunk> AsyncWriter<W, N> {
             buf: self.buf,
         }
     }
+
+    /// Write `buf` as the final data of the stream and finish, like `write_all` followed by
+    /// [`finish`](Self::finish).
+    ///
+    /// The last part of `buf` is normalized as the end of the stream, instead of being followed
+    /// by a separate empty final chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or closing the inner writer.
+    pub async fn finish_with(mut self, mut buf: &[u8]) -> std::io::Result<W> {
+        while !buf.is_empty() {
+            match std::future::poll_fn(|cx| Pin::new(&mut self).poll_write_chunks(cx, buf, true))
+                .await
+            {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(n) => buf = &buf[n..],
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.finish().await
+    }
 }
 
 pin_project! {
@@ -104,7 +194,7 @@ impl<W: AsyncWrite + Unpin, N: NormalizeChunk> Future for Finisher<W, N> {
         let mut this = self.project();
 
         let Some(writer) = this.writer.as_mut().get_mut() else {
-            panic!("polled after completion");
+            return Poll::Ready(Err(std::io::Error::other("finish polled after completion")));
         };
 
         let writer = pin!(FuturesIoWriter(writer));
@@ -141,15 +231,31 @@ impl<W: AsyncWrite + Unpin> AsyncWriteCompat for FuturesIoWriter<W> {
     }
 }
 
+impl<W: AsyncWrite, N: NormalizeChunk> AsyncWriter<W, N> {
+    /// Accept data from `buf`, leaving its last bytes for `finish` if `is_last` is set.
+    fn poll_write_chunks(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        is_last: bool,
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+        let writer = pin!(FuturesIoWriter(this.writer));
+        if is_last {
+            this.buf.poll_write_last(cx, writer, buf)
+        } else {
+            this.buf.poll_write(cx, writer, buf)
+        }
+    }
+}
+
 impl<W: AsyncWrite, N: NormalizeChunk> AsyncWrite for AsyncWriter<W, N> {
     fn poll_write(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<std::result::Result<usize, std::io::Error>> {
-        let this = self.project();
-        let writer = pin!(FuturesIoWriter(this.writer));
-        this.buf.poll_write(cx, writer, buf)
+        self.poll_write_chunks(cx, buf, false)
     }
 
     fn poll_flush(
@@ -168,6 +274,70 @@ impl<W: AsyncWrite, N: NormalizeChunk> AsyncWrite for AsyncWriter<W, N> {
     }
 }
 
+/// An `futures::io::AsyncRead` wrapper normalizing to the line ending chosen by an
+/// [`EolFormat`].
+pub type EolAsyncReader<R> =
+    EolDispatch<AsyncReader<R, LF>, AsyncReader<R, CRLF>, AsyncReader<R, CR>>;
+
+/// An `futures::io::AsyncWrite` wrapper normalizing to the line ending chosen by an
+/// [`EolFormat`].
+pub type EolAsyncWriter<W> =
+    EolDispatch<AsyncWriter<W, LF>, AsyncWriter<W, CRLF>, AsyncWriter<W, CR>>;
+
+impl<W: AsyncWrite + Unpin> EolAsyncWriter<W> {
+    /// Finish normalization, flush and close the inner writer and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or shutting down the inner writer.
+    pub async fn finish(self) -> std::io::Result<W> {
+        dispatch!(self, writer => writer.finish().await)
+    }
+}
+
+fn wrap_eol_reader<R>(format: EolFormat, reader: R, buf_size: usize) -> EolAsyncReader<R> {
+    match format {
+        EolFormat::Lf => EolDispatch::Lf(AsyncReader::new(reader, buf_size)),
+        EolFormat::CrLf => EolDispatch::CrLf(AsyncReader::new(reader, buf_size)),
+        EolFormat::Cr => EolDispatch::Cr(AsyncReader::new(reader, buf_size)),
+    }
+}
+
+fn wrap_eol_writer<W>(format: EolFormat, writer: W, buf_size: usize) -> EolAsyncWriter<W> {
+    match format {
+        EolFormat::Lf => EolDispatch::Lf(AsyncWriter::new(writer, buf_size)),
+        EolFormat::CrLf => EolDispatch::CrLf(AsyncWriter::new(writer, buf_size)),
+        EolFormat::Cr => EolDispatch::Cr(AsyncWriter::new(writer, buf_size)),
+    }
+}
+impl<L: AsyncRead, C: AsyncRead, R: AsyncRead> AsyncRead for EolDispatch<L, C, R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_read(cx, buf))
+    }
+}
+
+impl<L: AsyncWrite, C: AsyncWrite, R: AsyncWrite> AsyncWrite for EolDispatch<L, C, R> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_write(cx, buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_flush(cx))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_close(cx))
+    }
+}
+
 /// Extension trait to provide convenient methods on `Normalize` for `futures::io::AsyncRead`
 /// and `futures::io::AsyncWrite`.
 ///
@@ -178,10 +348,13 @@ where
 {
     /// Wrap a reader with a newline-normalizing `AsyncReader`.
     fn wrap_async_reader<R: AsyncRead>(reader: R) -> AsyncReader<R, Self> {
-        Self::wrap_async_reader_with_buffer_size(reader, 8192)
+        Self::wrap_async_reader_with_buffer_size(reader, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a reader with a newline-normalizing `AsyncReader` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_async_reader_with_buffer_size<R: AsyncRead>(
         reader: R,
         buf_size: usize,
@@ -189,14 +362,73 @@ where
 
     /// Wrap a writer with a newline-normalizing `AsyncWriter`.
     fn wrap_async_writer<W: AsyncWrite>(writer: W) -> AsyncWriter<W, Self> {
-        Self::wrap_async_writer_with_buffer_size(writer, 8192)
+        Self::wrap_async_writer_with_buffer_size(writer, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a writer with a newline-normalizing `AsyncWriter` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_async_writer_with_buffer_size<W: AsyncWrite>(
         writer: W,
         buf_size: usize,
     ) -> AsyncWriter<W, Self>;
+
+    /// Read everything from `reader` and return the normalized bytes.
+    ///
+    /// The returned future fails with any error produced by reading from `reader`.
+    fn async_read_to_vec<R: AsyncRead + Unpin>(
+        reader: R,
+    ) -> impl Future<Output = std::io::Result<Vec<u8>>> {
+        async move {
+            let mut reader = Self::wrap_async_reader(reader);
+            let mut out = Vec::new();
+            let mut buf = vec![0; DEFAULT_BUFFER_SIZE];
+            loop {
+                match std::future::poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf)).await
+                {
+                    Ok(0) => return Ok(out),
+                    Ok(n) => out.extend_from_slice(&buf[..n]),
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+    }
+
+    /// Read everything from `reader` and return the normalized text.
+    ///
+    /// The returned future fails with any error produced by reading from `reader`, or an error
+    /// of kind `InvalidData` if the normalized output is not valid UTF-8.
+    fn async_read_to_string<R: AsyncRead + Unpin>(
+        reader: R,
+    ) -> impl Future<Output = std::io::Result<String>> {
+        async move {
+            let out = Self::async_read_to_vec(reader).await?;
+            String::from_utf8(out)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        }
+    }
+
+    /// Wrap `writer`, pass the normalizing `AsyncWriter` to `f` and finish it once the future
+    /// returned by `f` succeeds.
+    ///
+    /// The returned future resolves to the inner writer together with the value produced by
+    /// `f`. If `f` fails, data still buffered in the `AsyncWriter` is discarded and the error is
+    /// returned.
+    fn with_async_writer<W, T, F>(writer: W, f: F) -> impl Future<Output = std::io::Result<(W, T)>>
+    where
+        W: AsyncWrite + Unpin,
+        F: for<'a> FnOnce(
+            &'a mut AsyncWriter<W, Self>,
+        ) -> Pin<Box<dyn Future<Output = std::io::Result<T>> + 'a>>,
+    {
+        async move {
+            let mut normalizer = Self::wrap_async_writer(writer);
+            let value = f(&mut normalizer).await?;
+            Ok((normalizer.finish().await?, value))
+        }
+    }
 }
 
 impl<N: NormalizeChunk> FuturesIoExt for N {
@@ -223,6 +455,12 @@ pub trait FuturesIoAsyncReadExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> AsyncReader<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the reader with an `EolAsyncReader` normalizing to the line ending chosen at
+    /// runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncReader<Self>
+    where
+        Self: Sized;
 }
 
 impl<R: AsyncRead> FuturesIoAsyncReadExt for R {
@@ -232,6 +470,13 @@ impl<R: AsyncRead> FuturesIoAsyncReadExt for R {
     {
         N::wrap_async_reader(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncReader<Self>
+    where
+        Self: Sized,
+    {
+        wrap_eol_reader(format, self, DEFAULT_BUFFER_SIZE)
+    }
 }
 
 /// Extension trait to provide convenient methods on `futures::io::AsyncWrite`.
@@ -242,6 +487,12 @@ pub trait FuturesIoAsyncWriteExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> AsyncWriter<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the writer with an `EolAsyncWriter` normalizing to the line ending chosen at
+    /// runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncWriter<Self>
+    where
+        Self: Sized;
 }
 
 impl<W: AsyncWrite> FuturesIoAsyncWriteExt for W {
@@ -251,4 +502,11 @@ impl<W: AsyncWrite> FuturesIoAsyncWriteExt for W {
     {
         N::wrap_async_writer(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncWriter<Self>
+    where
+        Self: Sized,
+    {
+        wrap_eol_writer(format, self, DEFAULT_BUFFER_SIZE)
+    }
 }
@@ -2,13 +2,29 @@
 //! traits to perform newline normalization on-the-fly.
 
 use std::{
-    io::{Read, Write},
+    io::{BufRead, Read, Write},
     marker::PhantomData,
+    sync::mpsc::Sender,
 };
 
-use crate::{helpers::slice_to_uninit_mut, NormalizeChunk};
+use crate::{
+    formats::ChunkEnd,
+    helpers::slice_to_uninit_mut,
+    wrappers::{
+        clamp_buffer_size, drain,
+        eol::{EolFormat, EolReader, EolWriter},
+        fits, normalize_staged, output_buffer_size,
+        stats::{ChunkStats, StatsRecorder, WindowedStats},
+        throttle::Throttle,
+        FlushPolicy, DEFAULT_BUFFER_SIZE,
+    },
+    NormalizeChunk,
+};
 
 /// A `std::io::Read` wrapper and implementation that normalizes newlines on-the-fly.
+///
+/// The wrapper only relies on [`NormalizeChunk`], so it can drive any streaming byte transform
+/// implementing that trait, not just the newline formats provided by this crate.
 pub struct Reader<R, N: NormalizeChunk> {
     _phantom: PhantomData<N>,
     inner: R,
@@ -18,33 +34,110 @@ pub struct Reader<R, N: NormalizeChunk> {
     output_size: usize,
     state: Option<N::State>,
     end_of_stream: bool,
+    bytes_consumed: u64,
+    bytes_produced: u64,
+    throttle: Option<Throttle>,
+    stats: Option<StatsRecorder>,
+    #[cfg(feature = "trace")]
+    trace: Option<crate::trace::ChunkTrace>,
 }
 
 impl<R: Read, N: NormalizeChunk> Reader<R, N> {
+    /// Wrap `reader`, reading from it in chunks of at most `buf_size` bytes.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     pub fn new(reader: R, buf_size: usize) -> Self {
+        let buf_size = clamp_buffer_size(buf_size);
         let input_buf = vec![0; buf_size].into_boxed_slice();
-        let required = N::max_output_size_for_chunk(buf_size, None, false);
+        let output_buf = vec![0; output_buffer_size::<N>(buf_size)].into_boxed_slice();
         Self {
             _phantom: PhantomData,
             inner: reader,
             input_buf,
-            output_buf: vec![0; required].into_boxed_slice(),
+            output_buf,
             output_pos: 0,
             output_size: 0,
             state: None,
             end_of_stream: false,
+            bytes_consumed: 0,
+            bytes_produced: 0,
+            throttle: None,
+            stats: None,
+            #[cfg(feature = "trace")]
+            trace: None,
         }
     }
 
-    fn fill_buf(&mut self) -> std::io::Result<()> {
-        self.output_pos = 0;
-        self.output_size = 0;
+    /// Limit the rate at which data is read from the inner reader to `bytes_per_second`, by
+    /// sleeping the current thread as needed.
+    #[must_use]
+    pub fn with_throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(Throttle::new(bytes_per_second));
+        self
+    }
 
-        if self.end_of_stream {
-            return Ok(());
-        }
+    /// Send a [`ChunkStats`] record to `sender` for every chunk read from the inner reader.
+    ///
+    /// The data itself is not affected; once the receiver is dropped no more records are sent.
+    #[must_use]
+    pub fn with_stats(mut self, sender: Sender<ChunkStats>) -> Self {
+        self.stats = Some(StatsRecorder::new(sender));
+        self
+    }
+
+    /// Add the statistics of every chunk read from the inner reader to `stats`, a sliding time window shared
+    /// with the caller. This replaces any [`with_stats`](Self::with_stats) channel.
+    #[must_use]
+    pub fn with_windowed_stats(mut self, stats: WindowedStats) -> Self {
+        self.stats = Some(StatsRecorder::windowed(stats));
+        self
+    }
+
+    /// Start recording the chunk calls made from now on, see [`trace`](crate::trace).
+    ///
+    /// The trace grows with every chunk, so only enable it while investigating a problem. This
+    /// method requires the `trace` feature to be enabled.
+    #[cfg(feature = "trace")]
+    #[must_use]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(crate::trace::ChunkTrace::new());
+        self
+    }
+
+    /// Returns the recorded trace, if recording was enabled with
+    /// [`with_trace`](Self::with_trace).
+    ///
+    /// This method requires the `trace` feature to be enabled.
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> Option<&crate::trace::ChunkTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Returns the number of bytes read from the inner reader so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    /// Returns the number of normalized bytes returned to the caller so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.bytes_produced
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.input_buf.len() + self.output_buf.len()
+    }
 
+    /// Read the next chunk from the inner reader and normalize it into `direct` if given, or
+    /// into the internal output buffer otherwise. Returns the number of bytes produced.
+    fn normalize_next(&mut self, direct: Option<&mut [u8]>) -> std::io::Result<usize> {
         let bytes_read = self.inner.read(&mut self.input_buf)?;
+        self.bytes_consumed += bytes_read as u64;
+        if let Some(throttle) = &mut self.throttle {
+            std::thread::sleep(throttle.delay(bytes_read as u64));
+        }
         let is_last_chunk = if bytes_read == 0 {
             self.end_of_stream = true;
             true
@@ -52,17 +145,54 @@ impl<R: Read, N: NormalizeChunk> Reader<R, N> {
             false
         };
 
+        let output = direct.unwrap_or(&mut self.output_buf);
         let status = N::normalize_chunk(
             &self.input_buf[..bytes_read],
-            slice_to_uninit_mut(&mut self.output_buf),
+            slice_to_uninit_mut(output),
             self.state.as_ref(),
             is_last_chunk,
         )
         .map_err(std::io::Error::other)?;
 
-        self.output_size = status.output_len();
+        if let Some(stats) = &mut self.stats {
+            stats.record(
+                &self.input_buf[..bytes_read],
+                status.output_len(),
+                is_last_chunk,
+            );
+        }
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.push(bytes_read, is_last_chunk);
+        }
         self.state = status.state().cloned();
-        Ok(())
+        Ok(status.output_len())
+    }
+
+    /// Whether data held back by the format has to be passed on before the next chunk is read,
+    /// so the output of that chunk fits in the output buffer.
+    fn must_drain(&self) -> bool {
+        let state = self.state.as_ref();
+        !fits::<N>(
+            &self.output_buf,
+            self.input_buf.len(),
+            state,
+            ChunkEnd::More,
+        ) || !fits::<N>(&self.output_buf, 0, state, ChunkEnd::Last)
+    }
+
+    /// Pass on a piece of the data held back by the format into the internal output buffer.
+    /// Returns the number of bytes produced.
+    fn drain(&mut self) -> std::io::Result<usize> {
+        let output_len = drain::<N>(&mut self.output_buf, &mut self.state)?;
+        if let Some(stats) = &mut self.stats {
+            stats.record(&[], output_len, false);
+        }
+        #[cfg(feature = "trace")]
+        if let Some(trace) = &mut self.trace {
+            trace.push(0, false);
+        }
+        Ok(output_len)
     }
 
     pub fn into_inner(self) -> R {
@@ -70,24 +200,94 @@ impl<R: Read, N: NormalizeChunk> Reader<R, N> {
     }
 }
 
+impl<R: Clone, N: NormalizeChunk> Clone for Reader<R, N> {
+    /// Clones the reader including any buffered output and the carried chunk state, so the
+    /// clone continues producing exactly the same output as the original from this point.
+    fn clone(&self) -> Self {
+        Self {
+            _phantom: PhantomData,
+            inner: self.inner.clone(),
+            input_buf: self.input_buf.clone(),
+            output_buf: self.output_buf.clone(),
+            output_pos: self.output_pos,
+            output_size: self.output_size,
+            state: self.state.clone(),
+            end_of_stream: self.end_of_stream,
+            bytes_consumed: self.bytes_consumed,
+            bytes_produced: self.bytes_produced,
+            throttle: self.throttle.clone(),
+            stats: self.stats.clone(),
+            #[cfg(feature = "trace")]
+            trace: self.trace.clone(),
+        }
+    }
+}
+
 impl<R: Read, N: NormalizeChunk> Read for Reader<R, N> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        if self.output_pos >= self.output_size {
-            self.fill_buf()?;
-        }
-        if self.output_size == 0 {
-            return Ok(0);
+        // A chunk can normalize to nothing (e.g. a lone `\n` completing a `\r\n`), so keep
+        // going until there is output or the stream has ended.
+        while self.output_pos >= self.output_size {
+            if self.end_of_stream {
+                return Ok(0);
+            }
+            if self.must_drain() {
+                self.output_pos = 0;
+                self.output_size = self.drain()?;
+                continue;
+            }
+            let worst_case =
+                N::max_output_size_for_chunk(self.input_buf.len(), self.state.as_ref(), true);
+            if buf.len() >= worst_case {
+                // Fast path: the caller's buffer can hold any chunk, so skip the output buffer.
+                let bytes_now = self.normalize_next(Some(buf))?;
+                if bytes_now > 0 {
+                    self.bytes_produced += bytes_now as u64;
+                    return Ok(bytes_now);
+                }
+            } else {
+                self.output_pos = 0;
+                self.output_size = self.normalize_next(None)?;
+            }
         }
 
         let bytes_now = buf.len().min(self.output_size - self.output_pos);
         buf[..bytes_now]
             .copy_from_slice(&self.output_buf[self.output_pos..self.output_pos + bytes_now]);
         self.output_pos += bytes_now;
+        self.bytes_produced += bytes_now as u64;
         Ok(bytes_now)
     }
 }
 
+impl<R: Read, N: NormalizeChunk> BufRead for Reader<R, N> {
+    /// Returns the normalized data in the internal output buffer, normalizing the next chunk
+    /// if it is empty.
+    ///
+    /// Consumers like [`NormalizerBuilder::copy`](crate::NormalizerBuilder::copy) use this to
+    /// move normalized data to their destination without an intermediate buffer.
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.output_pos >= self.output_size && !self.end_of_stream {
+            self.output_pos = 0;
+            self.output_size = if self.must_drain() {
+                self.drain()?
+            } else {
+                self.normalize_next(None)?
+            };
+        }
+        Ok(&self.output_buf[self.output_pos..self.output_size])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.output_size - self.output_pos);
+        self.output_pos += amt;
+        self.bytes_produced += amt as u64;
+    }
+}
+
 /// A `std::io::Write` wrapper and implementation that normalizes newlines on-the-fly.
+///
+/// Like [`Reader`], this works with any [`NormalizeChunk`] implementation.
 pub struct Writer<W, S: NormalizeChunk> {
     _phantom: PhantomData<S>,
     inner: W,
@@ -95,89 +295,261 @@ pub struct Writer<W, S: NormalizeChunk> {
     output_buf: Box<[u8]>,
     input_pos: usize,
     state: Option<S::State>,
+    flush_policy: FlushPolicy,
+    bytes_consumed: u64,
+    bytes_produced: u64,
+    throttle: Option<Throttle>,
+    stats: Option<StatsRecorder>,
+    #[cfg(feature = "trace")]
+    trace: Option<crate::trace::ChunkTrace>,
 }
 
 impl<W: Write, N: NormalizeChunk> Writer<W, N> {
+    /// Wrap `inner`, buffering up to `buf_size` bytes of input before normalizing them.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     pub fn new(inner: W, buf_size: usize) -> Self {
+        let buf_size = clamp_buffer_size(buf_size);
         let input_buf = vec![0; buf_size].into_boxed_slice();
-        let required = N::max_output_size_for_chunk(buf_size, None, false);
+        let output_buf = vec![0; output_buffer_size::<N>(buf_size)].into_boxed_slice();
         Self {
             _phantom: PhantomData,
             inner,
             input_buf,
-            output_buf: vec![0; required].into_boxed_slice(),
+            output_buf,
             input_pos: 0,
             state: None,
+            flush_policy: FlushPolicy::default(),
+            bytes_consumed: 0,
+            bytes_produced: 0,
+            throttle: None,
+            stats: None,
+            #[cfg(feature = "trace")]
+            trace: None,
         }
     }
 
+    /// Set the `FlushPolicy` that controls when buffered data is passed on to the inner writer.
+    #[must_use]
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+
+    /// Limit the rate at which data is accepted to `bytes_per_second`, by sleeping the current
+    /// thread as needed.
+    #[must_use]
+    pub fn with_throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(Throttle::new(bytes_per_second));
+        self
+    }
+
+    /// Send a [`ChunkStats`] record to `sender` for every chunk written to the inner writer.
+    ///
+    /// The data itself is not affected; once the receiver is dropped no more records are sent.
+    #[must_use]
+    pub fn with_stats(mut self, sender: Sender<ChunkStats>) -> Self {
+        self.stats = Some(StatsRecorder::new(sender));
+        self
+    }
+
+    /// Add the statistics of every chunk written to the inner writer to `stats`, a sliding time window shared
+    /// with the caller. This replaces any [`with_stats`](Self::with_stats) channel.
+    #[must_use]
+    pub fn with_windowed_stats(mut self, stats: WindowedStats) -> Self {
+        self.stats = Some(StatsRecorder::windowed(stats));
+        self
+    }
+
+    /// Start recording the chunk calls made from now on, see [`trace`](crate::trace).
+    ///
+    /// The trace grows with every chunk, so only enable it while investigating a problem. This
+    /// method requires the `trace` feature to be enabled.
+    #[cfg(feature = "trace")]
+    #[must_use]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(crate::trace::ChunkTrace::new());
+        self
+    }
+
+    /// Returns the recorded trace, if recording was enabled with
+    /// [`with_trace`](Self::with_trace).
+    ///
+    /// This method requires the `trace` feature to be enabled.
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> Option<&crate::trace::ChunkTrace> {
+        self.trace.as_ref()
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer.
+    ///
+    /// Writing to the inner writer directly bypasses normalization and can interleave with
+    /// output that is still buffered.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the number of bytes accepted from the caller so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.bytes_consumed
+    }
+
+    /// Returns the number of normalized bytes written to the inner writer so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.bytes_produced
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.input_buf.len() + self.output_buf.len()
+    }
+
     pub fn finish(self) -> std::io::Result<W> {
         let mut this = self;
         // Finalize any remaining input
-        let status = N::normalize_chunk(
-            &this.input_buf[..this.input_pos],
-            slice_to_uninit_mut(&mut this.output_buf),
-            this.state.as_ref(),
-            true, // this is the last chunk
-        )
-        .map_err(std::io::Error::other)?;
-
-        this.inner
-            .write_all(&this.output_buf[..status.output_len()])?;
+        this.write_chunk(ChunkEnd::Last)?;
         Ok(this.inner)
     }
-}
 
-impl<W: Write, N: NormalizeChunk> Write for Writer<W, N> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    /// Write `buf` as the final data of the stream and finish, like `write_all` followed by
+    /// [`finish`](Self::finish).
+    ///
+    /// The last part of `buf` is normalized as the end of the stream, instead of being followed
+    /// by a separate empty final chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to the inner writer.
+    pub fn finish_with(mut self, buf: &[u8]) -> std::io::Result<W> {
+        let bytes_written = self.write_chunks(buf, true)?;
+        if let Some(throttle) = &mut self.throttle {
+            std::thread::sleep(throttle.delay(bytes_written as u64));
+        }
+        self.finish()
+    }
+
+    /// Normalize everything in the input buffer and write the result to the inner writer.
+    fn write_chunk(&mut self, end: ChunkEnd) -> std::io::Result<()> {
+        loop {
+            let input_len = self.input_pos;
+            let (output_len, done) = normalize_staged::<N>(
+                &self.input_buf,
+                &mut self.input_pos,
+                &mut self.output_buf,
+                &mut self.state,
+                end,
+            )?;
+            let chunk = &self.input_buf[..input_len - self.input_pos];
+            let is_last_chunk = done && end.is_last_chunk();
+
+            self.inner.write_all(&self.output_buf[..output_len])?;
+            if let Some(stats) = &mut self.stats {
+                stats.record(chunk, output_len, is_last_chunk);
+            }
+            #[cfg(feature = "trace")]
+            if let Some(trace) = &mut self.trace {
+                trace.push(chunk.len(), is_last_chunk);
+            }
+            self.bytes_produced += output_len as u64;
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Normalize and write `buf` without applying the throttle. If `is_last` is set, `buf` is
+    /// the rest of the stream and its last bytes are left in the input buffer for `finish`.
+    fn write_chunks(&mut self, buf: &[u8], is_last: bool) -> std::io::Result<usize> {
         let mut source_buf = buf;
         let mut total_bytes = 0;
 
         while total_bytes < buf.len() {
+            // The last chunk of the stream always goes through the input buffer, as do chunks
+            // that have to wait for data held back by the format.
+            if self.input_pos == 0
+                && source_buf.len() >= self.input_buf.len() + usize::from(is_last)
+                && fits::<N>(
+                    &self.output_buf,
+                    self.input_buf.len(),
+                    self.state.as_ref(),
+                    ChunkEnd::More,
+                )
+            {
+                // Fast path: a full chunk is available, normalize it straight from the caller's
+                // buffer without staging it in the input buffer first.
+                let (chunk, rest) = source_buf.split_at(self.input_buf.len());
+                let status = N::normalize_chunk(
+                    chunk,
+                    slice_to_uninit_mut(&mut self.output_buf),
+                    self.state.as_ref(),
+                    false,
+                )
+                .map_err(std::io::Error::other)?;
+
+                self.inner
+                    .write_all(&self.output_buf[..status.output_len()])?;
+                if let Some(stats) = &mut self.stats {
+                    stats.record(chunk, status.output_len(), false);
+                }
+                #[cfg(feature = "trace")]
+                if let Some(trace) = &mut self.trace {
+                    trace.push(chunk.len(), false);
+                }
+                self.bytes_produced += status.output_len() as u64;
+                self.bytes_consumed += chunk.len() as u64;
+                self.state = status.state().cloned();
+                total_bytes += chunk.len();
+                source_buf = rest;
+                continue;
+            }
+
             let bytes_now = source_buf.len().min(self.input_buf.len() - self.input_pos);
             total_bytes += bytes_now;
+            self.bytes_consumed += bytes_now as u64;
 
             self.input_buf[self.input_pos..self.input_pos + bytes_now]
                 .copy_from_slice(&source_buf[..bytes_now]);
             self.input_pos += bytes_now;
             source_buf = &source_buf[bytes_now..];
 
-            if self.input_pos < self.input_buf.len() {
+            if self.input_pos < self.input_buf.len() && !self.flush_policy.eager {
                 // Not enough data yet to process a full chunk.
                 return Ok(total_bytes);
             }
+            if is_last && source_buf.is_empty() {
+                // The last chunk is left for `finish` to normalize.
+                return Ok(total_bytes);
+            }
 
-            let status = N::normalize_chunk(
-                &self.input_buf,
-                slice_to_uninit_mut(&mut self.output_buf),
-                self.state.as_ref(),
-                false,
-            )
-            .map_err(std::io::Error::other)?;
-
-            self.inner
-                .write_all(&self.output_buf[..status.output_len()])?;
-            self.state = status.state().cloned();
-            self.input_pos = 0;
+            self.write_chunk(ChunkEnd::More)?;
         }
         Ok(total_bytes)
     }
+}
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        let status = N::normalize_chunk(
-            &self.input_buf[..self.input_pos],
-            slice_to_uninit_mut(&mut self.output_buf),
-            self.state.as_ref(),
-            false, // flush is not neccesarily the end of stream
-        )
-        .map_err(std::io::Error::other)?;
-
-        if status.output_len() > 0 {
-            self.inner
-                .write_all(&self.output_buf[..status.output_len()])?;
-            self.state = status.state().cloned();
-            self.input_pos = 0;
+impl<W: Write, N: NormalizeChunk> Write for Writer<W, N> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let bytes_written = self.write_chunks(buf, false)?;
+        if let Some(throttle) = &mut self.throttle {
+            std::thread::sleep(throttle.delay(bytes_written as u64));
         }
+        Ok(bytes_written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        // Flush is not neccesarily the end of stream, at most a boundary if the policy says so.
+        self.write_chunk(if self.flush_policy.resolve_dangling_cr {
+            ChunkEnd::Boundary
+        } else {
+            ChunkEnd::More
+        })?;
         self.inner.flush()
     }
 }
@@ -190,19 +562,67 @@ where
 {
     /// Wrap a reader with a newline-normalizing `Reader`.
     fn wrap_reader<R: Read>(reader: R) -> Reader<R, Self> {
-        Self::wrap_reader_with_buffer_size(reader, 8192)
+        Self::wrap_reader_with_buffer_size(reader, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a reader with a newline-normalizing `Reader` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_reader_with_buffer_size<R: Read>(reader: R, buf_size: usize) -> Reader<R, Self>;
 
     /// Wrap a writer with a newline-normalizing `Writer`.
     fn wrap_writer<W: Write>(writer: W) -> Writer<W, Self> {
-        Self::wrap_writer_with_buffer_size(writer, 8192)
+        Self::wrap_writer_with_buffer_size(writer, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a writer with a newline-normalizing `Writer` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_writer_with_buffer_size<W: Write>(writer: W, buf_size: usize) -> Writer<W, Self>;
+
+    /// Read everything from `reader` and return the normalized bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader`.
+    fn read_to_vec<R: Read>(reader: R) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        Self::wrap_reader(reader).read_to_end(&mut out)?;
+        Ok(out)
+    }
+
+    /// Read everything from `reader` and return the normalized text.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by reading from `reader`, or an error of kind
+    /// `InvalidData` if the normalized output is not valid UTF-8.
+    fn read_to_string<R: Read>(reader: R) -> std::io::Result<String> {
+        let mut out = String::new();
+        Self::wrap_reader(reader).read_to_string(&mut out)?;
+        Ok(out)
+    }
+
+    /// Wrap `writer`, pass the normalizing `Writer` to `f` and finish it once `f` succeeds.
+    ///
+    /// Returns the inner writer together with the value returned by `f`. If `f` fails, data
+    /// still buffered in the `Writer` is discarded and the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by `f`, or any error produced by writing the remaining data
+    /// to `writer`.
+    fn with_writer<W, T, F>(writer: W, f: F) -> std::io::Result<(W, T)>
+    where
+        W: Write,
+        F: FnOnce(&mut Writer<W, Self>) -> std::io::Result<T>,
+    {
+        let mut normalizer = Self::wrap_writer(writer);
+        let value = f(&mut normalizer)?;
+        Ok((normalizer.finish()?, value))
+    }
 }
 
 impl<N: NormalizeChunk> IoExt for N {
@@ -221,6 +641,11 @@ pub trait ReadExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> Reader<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the reader with an `EolReader` normalizing to the line ending chosen at runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolReader<Self>
+    where
+        Self: Sized;
 }
 
 impl<R: Read> ReadExt for R {
@@ -230,6 +655,13 @@ impl<R: Read> ReadExt for R {
     {
         N::wrap_reader(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolReader<Self>
+    where
+        Self: Sized,
+    {
+        format.wrap_reader(self)
+    }
 }
 
 /// Extension trait to provide convenient methods on `std::io::Write`.
@@ -238,6 +670,11 @@ pub trait WriteExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> Writer<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the writer with an `EolWriter` normalizing to the line ending chosen at runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolWriter<Self>
+    where
+        Self: Sized;
 }
 
 impl<W: Write> WriteExt for W {
@@ -247,4 +684,11 @@ impl<W: Write> WriteExt for W {
     {
         N::wrap_writer(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolWriter<Self>
+    where
+        Self: Sized,
+    {
+        format.wrap_writer(self)
+    }
 }
@@ -1,4 +1,241 @@
+use crate::{formats::ChunkEnd, helpers::slice_to_uninit_mut, NormalizeChunk};
+
+/// The buffer size used by the wrappers when none is specified explicitly.
+pub(crate) const DEFAULT_BUFFER_SIZE: usize = 8192;
+
+/// The amount of output `NormalizerBuilder::normalize_large` keeps in memory when no spill
+/// threshold is configured.
+pub(crate) const DEFAULT_SPILL_THRESHOLD: usize = 8 << 20;
+
+/// The largest input buffer the wrappers will allocate.
+///
+/// The output buffer has to hold the worst-case expansion of a full input buffer (twice its
+/// size for CRLF), so on targets with a small address space (32-bit, wasm32) the limit is kept
+/// low enough for both buffers to be allocated comfortably.
+#[cfg(target_pointer_width = "64")]
+pub(crate) const MAX_BUFFER_SIZE: usize = 1 << 30;
+#[cfg(not(target_pointer_width = "64"))]
+pub(crate) const MAX_BUFFER_SIZE: usize = 16 << 20;
+
+/// Clamp a requested buffer size to what the wrappers can actually work with.
+pub(crate) fn clamp_buffer_size(buf_size: usize) -> usize {
+    buf_size.clamp(1, MAX_BUFFER_SIZE)
+}
+
+/// The size of the output buffer for chunks of up to `buf_size` bytes.
+///
+/// The output buffer is allocated for the worst case without any state, wherever the chunk
+/// ends, and never grows. Formats holding back data between chunks, such as
+/// `TrimTrailingNewlines`, may need more room once they carry state; they pass the held back
+/// data on in bounded pieces instead, see [`drain`].
+pub(crate) fn output_buffer_size<N: NormalizeChunk>(buf_size: usize) -> usize {
+    [ChunkEnd::More, ChunkEnd::Boundary, ChunkEnd::Last]
+        .into_iter()
+        .map(|end| end.max_output_size::<N>(buf_size, None))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Whether the output of a chunk of `chunk_size` bytes ending at `end` fits in `output_buf`.
+pub(crate) fn fits<N: NormalizeChunk>(
+    output_buf: &[u8],
+    chunk_size: usize,
+    state: Option<&N::State>,
+    end: ChunkEnd,
+) -> bool {
+    end.max_output_size::<N>(chunk_size, state) <= output_buf.len()
+}
+
+/// Normalize an empty chunk into `output_buf`, which passes on a piece of the data held back
+/// by the format. Returns the number of bytes produced.
+///
+/// This makes room for the next chunk when its output doesn't [`fits`] yet.
+pub(crate) fn drain<N: NormalizeChunk>(
+    output_buf: &mut [u8],
+    state: &mut Option<N::State>,
+) -> std::io::Result<usize> {
+    let status = N::normalize_chunk(&[], slice_to_uninit_mut(output_buf), state.as_ref(), false)
+        .map_err(std::io::Error::other)?;
+    *state = status.state().cloned();
+    Ok(status.output_len())
+}
+
+/// Take a step towards normalizing the first `*input_pos` bytes of `input_buf` as a chunk
+/// ending at `end`, writing the output to `output_buf`.
+///
+/// If the output of the chunk doesn't fit, the input is normalized without its end first, or
+/// held back data is [`drain`]ed; the caller repeats the step until it returns `true` along with
+/// the number of bytes produced. `*input_pos` is reset once the input is normalized.
+pub(crate) fn normalize_staged<N: NormalizeChunk>(
+    input_buf: &[u8],
+    input_pos: &mut usize,
+    output_buf: &mut [u8],
+    state: &mut Option<N::State>,
+    end: ChunkEnd,
+) -> std::io::Result<(usize, bool)> {
+    let chunk_end = if fits::<N>(output_buf, *input_pos, state.as_ref(), end) {
+        end
+    } else if *input_pos > 0 && fits::<N>(output_buf, *input_pos, state.as_ref(), ChunkEnd::More) {
+        ChunkEnd::More
+    } else {
+        return Ok((drain::<N>(output_buf, state)?, false));
+    };
+    let status = chunk_end
+        .normalize::<N>(
+            &input_buf[..*input_pos],
+            slice_to_uninit_mut(output_buf),
+            state.as_ref(),
+        )
+        .map_err(std::io::Error::other)?;
+    *state = status.state().cloned();
+    *input_pos = 0;
+    Ok((status.output_len(), chunk_end == end))
+}
+
+/// Upper bound for buffer sizes suggested by [`BufferSizeHint`].
+const MAX_BUFFER_SIZE_HINT: usize = if MAX_BUFFER_SIZE < 1 << 20 {
+    MAX_BUFFER_SIZE
+} else {
+    1 << 20
+};
+
+/// Suggests a suitable internal buffer size for wrappers around a reader or writer.
+///
+/// The defaults are chosen per wrapped type: files benefit from large buffers, sockets from
+/// small ones so data flows promptly, and in-memory sources are processed in as few chunks
+/// as possible. Types without a specific implementation can rely on the provided default.
+pub trait BufferSizeHint {
+    /// Returns the suggested size of the internal input buffer.
+    fn buffer_size_hint(&self) -> usize {
+        DEFAULT_BUFFER_SIZE
+    }
+}
+
+impl BufferSizeHint for std::fs::File {
+    fn buffer_size_hint(&self) -> usize {
+        64 * 1024
+    }
+}
+
+impl BufferSizeHint for std::net::TcpStream {
+    fn buffer_size_hint(&self) -> usize {
+        4 * 1024
+    }
+}
+
+impl BufferSizeHint for &[u8] {
+    fn buffer_size_hint(&self) -> usize {
+        self.len().clamp(1, MAX_BUFFER_SIZE_HINT)
+    }
+}
+
+impl<T: AsRef<[u8]>> BufferSizeHint for std::io::Cursor<T> {
+    fn buffer_size_hint(&self) -> usize {
+        let len = self.get_ref().as_ref().len() as u64;
+        usize::try_from(len.saturating_sub(self.position()))
+            .unwrap_or(MAX_BUFFER_SIZE_HINT)
+            .clamp(1, MAX_BUFFER_SIZE_HINT)
+    }
+}
+
+/// A `Vec` is written to, so its length tells nothing about the amount of data to come.
+impl BufferSizeHint for Vec<u8> {}
+
+impl BufferSizeHint for std::io::Stdin {}
+
+impl BufferSizeHint for std::io::StdinLock<'_> {}
+
+impl BufferSizeHint for std::io::Stdout {}
+
+impl BufferSizeHint for std::io::StdoutLock<'_> {}
+
+/// Data passes through the buffer of the `BufReader`, so its capacity is a natural chunk size.
+impl<R> BufferSizeHint for std::io::BufReader<R> {
+    fn buffer_size_hint(&self) -> usize {
+        self.capacity().clamp(1, MAX_BUFFER_SIZE_HINT)
+    }
+}
+
+/// Data passes through the buffer of the `BufWriter`, so its capacity is a natural chunk size.
+impl<W: std::io::Write> BufferSizeHint for std::io::BufWriter<W> {
+    fn buffer_size_hint(&self) -> usize {
+        self.capacity().clamp(1, MAX_BUFFER_SIZE_HINT)
+    }
+}
+
+impl<T: BufferSizeHint + ?Sized> BufferSizeHint for &mut T {
+    fn buffer_size_hint(&self) -> usize {
+        (**self).buffer_size_hint()
+    }
+}
+
+impl<T: BufferSizeHint + ?Sized> BufferSizeHint for Box<T> {
+    fn buffer_size_hint(&self) -> usize {
+        (**self).buffer_size_hint()
+    }
+}
+
+/// Controls when a normalizing writer passes buffered data on to the writer it wraps.
+///
+/// The default policy maximizes batching: input is only normalized once the internal buffer
+/// is full, and a flush never resolves a trailing `\r` because the next write might start
+/// with the matching `\n`. Interactive uses (e.g. a REPL writing through the wrapper) will
+/// want [`FlushPolicy::interactive`] instead so output shows up promptly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlushPolicy {
+    pub(crate) resolve_dangling_cr: bool,
+    pub(crate) eager: bool,
+}
+
+impl FlushPolicy {
+    /// Buffer as much as possible. This is the default.
+    #[must_use]
+    pub fn batch() -> Self {
+        Self::default()
+    }
+
+    /// Normalize on every write and treat every flush as a line boundary.
+    #[must_use]
+    pub fn interactive() -> Self {
+        Self {
+            resolve_dangling_cr: true,
+            eager: true,
+        }
+    }
+
+    /// Whether `flush()` resolves a trailing `\r` as a complete line ending.
+    ///
+    /// When enabled, the flush is treated as a soft boundary, see
+    /// [`NormalizeChunk::normalize_chunk_at_boundary`](crate::NormalizeChunk::normalize_chunk_at_boundary):
+    /// a `\r` at the end of the flushed data is converted right away. As a consequence a `\n`
+    /// at the start of the next write is seen as a line ending of its own, so a `\r\n` split by
+    /// a flush becomes two line endings. The stream doesn't end, so adapters acting at the end
+    /// of the stream, such as `EnsureFinalNewline`, are not affected by flushing.
+    #[must_use]
+    pub fn resolve_dangling_cr(mut self, resolve_dangling_cr: bool) -> Self {
+        self.resolve_dangling_cr = resolve_dangling_cr;
+        self
+    }
+
+    /// Whether every write is normalized and passed on immediately, instead of waiting for
+    /// the internal buffer to fill up.
+    #[must_use]
+    pub fn eager(mut self, eager: bool) -> Self {
+        self.eager = eager;
+        self
+    }
+}
+
+pub(crate) mod chunks;
+pub(crate) mod commit;
+pub(crate) mod console;
+pub(crate) mod eol;
+pub(crate) mod fmt;
 pub(crate) mod io;
+pub(crate) mod spool;
+pub(crate) mod stats;
+pub(crate) mod tee;
+pub(crate) mod throttle;
 
 #[cfg(any(feature = "futures-io", feature = "tokio"))]
 pub mod async_core;
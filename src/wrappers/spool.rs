@@ -0,0 +1,192 @@
+//! The `spool` module provides a byte sink that is kept in memory up to a limit and spills
+//! to a temporary file beyond it.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A temporary file that is removed when dropped.
+struct TempFile {
+    file: File,
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn create() -> std::io::Result<Self> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        loop {
+            let path = std::env::temp_dir().join(format!(
+                "eolify-{}-{}.spool",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            match OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => return Ok(Self { file, path }),
+                // Left behind by an earlier process with the same id.
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Collects written bytes in memory until they exceed `limit`, after which everything is moved
+/// to a temporary file and later writes go there directly.
+pub(crate) struct Spool {
+    memory: Vec<u8>,
+    file: Option<TempFile>,
+    limit: Option<usize>,
+}
+
+impl Spool {
+    /// Create a spool that spills to disk beyond `limit` bytes, or never if `limit` is `None`.
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        Self {
+            memory: Vec::new(),
+            file: None,
+            limit,
+        }
+    }
+
+    /// Change the spill limit. Data that was spilled already stays on disk.
+    pub(crate) fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Whether the data was spilled to a temporary file.
+    pub(crate) fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Write everything collected so far to `writer`, returning the number of bytes written.
+    pub(crate) fn copy_to<W: Write + ?Sized>(&mut self, writer: &mut W) -> std::io::Result<u64> {
+        match &mut self.file {
+            Some(temp) => {
+                temp.file.seek(SeekFrom::Start(0))?;
+                std::io::copy(&mut temp.file, writer)
+            }
+            None => {
+                writer.write_all(&self.memory)?;
+                Ok(self.memory.len() as u64)
+            }
+        }
+    }
+
+    /// Turn the collected bytes into a readable, seekable `SpooledOutput`, positioned at the
+    /// start.
+    pub(crate) fn into_output(self) -> std::io::Result<SpooledOutput> {
+        let inner = match self.file {
+            Some(mut temp) => {
+                temp.file.flush()?;
+                temp.file.seek(SeekFrom::Start(0))?;
+                SpooledInner::File(temp)
+            }
+            None => SpooledInner::Memory(Cursor::new(self.memory)),
+        };
+        Ok(SpooledOutput { inner })
+    }
+
+    fn spill(&mut self) -> std::io::Result<&mut File> {
+        let mut temp = TempFile::create()?;
+        temp.file.write_all(&self.memory)?;
+        self.memory = Vec::new();
+        Ok(&mut self.file.insert(temp).file)
+    }
+}
+
+impl Write for Spool {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(temp) = &mut self.file {
+            return temp.file.write(buf);
+        }
+        match self.limit {
+            Some(limit) if self.memory.len().saturating_add(buf.len()) > limit => {
+                self.spill()?.write(buf)
+            }
+            _ => {
+                self.memory.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.file {
+            Some(temp) => temp.file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Normalized output that is either held in memory or stored in a temporary file.
+///
+/// The temporary file is removed when the `SpooledOutput` is dropped.
+pub struct SpooledOutput {
+    inner: SpooledInner,
+}
+
+enum SpooledInner {
+    Memory(Cursor<Vec<u8>>),
+    File(TempFile),
+}
+
+impl SpooledOutput {
+    /// Whether the output was spilled to a temporary file.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.inner, SpooledInner::File(_))
+    }
+
+    /// Returns the total length of the output in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata of the temporary file can't be read.
+    pub fn len(&self) -> std::io::Result<u64> {
+        match &self.inner {
+            SpooledInner::Memory(cursor) => Ok(cursor.get_ref().len() as u64),
+            SpooledInner::File(temp) => Ok(temp.file.metadata()?.len()),
+        }
+    }
+
+    /// Returns `true` if the output is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata of the temporary file can't be read.
+    pub fn is_empty(&self) -> std::io::Result<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+impl Read for SpooledOutput {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            SpooledInner::Memory(cursor) => cursor.read(buf),
+            SpooledInner::File(temp) => temp.file.read(buf),
+        }
+    }
+}
+
+impl Seek for SpooledOutput {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match &mut self.inner {
+            SpooledInner::Memory(cursor) => cursor.seek(pos),
+            SpooledInner::File(temp) => temp.file.seek(pos),
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! The `stats` module provides per-chunk statistics about the line endings flowing through the
+//! wrappers, delivered over a channel or aggregated into time windows while the data itself
+//! passes through unchanged.
+
+use std::{
+    collections::VecDeque,
+    sync::{mpsc::Sender, Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{helpers::LineEndingCounter, types::LineTerminator};
+
+/// Statistics about one chunk of input processed by a [`Reader`](crate::Reader) or
+/// [`Writer`](crate::Writer).
+///
+/// A `\r\n` split across two chunks is counted in the chunk holding the `\n`; a `\r` at the end
+/// of the stream is counted in a final record with a length of zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ChunkStats {
+    /// Offset of the chunk in the input stream.
+    pub offset: u64,
+    /// Number of input bytes in the chunk.
+    pub len: usize,
+    /// Number of normalized bytes produced for the chunk.
+    pub output_len: usize,
+    /// Number of `\n` line endings.
+    pub lf: u64,
+    /// Number of `\r\n` line endings.
+    pub crlf: u64,
+    /// Number of lone `\r` line endings.
+    pub cr: u64,
+}
+
+impl ChunkStats {
+    /// Returns the number of line endings in the chunk.
+    #[must_use]
+    pub fn lines(&self) -> u64 {
+        self.lf + self.crlf + self.cr
+    }
+
+    /// Returns the number of line endings that differ from `target` and thus had to be
+    /// converted when normalizing to it.
+    #[must_use]
+    pub fn conversions(&self, target: LineTerminator) -> u64 {
+        let native = match target {
+            LineTerminator::Lf => self.lf,
+            LineTerminator::CrLf => self.crlf,
+            LineTerminator::Cr => self.cr,
+        };
+        self.lines() - native
+    }
+}
+
+/// Line ending counts aggregated over any number of chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EolStats {
+    /// Number of input bytes.
+    pub bytes: u64,
+    /// Number of `\n` line endings.
+    pub lf: u64,
+    /// Number of `\r\n` line endings.
+    pub crlf: u64,
+    /// Number of lone `\r` line endings.
+    pub cr: u64,
+}
+
+impl EolStats {
+    /// Returns the number of line endings.
+    #[must_use]
+    pub fn lines(&self) -> u64 {
+        self.lf + self.crlf + self.cr
+    }
+
+    /// Add the counts of `chunk`.
+    pub fn add_chunk(&mut self, chunk: &ChunkStats) {
+        self.bytes += chunk.len as u64;
+        self.lf += chunk.lf;
+        self.crlf += chunk.crlf;
+        self.cr += chunk.cr;
+    }
+
+    /// Add the counts of `other`.
+    pub fn merge(&mut self, other: &EolStats) {
+        self.bytes += other.bytes;
+        self.lf += other.lf;
+        self.crlf += other.crlf;
+        self.cr += other.cr;
+    }
+}
+
+/// Line ending statistics for a sliding time window, e.g. "the line ending mix over the last
+/// hour", without keeping a record per chunk or per line.
+///
+/// The window is a ring of `intervals` buckets of `interval` each; chunks are added to the
+/// bucket of the moment they are recorded, and buckets older than the window are dropped. The
+/// value is a cheap handle: clones share the same statistics, so one clone can be passed to
+/// [`Reader::with_windowed_stats`](crate::Reader::with_windowed_stats) or
+/// [`Writer::with_windowed_stats`](crate::Writer::with_windowed_stats) while another is used to
+/// read the statistics.
+///
+/// ```
+/// use std::{io::Read, time::Duration};
+/// use eolify::{ReadExt, WindowedStats, LF};
+///
+/// let stats = WindowedStats::new(Duration::from_secs(60), 60);
+/// let mut output = String::new();
+/// b"one\r\ntwo\n"
+///     .as_ref()
+///     .normalize_newlines(LF)
+///     .with_windowed_stats(stats.clone())
+///     .read_to_string(&mut output)?;
+///
+/// let totals = stats.totals();
+/// assert_eq!((totals.lf, totals.crlf), (1, 1));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowedStats {
+    inner: Arc<Mutex<Window>>,
+}
+
+#[derive(Debug)]
+struct Window {
+    start: Instant,
+    interval: Duration,
+    intervals: usize,
+    /// Buckets keyed by their interval number since `start`, oldest first.
+    buckets: VecDeque<(u64, EolStats)>,
+}
+
+impl Window {
+    fn index(&self, now: Instant) -> u64 {
+        let elapsed = now.saturating_duration_since(self.start).as_nanos();
+        u64::try_from(elapsed / self.interval.as_nanos()).unwrap_or(u64::MAX)
+    }
+
+    fn expire(&mut self, index: u64) {
+        while let Some(&(oldest, _)) = self.buckets.front() {
+            if oldest.saturating_add(self.intervals as u64) > index {
+                break;
+            }
+            self.buckets.pop_front();
+        }
+    }
+}
+
+impl WindowedStats {
+    /// Create statistics covering the last `intervals` periods of length `interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero or `intervals` is zero.
+    #[must_use]
+    pub fn new(interval: Duration, intervals: usize) -> Self {
+        assert!(!interval.is_zero(), "interval must not be zero");
+        assert!(intervals > 0, "intervals must not be zero");
+        Self {
+            inner: Arc::new(Mutex::new(Window {
+                start: Instant::now(),
+                interval,
+                intervals,
+                buckets: VecDeque::with_capacity(intervals),
+            })),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Window> {
+        // The window is consistent after every operation, so a panic elsewhere can't corrupt it.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Add `chunk` to the current interval.
+    pub fn record(&self, chunk: &ChunkStats) {
+        self.record_at(chunk, Instant::now());
+    }
+
+    /// Add `chunk` to the interval containing `now`. Times before the latest recorded interval
+    /// are counted in that interval.
+    pub fn record_at(&self, chunk: &ChunkStats, now: Instant) {
+        let mut window = self.lock();
+        let mut index = window.index(now);
+        if let Some(&(latest, _)) = window.buckets.back() {
+            index = index.max(latest);
+        }
+        window.expire(index);
+        match window.buckets.back_mut() {
+            Some((latest, stats)) if *latest == index => stats.add_chunk(chunk),
+            _ => {
+                let mut stats = EolStats::default();
+                stats.add_chunk(chunk);
+                window.buckets.push_back((index, stats));
+            }
+        }
+    }
+
+    /// Returns the totals over the window ending now.
+    #[must_use]
+    pub fn totals(&self) -> EolStats {
+        self.totals_at(Instant::now())
+    }
+
+    /// Returns the totals over the window ending at `now`.
+    #[must_use]
+    pub fn totals_at(&self, now: Instant) -> EolStats {
+        self.intervals_at(now)
+            .iter()
+            .fold(EolStats::default(), |mut totals, stats| {
+                totals.merge(stats);
+                totals
+            })
+    }
+
+    /// Returns the statistics of every interval in the window ending at `now`, oldest first.
+    /// Intervals without any recorded chunk are included with zero counts.
+    #[must_use]
+    pub fn intervals_at(&self, now: Instant) -> Vec<EolStats> {
+        let window = self.lock();
+        let index = window.index(now);
+        let first = (index + 1).saturating_sub(window.intervals as u64);
+        let mut intervals = vec![EolStats::default(); window.intervals.min((index + 1) as usize)];
+        for &(bucket, stats) in &window.buckets {
+            if (first..=index).contains(&bucket) {
+                intervals[(bucket - first) as usize] = stats;
+            }
+        }
+        intervals
+    }
+}
+
+/// Where a [`StatsRecorder`] delivers its records.
+#[derive(Clone)]
+enum StatsSink {
+    Channel(Sender<ChunkStats>),
+    Windowed(WindowedStats),
+}
+
+/// Counts line endings chunk by chunk and delivers a [`ChunkStats`] for each chunk.
+#[derive(Clone)]
+pub(crate) struct StatsRecorder {
+    sink: StatsSink,
+    offset: u64,
+    counter: LineEndingCounter,
+}
+
+impl StatsRecorder {
+    pub(crate) fn new(sender: Sender<ChunkStats>) -> Self {
+        Self::with_sink(StatsSink::Channel(sender))
+    }
+
+    pub(crate) fn windowed(stats: WindowedStats) -> Self {
+        Self::with_sink(StatsSink::Windowed(stats))
+    }
+
+    fn with_sink(sink: StatsSink) -> Self {
+        Self {
+            sink,
+            offset: 0,
+            counter: LineEndingCounter::default(),
+        }
+    }
+
+    /// Record a chunk of `input` that normalized to `output_len` bytes. A disconnected receiver
+    /// is ignored, so dropping it simply stops the statistics.
+    pub(crate) fn record(&mut self, input: &[u8], output_len: usize, is_last_chunk: bool) {
+        if input.is_empty() && !(is_last_chunk && self.counter.has_pending_cr()) {
+            return;
+        }
+
+        let (lf, crlf, cr) = self.counter.count(input, is_last_chunk);
+        let stats = ChunkStats {
+            offset: self.offset,
+            len: input.len(),
+            output_len,
+            lf,
+            crlf,
+            cr,
+        };
+        self.offset += input.len() as u64;
+        match &self.sink {
+            StatsSink::Channel(sender) => {
+                let _ = sender.send(stats);
+            }
+            StatsSink::Windowed(windowed) => windowed.record(&stats),
+        }
+    }
+}
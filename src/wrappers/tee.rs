@@ -0,0 +1,217 @@
+//! The `tee` module provides `std::io::Read` and `std::io::Write` adapters that copy all data to
+//! a second sink, so the original and the normalized bytes can be handled in a single pass.
+//!
+//! Together with [`HashWriter`] this computes digests during normalization: tee the original
+//! bytes into a hasher before the normalizing wrapper, the normalized bytes after it, or both.
+//! Digest implementations that implement `std::io::Write` can be used as sink directly.
+//!
+//! ```
+//! use std::{collections::hash_map::DefaultHasher, hash::Hasher, io::Read};
+//! use eolify::{HashWriter, ReadExt, TeeReader, LF};
+//!
+//! let original = TeeReader::new(b"a\r\nb\r\n".as_ref(), HashWriter::new(DefaultHasher::new()));
+//! let mut normalized = TeeReader::new(
+//!     original.normalize_newlines(LF),
+//!     HashWriter::new(DefaultHasher::new()),
+//! );
+//! let mut out = Vec::new();
+//! normalized.read_to_end(&mut out).unwrap();
+//!
+//! let (reader, normalized_hash) = normalized.into_inner();
+//! let original_hash = reader.into_inner().into_inner().1;
+//! assert_eq!(out, b"a\nb\n");
+//! assert_ne!(original_hash.finish(), normalized_hash.finish());
+//! ```
+
+use std::{
+    hash::Hasher,
+    io::{Read, Write},
+};
+
+use crate::{
+    wrappers::io::{Reader, Writer},
+    IoExt, NormalizeChunk,
+};
+
+/// A `std::io::Read` implementation that writes every byte read from the inner reader to
+/// `sink` before returning it.
+///
+/// Combined with the normalizing wrappers this either archives the original bytes while the
+/// caller reads normalized data ([`TeeExt::normalize_newlines_tee`]), or hands the original
+/// bytes to the caller while normalized data goes to the sink
+/// ([`TeeExt::tee_normalized`]).
+pub struct TeeReader<R, W> {
+    inner: R,
+    sink: W,
+}
+
+impl<R: Read, W: Write> TeeReader<R, W> {
+    pub fn new(reader: R, sink: W) -> Self {
+        Self {
+            inner: reader,
+            sink,
+        }
+    }
+
+    /// Gets a reference to the sink.
+    pub fn sink(&self) -> &W {
+        &self.sink
+    }
+
+    /// Returns the inner reader and the sink.
+    pub fn into_inner(self) -> (R, W) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sink.write_all(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+/// A `std::io::Write` implementation that writes every byte to both the inner writer and
+/// `sink`.
+pub struct TeeWriter<W, S> {
+    inner: W,
+    sink: S,
+}
+
+impl<W: Write, S: Write> TeeWriter<W, S> {
+    pub fn new(writer: W, sink: S) -> Self {
+        Self {
+            inner: writer,
+            sink,
+        }
+    }
+
+    /// Gets a reference to the sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns the inner writer and the sink.
+    pub fn into_inner(self) -> (W, S) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<W: Write, S: Write> Write for TeeWriter<W, S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.sink.write_all(&buf[..n])?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()?;
+        self.sink.flush()
+    }
+}
+
+/// A `std::io::Write` implementation feeding all written bytes to a `std::hash::Hasher`.
+#[derive(Debug, Clone, Default)]
+pub struct HashWriter<H>(H);
+
+impl<H: Hasher> HashWriter<H> {
+    pub fn new(hasher: H) -> Self {
+        Self(hasher)
+    }
+
+    /// Returns the hash of all bytes written so far.
+    pub fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    /// Returns the inner hasher.
+    pub fn into_inner(self) -> H {
+        self.0
+    }
+}
+
+impl<H: Hasher> Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Extension trait to tee a `std::io::Read` while normalizing it.
+pub trait TeeExt {
+    /// Normalize the reader, writing the original bytes to `sink` as they are read.
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use eolify::{TeeExt, LF};
+    ///
+    /// let mut reader = b"a\r\nb\r\n".as_ref().normalize_newlines_tee(LF, Vec::new());
+    /// let mut normalized = Vec::new();
+    /// reader.read_to_end(&mut normalized).unwrap();
+    ///
+    /// let (_, original) = reader.into_inner().into_inner();
+    /// assert_eq!(normalized, b"a\nb\n");
+    /// assert_eq!(original, b"a\r\nb\r\n");
+    /// ```
+    fn normalize_newlines_tee<N: NormalizeChunk, W: Write>(
+        self,
+        _: N,
+        sink: W,
+    ) -> Reader<TeeReader<Self, W>, N>
+    where
+        Self: Sized;
+
+    /// Pass the original bytes through unchanged, writing the normalized bytes to `sink`.
+    ///
+    /// The `Writer` wrapping `sink` must be finished once reading is done, to write out any
+    /// data it still buffers.
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use eolify::{TeeExt, CRLF};
+    ///
+    /// let mut reader = b"a\nb\n".as_ref().tee_normalized(CRLF, Vec::new());
+    /// let mut original = Vec::new();
+    /// reader.read_to_end(&mut original).unwrap();
+    ///
+    /// let normalized = reader.into_inner().1.finish().unwrap();
+    /// assert_eq!(original, b"a\nb\n");
+    /// assert_eq!(normalized, b"a\r\nb\r\n");
+    /// ```
+    fn tee_normalized<N: NormalizeChunk, W: Write>(
+        self,
+        _: N,
+        sink: W,
+    ) -> TeeReader<Self, Writer<W, N>>
+    where
+        Self: Sized;
+}
+
+impl<R: Read> TeeExt for R {
+    fn normalize_newlines_tee<N: NormalizeChunk, W: Write>(
+        self,
+        _: N,
+        sink: W,
+    ) -> Reader<TeeReader<Self, W>, N>
+    where
+        Self: Sized,
+    {
+        N::wrap_reader(TeeReader::new(self, sink))
+    }
+
+    fn tee_normalized<N: NormalizeChunk, W: Write>(
+        self,
+        _: N,
+        sink: W,
+    ) -> TeeReader<Self, Writer<W, N>>
+    where
+        Self: Sized,
+    {
+        TeeReader::new(self, N::wrap_writer(sink))
+    }
+}
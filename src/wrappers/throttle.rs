@@ -0,0 +1,32 @@
+//! The `throttle` module provides the rate limiting shared by the wrappers.
+
+use std::time::{Duration, Instant};
+
+/// Tracks the bytes passed through a wrapper and computes how long to pause to keep the
+/// average rate at or below `bytes_per_second`.
+#[derive(Debug, Clone)]
+pub(crate) struct Throttle {
+    bytes_per_second: u64,
+    start: Option<Instant>,
+    bytes: u64,
+}
+
+impl Throttle {
+    /// Create a throttle for the given rate. A rate of zero is treated as one byte per second.
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            start: None,
+            bytes: 0,
+        }
+    }
+
+    /// Record that `bytes` passed through and return how long to wait before continuing.
+    pub(crate) fn delay(&mut self, bytes: u64) -> Duration {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        self.bytes = self.bytes.saturating_add(bytes);
+        let nanos = u128::from(self.bytes) * 1_000_000_000 / u128::from(self.bytes_per_second);
+        let expected = Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX));
+        expected.saturating_sub(start.elapsed())
+    }
+}
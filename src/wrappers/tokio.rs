@@ -7,11 +7,19 @@ use std::{
 };
 
 use pin_project_lite::pin_project;
-use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::{
+    io::{AsyncRead, AsyncSeek, AsyncWrite},
+    time::Sleep,
+};
 
 use crate::{
-    wrappers::async_core::{AsyncReadCompat, AsyncWriteCompat, ReadBuffer, WriteBuffer},
-    NormalizeChunk,
+    wrappers::{
+        async_core::{AsyncReadCompat, AsyncWriteCompat, ReadBuffer, WriteBuffer},
+        eol::{dispatch, EolDispatch, EolFormat},
+        throttle::Throttle,
+        FlushPolicy, DEFAULT_BUFFER_SIZE,
+    },
+    NormalizeChunk, CR, CRLF, LF,
 };
 
 pin_project! {
@@ -20,6 +28,8 @@ pin_project! {
         #[pin]
         reader: R,
         buf: ReadBuffer<N>,
+        throttle: Option<Throttle>,
+        sleep: Option<Pin<Box<Sleep>>>,
     }
 }
 
@@ -28,12 +38,44 @@ impl<R, N: NormalizeChunk> AsyncReader<R, N> {
         Self {
             reader,
             buf: ReadBuffer::new(buf_size),
+            throttle: None,
+            sleep: None,
         }
     }
 
+    /// Limit the rate at which data is read from the inner reader to `bytes_per_second`, by
+    /// pausing with `tokio::time::sleep` as needed.
+    #[must_use]
+    pub fn with_throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(Throttle::new(bytes_per_second));
+        self
+    }
+
     pub fn into_inner(self) -> R {
         self.reader
     }
+
+    /// Returns the number of bytes read from the inner reader so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.buf.bytes_consumed()
+    }
+
+    /// Returns the number of normalized bytes returned to the caller so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.buf.bytes_produced()
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.buffer_capacity()
+    }
+
+    /// Returns `true` if no normalized data is buffered, i.e. all output for the data read
+    /// from the inner reader so far was returned to the caller.
+    pub(crate) fn is_drained(&self) -> bool {
+        self.buf.is_drained()
+    }
 }
 
 struct TokioReader<R: AsyncRead>(R);
@@ -61,10 +103,21 @@ impl<R: AsyncRead, N: NormalizeChunk> AsyncRead for AsyncReader<R, N> {
         buf: &mut tokio::io::ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
         let this = self.project();
+        if poll_throttle(this.sleep, cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let consumed_before = this.buf.bytes_consumed();
         let reader = pin!(TokioReader(this.reader));
-        match this.buf.poll_read(cx, reader, buf.initialize_unfilled()) {
+        // SAFETY: `poll_read_uninit` never de-initializes memory, it only writes to it.
+        let unfilled = unsafe { buf.unfilled_mut() };
+        match this.buf.poll_read_uninit(cx, reader, unfilled) {
             Poll::Ready(Ok(n)) => {
+                // SAFETY: `poll_read_uninit` initialized the first `n` bytes of the unfilled part.
+                unsafe { buf.assume_init(n) };
                 buf.advance(n);
+                let consumed = this.buf.bytes_consumed() - consumed_before;
+                schedule_throttle(this.throttle, this.sleep, consumed);
                 Poll::Ready(Ok(()))
             }
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
@@ -73,12 +126,31 @@ impl<R: AsyncRead, N: NormalizeChunk> AsyncRead for AsyncReader<R, N> {
     }
 }
 
+/// Seeks the inner reader. Positions refer to the inner, un-normalized stream, and a
+/// successful seek discards any buffered output and the normalization state, so reading
+/// resumes as if the stream started at the new position. Relative seeks are relative to the
+/// position of the inner reader, which may be ahead of the data returned so far.
+impl<R: AsyncSeek, N: NormalizeChunk> AsyncSeek for AsyncReader<R, N> {
+    fn start_seek(self: Pin<&mut Self>, position: std::io::SeekFrom) -> std::io::Result<()> {
+        let this = self.project();
+        this.reader.start_seek(position)?;
+        this.buf.reset();
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        self.project().reader.poll_complete(cx)
+    }
+}
+
 pin_project! {
     /// An `tokio::AsyncWrite` wrapper and implementation that normalizes newlines on-the-fly.
     pub struct AsyncWriter<W, N: NormalizeChunk> {
         #[pin]
         writer: W,
         buf: WriteBuffer<N>,
+        throttle: Option<Throttle>,
+        sleep: Option<Pin<Box<Sleep>>>,
     }
 }
 
@@ -87,8 +159,46 @@ impl<W, N: NormalizeChunk> AsyncWriter<W, N> {
         Self {
             writer,
             buf: WriteBuffer::new(buf_size),
+            throttle: None,
+            sleep: None,
         }
     }
+
+    /// Limit the rate at which data is accepted to `bytes_per_second`, by pausing with
+    /// `tokio::time::sleep` as needed.
+    #[must_use]
+    pub fn with_throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(Throttle::new(bytes_per_second));
+        self
+    }
+
+    /// Set the `FlushPolicy` that controls when buffered data is passed on to the inner writer.
+    #[must_use]
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.buf.set_flush_policy(flush_policy);
+        self
+    }
+
+    /// Returns the number of bytes accepted from the caller so far.
+    pub fn bytes_consumed(&self) -> u64 {
+        self.buf.bytes_consumed()
+    }
+
+    /// Returns the number of normalized bytes written to the inner writer so far.
+    pub fn bytes_produced(&self) -> u64 {
+        self.buf.bytes_produced()
+    }
+
+    /// Returns the number of bytes allocated for the internal buffers, which bounds the memory
+    /// used for normalization regardless of the input.
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.buffer_capacity()
+    }
+
+    /// Returns `true` once the writer was shut down. Shutting down again is a no-op.
+    pub fn is_finished(&self) -> bool {
+        self.buf.is_finished()
+    }
 }
 
 impl<W: AsyncWrite + Unpin, N: NormalizeChunk> AsyncWriter<W, N> {
@@ -98,8 +208,81 @@ impl<W: AsyncWrite + Unpin, N: NormalizeChunk> AsyncWriter<W, N> {
             buf: self.buf,
         }
     }
+
+    /// Write `buf` as the final data of the stream and finish, like `write_all` followed by
+    /// [`finish`](Self::finish).
+    ///
+    /// The last part of `buf` is normalized as the end of the stream, instead of being followed
+    /// by a separate empty final chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or shutting down the inner writer.
+    pub async fn finish_with(mut self, mut buf: &[u8]) -> std::io::Result<W> {
+        while !buf.is_empty() {
+            match std::future::poll_fn(|cx| Pin::new(&mut self).poll_write_chunks(cx, buf, true))
+                .await
+            {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(n) => buf = &buf[n..],
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.finish().await
+    }
+
+    /// Like [`finish`](Self::finish), but gives up once `timeout` has elapsed.
+    ///
+    /// On timeout the inner writer is returned together with the normalized bytes that could
+    /// not be written to it in time, so the caller can decide what to do with a slow peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(FinishTimeout)` if the timeout elapsed, or `Ok(Err(_))` if writing to or
+    /// flushing the inner writer failed.
+    pub async fn finish_with_timeout(
+        self,
+        timeout: std::time::Duration,
+    ) -> Result<std::io::Result<W>, FinishTimeout<W>> {
+        let mut finisher = Finisher {
+            writer: Some(self.writer),
+            buf: self.buf,
+        };
+        match tokio::time::timeout(timeout, &mut finisher).await {
+            Ok(result) => Ok(result),
+            Err(_) => Err(FinishTimeout {
+                writer: finisher
+                    .writer
+                    .take()
+                    .expect("writer is only taken on completion"),
+                unflushed: finisher.buf.take_unflushed(),
+            }),
+        }
+    }
+}
+
+/// Returned by [`AsyncWriter::finish_with_timeout`] when finishing took too long.
+#[derive(Debug)]
+pub struct FinishTimeout<W> {
+    /// The inner writer.
+    pub writer: W,
+    /// Normalized bytes that were not written to `writer` yet.
+    pub unflushed: Vec<u8>,
+}
+
+impl<W> std::fmt::Display for FinishTimeout<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "finishing timed out with {} bytes unflushed",
+            self.unflushed.len()
+        )
+    }
 }
 
+impl<W: std::fmt::Debug> std::error::Error for FinishTimeout<W> {}
+
 pin_project! {
 struct Finisher<W, N: NormalizeChunk> {
     #[pin]
@@ -115,7 +298,7 @@ impl<W: AsyncWrite + Unpin, N: NormalizeChunk> Future for Finisher<W, N> {
         let mut this = self.project();
 
         let Some(writer) = this.writer.as_mut().get_mut() else {
-            panic!("polled after completion");
+            return Poll::Ready(Err(std::io::Error::other("finish polled after completion")));
         };
 
         let writer = pin!(TokioWriter(writer));
@@ -152,15 +335,39 @@ impl<W: AsyncWrite + Unpin> AsyncWriteCompat for TokioWriter<W> {
     }
 }
 
+impl<W: AsyncWrite, N: NormalizeChunk> AsyncWriter<W, N> {
+    /// Accept data from `buf`, leaving its last bytes for `finish` if `is_last` is set.
+    fn poll_write_chunks(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        is_last: bool,
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.project();
+        if poll_throttle(this.sleep, cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        let writer = pin!(TokioWriter(this.writer));
+        let result = if is_last {
+            this.buf.poll_write_last(cx, writer, buf)
+        } else {
+            this.buf.poll_write(cx, writer, buf)
+        };
+        if let Poll::Ready(Ok(n)) = result {
+            schedule_throttle(this.throttle, this.sleep, n as u64);
+        }
+        result
+    }
+}
+
 impl<W: AsyncWrite, N: NormalizeChunk> AsyncWrite for AsyncWriter<W, N> {
     fn poll_write(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<std::result::Result<usize, std::io::Error>> {
-        let this = self.project();
-        let writer = pin!(TokioWriter(this.writer));
-        this.buf.poll_write(cx, writer, buf)
+        self.poll_write_chunks(cx, buf, false)
     }
 
     fn poll_flush(
@@ -182,6 +389,101 @@ impl<W: AsyncWrite, N: NormalizeChunk> AsyncWrite for AsyncWriter<W, N> {
     }
 }
 
+/// An `tokio::AsyncRead` wrapper normalizing to the line ending chosen by an
+/// [`EolFormat`].
+pub type EolAsyncReader<R> =
+    EolDispatch<AsyncReader<R, LF>, AsyncReader<R, CRLF>, AsyncReader<R, CR>>;
+
+/// An `tokio::AsyncWrite` wrapper normalizing to the line ending chosen by an
+/// [`EolFormat`].
+pub type EolAsyncWriter<W> =
+    EolDispatch<AsyncWriter<W, LF>, AsyncWriter<W, CRLF>, AsyncWriter<W, CR>>;
+
+impl<W: AsyncWrite + Unpin> EolAsyncWriter<W> {
+    /// Finish normalization, flush and shut down the inner writer and return the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error produced by writing to or shutting down the inner writer.
+    pub async fn finish(self) -> std::io::Result<W> {
+        dispatch!(self, writer => writer.finish().await)
+    }
+}
+
+fn wrap_eol_reader<R>(format: EolFormat, reader: R, buf_size: usize) -> EolAsyncReader<R> {
+    match format {
+        EolFormat::Lf => EolDispatch::Lf(AsyncReader::new(reader, buf_size)),
+        EolFormat::CrLf => EolDispatch::CrLf(AsyncReader::new(reader, buf_size)),
+        EolFormat::Cr => EolDispatch::Cr(AsyncReader::new(reader, buf_size)),
+    }
+}
+
+fn wrap_eol_writer<W>(format: EolFormat, writer: W, buf_size: usize) -> EolAsyncWriter<W> {
+    match format {
+        EolFormat::Lf => EolDispatch::Lf(AsyncWriter::new(writer, buf_size)),
+        EolFormat::CrLf => EolDispatch::CrLf(AsyncWriter::new(writer, buf_size)),
+        EolFormat::Cr => EolDispatch::Cr(AsyncWriter::new(writer, buf_size)),
+    }
+}
+impl<L: AsyncRead, C: AsyncRead, R: AsyncRead> AsyncRead for EolDispatch<L, C, R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_read(cx, buf))
+    }
+}
+
+impl<L: AsyncWrite, C: AsyncWrite, R: AsyncWrite> AsyncWrite for EolDispatch<L, C, R> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::result::Result<usize, std::io::Error>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_write(cx, buf))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<std::result::Result<(), std::io::Error>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_flush(cx))
+    }
+
+    fn poll_shutdown(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<std::result::Result<(), std::io::Error>> {
+        dispatch!(self.as_pin_mut(), inner => inner.poll_shutdown(cx))
+    }
+}
+
+/// Wait for a pause scheduled by `schedule_throttle` to elapse.
+fn poll_throttle(sleep: &mut Option<Pin<Box<Sleep>>>, cx: &mut Context<'_>) -> Poll<()> {
+    if let Some(pause) = sleep {
+        if pause.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        *sleep = None;
+    }
+    Poll::Ready(())
+}
+
+/// Record that `bytes` passed through and schedule a pause if the throttle requires one.
+fn schedule_throttle(
+    throttle: &mut Option<Throttle>,
+    sleep: &mut Option<Pin<Box<Sleep>>>,
+    bytes: u64,
+) {
+    if let Some(throttle) = throttle {
+        let delay = throttle.delay(bytes);
+        if !delay.is_zero() {
+            *sleep = Some(Box::pin(tokio::time::sleep(delay)));
+        }
+    }
+}
+
 /// Extension trait to provide convenient methods on `Normalize` for `tokio::AsyncRead`
 /// and `tokio::AsyncWrite`.
 ///
@@ -192,10 +494,13 @@ where
 {
     /// Wrap a reader with a newline-normalizing `AsyncReader`.
     fn wrap_async_reader<R: AsyncRead>(reader: R) -> AsyncReader<R, Self> {
-        Self::wrap_async_reader_with_buffer_size(reader, 8192)
+        Self::wrap_async_reader_with_buffer_size(reader, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a reader with a newline-normalizing `AsyncReader` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_async_reader_with_buffer_size<R: AsyncRead>(
         reader: R,
         buf_size: usize,
@@ -203,14 +508,66 @@ where
 
     /// Wrap a writer with a newline-normalizing `AsyncWriter`.
     fn wrap_async_writer<W: AsyncWrite>(writer: W) -> AsyncWriter<W, Self> {
-        Self::wrap_async_writer_with_buffer_size(writer, 8192)
+        Self::wrap_async_writer_with_buffer_size(writer, DEFAULT_BUFFER_SIZE)
     }
 
     /// Wrap a writer with a newline-normalizing `AsyncWriter` and specify the internal buffer size.
+    ///
+    /// The buffer size is clamped to at least one byte and at most a platform dependent
+    /// maximum (1 GiB on 64-bit targets, 16 MiB otherwise).
     fn wrap_async_writer_with_buffer_size<W: AsyncWrite>(
         writer: W,
         buf_size: usize,
     ) -> AsyncWriter<W, Self>;
+
+    /// Read everything from `reader` and return the normalized bytes.
+    ///
+    /// The returned future fails with any error produced by reading from `reader`.
+    fn async_read_to_vec<R: AsyncRead + Unpin>(
+        reader: R,
+    ) -> impl Future<Output = std::io::Result<Vec<u8>>> {
+        async move {
+            let mut out = Vec::new();
+            tokio::io::AsyncReadExt::read_to_end(&mut Self::wrap_async_reader(reader), &mut out)
+                .await?;
+            Ok(out)
+        }
+    }
+
+    /// Read everything from `reader` and return the normalized text.
+    ///
+    /// The returned future fails with any error produced by reading from `reader`, or an error
+    /// of kind `InvalidData` if the normalized output is not valid UTF-8.
+    fn async_read_to_string<R: AsyncRead + Unpin>(
+        reader: R,
+    ) -> impl Future<Output = std::io::Result<String>> {
+        async move {
+            let mut out = String::new();
+            tokio::io::AsyncReadExt::read_to_string(&mut Self::wrap_async_reader(reader), &mut out)
+                .await?;
+            Ok(out)
+        }
+    }
+
+    /// Wrap `writer`, pass the normalizing `AsyncWriter` to `f` and finish it once the future
+    /// returned by `f` succeeds.
+    ///
+    /// The returned future resolves to the inner writer together with the value produced by
+    /// `f`. If `f` fails, data still buffered in the `AsyncWriter` is discarded and the error is
+    /// returned.
+    fn with_async_writer<W, T, F>(writer: W, f: F) -> impl Future<Output = std::io::Result<(W, T)>>
+    where
+        W: AsyncWrite + Unpin,
+        F: for<'a> FnOnce(
+            &'a mut AsyncWriter<W, Self>,
+        ) -> Pin<Box<dyn Future<Output = std::io::Result<T>> + 'a>>,
+    {
+        async move {
+            let mut normalizer = Self::wrap_async_writer(writer);
+            let value = f(&mut normalizer).await?;
+            Ok((normalizer.finish().await?, value))
+        }
+    }
 }
 
 impl<N: NormalizeChunk> TokioExt for N {
@@ -237,6 +594,12 @@ pub trait TokioAsyncReadExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> AsyncReader<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the reader with an `EolAsyncReader` normalizing to the line ending chosen at
+    /// runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncReader<Self>
+    where
+        Self: Sized;
 }
 
 impl<R: AsyncRead> TokioAsyncReadExt for R {
@@ -246,6 +609,13 @@ impl<R: AsyncRead> TokioAsyncReadExt for R {
     {
         N::wrap_async_reader(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncReader<Self>
+    where
+        Self: Sized,
+    {
+        wrap_eol_reader(format, self, DEFAULT_BUFFER_SIZE)
+    }
 }
 
 /// Extension trait to provide convenient methods on `tokio::AsyncWrite`.
@@ -256,6 +626,12 @@ pub trait TokioAsyncWriteExt {
     fn normalize_newlines<N: NormalizeChunk>(self, _: N) -> AsyncWriter<Self, N>
     where
         Self: Sized;
+
+    /// Wrap the writer with an `EolAsyncWriter` normalizing to the line ending chosen at
+    /// runtime.
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncWriter<Self>
+    where
+        Self: Sized;
 }
 
 impl<W: AsyncWrite> TokioAsyncWriteExt for W {
@@ -265,4 +641,51 @@ impl<W: AsyncWrite> TokioAsyncWriteExt for W {
     {
         N::wrap_async_writer(self)
     }
+
+    fn normalize_newlines_to(self, format: EolFormat) -> EolAsyncWriter<Self>
+    where
+        Self: Sized,
+    {
+        wrap_eol_writer(format, self, DEFAULT_BUFFER_SIZE)
+    }
+}
+
+/// Extension trait to provide convenient methods on streams implementing both
+/// `tokio::AsyncRead` and `tokio::AsyncWrite`.
+///
+/// This trait requires the `tokio` feature to be enabled.
+pub trait TokioAsyncStreamExt {
+    /// Split the stream with `tokio::io::split` and wrap the halves with a newline-normalizing
+    /// `AsyncReader` and `AsyncWriter`, each using its own format.
+    #[allow(clippy::type_complexity)]
+    fn split_normalized<R: NormalizeChunk, W: NormalizeChunk>(
+        self,
+        _: R,
+        _: W,
+    ) -> (
+        AsyncReader<tokio::io::ReadHalf<Self>, R>,
+        AsyncWriter<tokio::io::WriteHalf<Self>, W>,
+    )
+    where
+        Self: Sized;
+}
+
+impl<S: AsyncRead + AsyncWrite> TokioAsyncStreamExt for S {
+    fn split_normalized<R: NormalizeChunk, W: NormalizeChunk>(
+        self,
+        _: R,
+        _: W,
+    ) -> (
+        AsyncReader<tokio::io::ReadHalf<Self>, R>,
+        AsyncWriter<tokio::io::WriteHalf<Self>, W>,
+    )
+    where
+        Self: Sized,
+    {
+        let (read_half, write_half) = tokio::io::split(self);
+        (
+            R::wrap_async_reader(read_half),
+            W::wrap_async_writer(write_half),
+        )
+    }
 }
@@ -0,0 +1,55 @@
+use std::panic;
+
+use eolify::{assert_eol_eq, assert_eol_normalized, CRLF, LF};
+
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let err = panic::catch_unwind(f).unwrap_err();
+    err.downcast_ref::<String>().cloned().unwrap_or_default()
+}
+
+#[test]
+fn equal_inputs_pass() {
+    assert_eol_eq!("a\r\nb", b"a\r\nb");
+    assert_eol_eq!(Vec::from("x\n"), "x\n");
+}
+
+#[test]
+fn differing_line_endings_are_shown() {
+    let msg = panic_message(|| assert_eol_eq!("same\none\r\ntwo\n", "same\none\ntwo\n"));
+    assert!(msg.contains("line 2:"), "{msg}");
+    assert!(msg.contains(r#" left: "one\r\n""#), "{msg}");
+    assert!(msg.contains(r#"right: "one\n""#), "{msg}");
+    assert!(!msg.contains("line 1:"), "{msg}");
+}
+
+#[test]
+fn missing_lines_are_shown() {
+    let msg = panic_message(|| assert_eol_eq!("a\nb\n", "a\n"));
+    assert!(msg.contains("line 2:"), "{msg}");
+    assert!(msg.contains("right: <missing>"), "{msg}");
+}
+
+#[test]
+fn long_reports_are_truncated() {
+    let left = "x\r\n".repeat(15);
+    let right = "x\n".repeat(15);
+    let msg = panic_message(move || assert_eol_eq!(left, right));
+    assert!(msg.contains("line 10:"), "{msg}");
+    assert!(!msg.contains("line 11:"), "{msg}");
+    assert!(msg.contains("and 5 more differing lines"), "{msg}");
+}
+
+#[test]
+fn normalized_file() {
+    let path = std::env::temp_dir().join(format!("eolify-assert-{}.txt", std::process::id()));
+    std::fs::write(&path, b"one\r\ntwo\r\n").unwrap();
+    assert_eol_normalized!(path, CRLF);
+
+    let result = panic::catch_unwind(|| assert_eol_normalized!(&path, LF));
+    std::fs::remove_file(&path).unwrap();
+    let err = result.unwrap_err();
+    let msg = err.downcast_ref::<String>().cloned().unwrap_or_default();
+    assert!(msg.contains("is not normalized"), "{msg}");
+    assert!(msg.contains(r#"  actual: "one\r\n""#), "{msg}");
+    assert!(msg.contains(r#"expected: "one\n""#), "{msg}");
+}
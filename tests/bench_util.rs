@@ -0,0 +1,35 @@
+#![cfg(feature = "bench-util")]
+
+use eolify::{
+    bench::{measure_chunked, Pattern},
+    CRLF, LF,
+};
+
+#[test]
+fn patterns_have_requested_size() {
+    for pattern in Pattern::ALL {
+        assert_eq!(pattern.generate(1000).len(), 1000, "{pattern:?}");
+    }
+}
+
+#[test]
+fn patterns_are_deterministic() {
+    assert_eq!(Pattern::Random.generate(64), Pattern::Random.generate(64));
+    assert_eq!(Pattern::Crlf.generate(4), b"\r\n\r\n");
+    assert_eq!(Pattern::Mixed.generate(8), b"\r\nabbbb\r");
+}
+
+#[test]
+fn measure_counts_all_iterations() {
+    let input = Pattern::Mixed.generate(10_000);
+    let crlf = measure_chunked::<CRLF>(&input, 1024, 3);
+    assert_eq!(crlf.bytes(), 30_000);
+    let lf = measure_chunked::<LF>(&input, 7, 1);
+    assert_eq!(lf.bytes(), 10_000);
+    assert!(lf.bytes_per_second() > 0.0);
+}
+
+#[test]
+fn measure_handles_empty_input() {
+    assert_eq!(measure_chunked::<LF>(b"", 16, 2).bytes(), 0);
+}
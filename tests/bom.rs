@@ -0,0 +1,139 @@
+use std::io::Read;
+
+use eolify::{AddBom, Bom, IoExt, Normalize, NormalizeChunk, PreservesUtf8, StripBom, CRLF, LF};
+use proptest::{
+    arbitrary::any, collection::vec, prop_assert_eq, prop_oneof, proptest, strategy::Just,
+    strategy::Strategy,
+};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[test]
+fn detects_boms() {
+    assert_eq!(Bom::detect(b"\xFE\xFF\0a"), Some(Bom::Utf16Be));
+    assert_eq!(Bom::detect(b"\xEF\xBB"), None);
+    assert_eq!(Bom::detect(b""), None);
+    assert_eq!(Bom::Utf16Le.as_bytes(), b"\xFF\xFE");
+}
+
+#[test]
+fn strips_utf8_and_utf16_boms() {
+    assert_eq!(StripBom::<LF>::normalize(b"\xEF\xBB\xBFa\r\n"), b"a\n");
+    assert_eq!(StripBom::<LF>::normalize(b"\xFF\xFEa"), b"a");
+    assert_eq!(StripBom::<LF>::normalize(b"\xFE\xFFa"), b"a");
+    // Only at the start of the stream.
+    assert_eq!(
+        StripBom::<LF>::normalize(b"a\xEF\xBB\xBF"),
+        b"a\xEF\xBB\xBF"
+    );
+    assert_eq!(StripBom::<LF>::normalize_utf8("\u{feff}x\r"), "x\n");
+}
+
+#[test]
+fn utf16_content_is_normalized_bytewise() {
+    // Only the BOM is recognized; the UTF-16 line ending is rewritten as single bytes.
+    assert_eq!(
+        StripBom::<CRLF>::normalize(b"\xFF\xFEa\0\n\0"),
+        b"a\0\r\n\0"
+    );
+    assert_eq!(
+        AddBom::<CRLF>::normalize(b"\xFE\xFF\0a\0\n"),
+        b"\xFE\xFF\0a\0\r\n"
+    );
+}
+
+#[test]
+fn partial_bom_is_content() {
+    assert_eq!(StripBom::<LF>::normalize(b"\xEF"), b"\xEF");
+    assert_eq!(StripBom::<LF>::normalize(b"\xEF\xBB\r\n"), b"\xEF\xBB\n");
+    assert_eq!(AddBom::<LF>::normalize(b"\xFF"), b"\xEF\xBB\xBF\xFF");
+}
+
+#[test]
+fn adds_bom_unless_present() {
+    assert_eq!(AddBom::<CRLF>::normalize(b"a\n"), b"\xEF\xBB\xBFa\r\n");
+    assert_eq!(
+        AddBom::<CRLF>::normalize(b"\xEF\xBB\xBFa"),
+        b"\xEF\xBB\xBFa"
+    );
+    assert_eq!(AddBom::<CRLF>::normalize(b"\xFF\xFEa"), b"\xFF\xFEa");
+    assert_eq!(AddBom::<CRLF>::normalize(b""), b"");
+    assert_eq!(AddBom::<CRLF>::normalize_utf8("a"), "\u{feff}a");
+}
+
+#[test]
+fn bom_split_across_single_byte_reads() {
+    let mut out = Vec::new();
+    StripBom::<CRLF>::wrap_reader_with_buffer_size(b"\xEF\xBB\xBFa\nb".as_ref(), 1)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"a\r\nb");
+
+    let mut out = Vec::new();
+    AddBom::<CRLF>::wrap_reader_with_buffer_size(b"\xEF\xBBa\n".as_ref(), 1)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"\xEF\xBB\xBF\xEF\xBBa\r\n");
+}
+
+#[test]
+fn empty_chunks_before_content() {
+    let mut out = Vec::new();
+    let status = AddBom::<LF>::normalize_chunk_into(b"", &mut out, None, false).unwrap();
+    let status = AddBom::<LF>::normalize_chunk_into(b"", &mut out, status.state(), false).unwrap();
+    AddBom::<LF>::normalize_chunk_into(b"x", &mut out, status.state(), true).unwrap();
+    assert_eq!(out, b"\xEF\xBB\xBFx");
+}
+
+fn input() -> impl Strategy<Value = Vec<u8>> {
+    (
+        prop_oneof![
+            Just(&b""[..]),
+            Just(UTF8_BOM),
+            Just(&UTF8_BOM[..2]),
+            Just(&b"\xFF\xFE"[..]),
+            Just(&b"\xFE"[..]),
+        ],
+        vec(any::<u8>(), 0..16),
+    )
+        .prop_map(|(start, rest)| [start, &rest].concat())
+}
+
+proptest! {
+    #[test]
+    fn chunk_split_matches_whole(data in input(), split in 0usize..32) {
+        let split = split.min(data.len());
+        let (a, b) = data.split_at(split);
+
+        for add in [false, true] {
+            let mut out = Vec::new();
+            let whole = if add {
+                let status = AddBom::<CRLF>::normalize_chunk_into(a, &mut out, None, false).unwrap();
+                AddBom::<CRLF>::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+                AddBom::<CRLF>::normalize(&data)
+            } else {
+                let status = StripBom::<CRLF>::normalize_chunk_into(a, &mut out, None, false).unwrap();
+                StripBom::<CRLF>::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+                StripBom::<CRLF>::normalize(&data)
+            };
+            prop_assert_eq!(out, whole);
+        }
+    }
+
+    #[test]
+    fn matches_normalizing_without_bom(data in input()) {
+        let (bom, rest) = match Bom::detect(&data) {
+            Some(bom) => (Some(bom), &data[bom.as_bytes().len()..]),
+            None => (None, &data[..]),
+        };
+        let normalized = LF::normalize(rest);
+        prop_assert_eq!(StripBom::<LF>::normalize(&data), normalized.clone());
+
+        let added = match bom {
+            Some(bom) => [bom.as_bytes(), &normalized].concat(),
+            None if data.is_empty() => Vec::new(),
+            None => [UTF8_BOM, &normalized].concat(),
+        };
+        prop_assert_eq!(AddBom::<LF>::normalize(&data), added);
+    }
+}
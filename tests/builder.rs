@@ -0,0 +1,316 @@
+use std::io::{Read, Write};
+
+use eolify::{BufferSizeHint, CopyOutcome, Normalizer, CRLF, LF};
+
+#[test]
+fn reader_uses_target() {
+    let normalizer = Normalizer::builder().target(LF).buffer_size(4);
+    let mut out = Vec::new();
+    normalizer
+        .reader(b"foo\r\nbar\rbaz".as_ref())
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"foo\nbar\nbaz".to_vec());
+}
+
+#[test]
+fn writer_uses_target() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(2);
+    let mut writer = normalizer.writer(Vec::new());
+    writer.write_all(b"foo\r").unwrap();
+    writer.write_all(b"\nbar\n").unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"foo\r\nbar\r\n".to_vec());
+}
+
+#[test]
+fn copy_normalizes() {
+    let normalizer = Normalizer::builder().target(CRLF);
+    let mut input = b"one\ntwo\rthree".as_ref();
+    let mut out = Vec::new();
+    let n = normalizer.copy(&mut input, &mut out).unwrap();
+    assert_eq!(out, b"one\r\ntwo\r\nthree".to_vec());
+    assert_eq!(n, out.len() as u64);
+}
+
+#[test]
+fn builder_is_reusable() {
+    let normalizer = Normalizer::builder().target(LF);
+    let copy = normalizer.clone();
+    assert_eq!(normalizer, copy);
+    let normalizer = normalizer.target(CRLF);
+
+    let mut out = Vec::new();
+    copy.copy(&mut b"a\r\nb".as_ref(), &mut out).unwrap();
+    assert_eq!(out, b"a\nb".to_vec());
+
+    out.clear();
+    normalizer.copy(&mut b"a\nb".as_ref(), &mut out).unwrap();
+    assert_eq!(out, b"a\r\nb".to_vec());
+}
+
+#[test]
+fn strip_bom_wraps_target() {
+    let normalizer = Normalizer::builder()
+        .target(CRLF)
+        .strip_bom()
+        .buffer_size(1);
+    let mut out = Vec::new();
+    normalizer
+        .copy(&mut b"\xEF\xBB\xBFone\ntwo".as_ref(), &mut out)
+        .unwrap();
+    assert_eq!(out, b"one\r\ntwo".to_vec());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_copy_normalizes() {
+    let normalizer = Normalizer::builder().target(LF).buffer_size(3);
+    let mut input = b"one\r\ntwo\rthree".as_ref();
+    let mut out = Vec::new();
+    normalizer.tokio_copy(&mut input, &mut out).await.unwrap();
+    assert_eq!(out, b"one\ntwo\nthree".to_vec());
+}
+
+#[test]
+fn copy_with_progress_reports_at_intervals() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(4);
+    let input = "ab\n".repeat(100);
+    let mut out = Vec::new();
+    let mut reports = Vec::new();
+    let total = normalizer
+        .copy_with_progress(&mut input.as_bytes(), &mut out, 50, |n| reports.push(n))
+        .unwrap();
+    assert_eq!(out, "ab\r\n".repeat(100).as_bytes());
+    assert_eq!(total, 400);
+    assert!(reports.len() >= 7, "{reports:?}");
+    assert!(reports.windows(2).all(|w| w[1] - w[0] >= 50), "{reports:?}");
+    assert!(*reports.last().unwrap() <= total);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_copy_yielding_normalizes() {
+    let normalizer = Normalizer::builder().target(LF).buffer_size(3);
+    let input = "one\r\ntwo\r".repeat(50);
+    let mut out = Vec::new();
+    let total = normalizer
+        .tokio_copy_yielding(&mut input.as_bytes(), &mut out, 16)
+        .await
+        .unwrap();
+    assert_eq!(out, "one\ntwo\n".repeat(50).as_bytes());
+    assert_eq!(total, 400);
+}
+
+#[test]
+fn tuned_reader_uses_hint_of_in_memory_source() {
+    let input = b"one\r\ntwo\rthree";
+    let normalizer = Normalizer::builder().target(LF);
+    let mut out = Vec::new();
+    normalizer
+        .tuned_reader(input.as_ref())
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"one\ntwo\nthree".to_vec());
+}
+
+#[test]
+fn buffer_size_hints() {
+    assert_eq!(b"abc".as_ref().buffer_size_hint(), 3);
+    assert_eq!(b"".as_ref().buffer_size_hint(), 1);
+    let mut cursor = std::io::Cursor::new(vec![0; 10]);
+    cursor.set_position(4);
+    assert_eq!(cursor.buffer_size_hint(), 6);
+    assert_eq!(vec![0; 10].buffer_size_hint(), 8192);
+    assert_eq!(std::io::stdin().buffer_size_hint(), 8192);
+    assert_eq!(std::io::stdout().buffer_size_hint(), 8192);
+    let reader = std::io::BufReader::with_capacity(100, b"".as_ref());
+    assert_eq!(reader.buffer_size_hint(), 100);
+    let writer = std::io::BufWriter::with_capacity(200, Vec::new());
+    assert_eq!(writer.buffer_size_hint(), 200);
+}
+
+#[test]
+fn explicit_buffer_size_overrides_hint() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(1);
+    let mut writer = normalizer.tuned_writer(Vec::new());
+    writer.write_all(b"a\nb").unwrap();
+    // With a buffer size of one every byte is written through immediately.
+    assert_eq!(writer.get_ref(), b"a\r\nb");
+}
+
+/// A reader that hands out `chunk` bytes per read and sets `cancel` after the first read.
+struct CancellingReader<'a> {
+    data: &'a [u8],
+    chunk: usize,
+    cancel: &'a std::sync::atomic::AtomicBool,
+}
+
+impl Read for CancellingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        self.cancel
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+#[test]
+fn copy_cancellable_completes() {
+    let normalizer = Normalizer::builder().target(CRLF);
+    let cancel = std::sync::atomic::AtomicBool::new(false);
+    let mut out = Vec::new();
+    let outcome = normalizer
+        .copy_cancellable(&mut b"a\nb\n".as_ref(), &mut out, &cancel)
+        .unwrap();
+    assert_eq!(outcome, CopyOutcome::Complete { written: 6 });
+    assert_eq!(out, b"a\r\nb\r\n");
+}
+
+#[test]
+fn copy_cancellable_stops_with_partial_report() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(4);
+    let cancel = std::sync::atomic::AtomicBool::new(false);
+    let mut reader = CancellingReader {
+        data: b"a\nb\nc\nd\n",
+        chunk: 4,
+        cancel: &cancel,
+    };
+    let mut out = Vec::new();
+    let outcome = normalizer
+        .copy_cancellable(&mut reader, &mut out, &cancel)
+        .unwrap();
+    assert!(outcome.is_cancelled());
+    assert_eq!(
+        outcome,
+        CopyOutcome::Cancelled {
+            consumed: 4,
+            written: 6
+        }
+    );
+    assert_eq!(out, b"a\r\nb\r\n");
+}
+
+#[test]
+fn copy_cancellable_writes_all_output_of_consumed_input() {
+    // Every chunk normalizes to twice its size, more than the reader hands out at once.
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(8192);
+    let cancel = std::sync::atomic::AtomicBool::new(false);
+    let data = vec![b'\n'; 16384];
+    let mut reader = CancellingReader {
+        data: &data,
+        chunk: 8192,
+        cancel: &cancel,
+    };
+    let mut out = Vec::new();
+    let outcome = normalizer
+        .copy_cancellable(&mut reader, &mut out, &cancel)
+        .unwrap();
+    assert_eq!(
+        outcome,
+        CopyOutcome::Cancelled {
+            consumed: 8192,
+            written: 16384
+        }
+    );
+    assert_eq!(out, b"\r\n".repeat(8192));
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for CancellingReader<'_> {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let n = this.chunk.min(buf.remaining()).min(this.data.len());
+        buf.put_slice(&this.data[..n]);
+        this.data = &this.data[n..];
+        this.cancel
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_copy_cancellable_writes_all_output_of_consumed_input() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(8192);
+    let cancel = std::sync::atomic::AtomicBool::new(false);
+    let data = vec![b'\n'; 16384];
+    let mut reader = CancellingReader {
+        data: &data,
+        chunk: 8192,
+        cancel: &cancel,
+    };
+    let mut out = Vec::new();
+    let outcome = normalizer
+        .tokio_copy_cancellable(&mut reader, &mut out, &cancel)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        CopyOutcome::Cancelled {
+            consumed: 8192,
+            written: 16384
+        }
+    );
+    assert_eq!(out, b"\r\n".repeat(8192));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_copy_cancellable_honours_preset_flag() {
+    let normalizer = Normalizer::builder().target(LF);
+    let cancel = std::sync::atomic::AtomicBool::new(true);
+    let mut out = Vec::new();
+    let outcome = normalizer
+        .tokio_copy_cancellable(&mut b"a\r\n".as_ref(), &mut out, &cancel)
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome,
+        CopyOutcome::Cancelled {
+            consumed: 0,
+            written: 0
+        }
+    );
+    assert!(out.is_empty());
+}
+
+#[test]
+fn memory_limit_bounds_buffers() {
+    for limit in [4, 100, 4096, 1 << 20] {
+        let normalizer = Normalizer::builder().target(CRLF).memory_limit(limit);
+        let reader = normalizer.reader(b"".as_ref());
+        let writer = normalizer.writer(Vec::new());
+        assert!(reader.buffer_capacity() <= limit, "limit {limit}");
+        assert!(writer.buffer_capacity() <= limit, "limit {limit}");
+        // CRLF needs twice the input buffer size for its output, so the limit is used fully.
+        assert!(writer.buffer_capacity() + 3 > limit, "limit {limit}");
+    }
+
+    let normalizer = Normalizer::builder().target(LF).memory_limit(10);
+    let mut out = Vec::new();
+    normalizer
+        .copy(&mut "a\r\nb\r".repeat(100).as_bytes(), &mut out)
+        .unwrap();
+    assert_eq!(out, "a\nb\n".repeat(100).as_bytes());
+}
+
+#[test]
+#[should_panic(expected = "too small")]
+fn memory_limit_too_small() {
+    let _ = Normalizer::builder().target(CRLF).memory_limit(2);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn memory_limit_bounds_async_writer() {
+    let normalizer = Normalizer::builder().target(CRLF).memory_limit(1000);
+    assert!(normalizer.tokio_writer(Vec::new()).buffer_capacity() <= 1000);
+    assert!(normalizer.tokio_reader(b"".as_ref()).buffer_capacity() <= 1000);
+}
@@ -0,0 +1,29 @@
+use eolify::capabilities;
+
+#[test]
+fn describes_this_build() {
+    let capabilities = capabilities();
+    assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+    for name in ["cr", "crlf", "lf", "ndjson"] {
+        assert!(capabilities.formats.iter().any(|format| format == name));
+    }
+    assert_eq!(capabilities.features.tokio, cfg!(feature = "tokio"));
+    assert_eq!(capabilities.features.serde, cfg!(feature = "serde"));
+    assert!(capabilities.limits.default_buffer_size <= capabilities.limits.max_buffer_size);
+
+    let text = capabilities.to_string();
+    assert!(text.starts_with(&format!("eolify {}\n", capabilities.version)));
+    assert!(text.contains("formats: cr, crlf, lf, ndjson"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_for_diagnostics() {
+    let capabilities = capabilities();
+    let json = serde_json::to_string(&capabilities).unwrap();
+    assert!(json.contains("\"max_buffer_size\""));
+    assert_eq!(
+        serde_json::from_str::<eolify::Capabilities>(&json).unwrap(),
+        capabilities
+    );
+}
@@ -0,0 +1,123 @@
+use eolify::{
+    check::{
+        check_path, check_reader, check_slice, report_reader, report_slice, CheckOutcome,
+        FinalNewline,
+    },
+    CRLF, LF,
+};
+
+#[test]
+fn slice_outcomes() {
+    assert!(check_slice::<LF>(b"a\nb\n").is_clean());
+    assert!(matches!(
+        check_slice::<LF>(b"a\r\nb\n"),
+        CheckOutcome::WouldChange(1)
+    ));
+}
+
+#[test]
+fn reader_matches_slice() {
+    for input in [
+        b"".as_ref(),
+        b"a\r\n",
+        b"a\r",
+        b"a\n",
+        b"\r\n\r\n",
+        b"no newline",
+    ] {
+        assert_eq!(
+            check_reader::<CRLF, _>(input).exit_code(),
+            check_slice::<CRLF>(input).exit_code(),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn reader_detects_change_across_buffers() {
+    let mut input = "x\r\n".repeat(10_000).into_bytes();
+    assert!(check_reader::<CRLF, _>(input.as_slice()).is_clean());
+    input.push(b'\n');
+    assert_eq!(check_reader::<CRLF, _>(input.as_slice()).exit_code(), 1);
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let outcome = check_path::<LF>("this/file/does/not/exist");
+    assert!(matches!(&outcome, CheckOutcome::Errors(errors) if errors.len() == 1));
+    assert_eq!(outcome.exit_code(), 2);
+}
+
+#[test]
+fn merging_outcomes() {
+    let outcome: CheckOutcome = [
+        CheckOutcome::Clean,
+        CheckOutcome::WouldChange(2),
+        CheckOutcome::WouldChange(1),
+    ]
+    .into_iter()
+    .collect();
+    assert!(matches!(outcome, CheckOutcome::WouldChange(3)));
+
+    let outcome = outcome.merge(std::io::Error::other("boom").into());
+    assert_eq!(outcome.exit_code(), 2);
+    assert_eq!(CheckOutcome::Clean.exit_code(), 0);
+}
+
+#[test]
+fn final_newline_audit() {
+    for (input, expected) in [
+        (b"".as_ref(), FinalNewline::Present),
+        (b"a\n", FinalNewline::Present),
+        (b"a\r\n", FinalNewline::Present),
+        (b"a", FinalNewline::Missing),
+        (b"a\nb", FinalNewline::Missing),
+        (b"a\n\n", FinalNewline::Multiple(2)),
+        (b"a\r\n\r\n\r\n", FinalNewline::Multiple(3)),
+        (b"a\r\r\n", FinalNewline::Multiple(2)),
+        (b"\n", FinalNewline::Present),
+    ] {
+        assert_eq!(
+            report_slice::<LF>(input).final_newline,
+            expected,
+            "{input:?}"
+        );
+        assert_eq!(
+            report_reader::<LF, _>(input).unwrap().final_newline,
+            expected,
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn report_categories_are_separate() {
+    let report = report_reader::<LF, _>(b"a\r\nb\n\n".as_ref()).unwrap();
+    assert!(report.would_change);
+    assert_eq!(report.final_newline, FinalNewline::Multiple(2));
+    assert!(!report.is_clean());
+    assert_eq!(report.eol_outcome().exit_code(), 1);
+
+    let report = report_slice::<CRLF>(b"a\r\nb");
+    assert!(!report.would_change);
+    assert_eq!(report.final_newline, FinalNewline::Missing);
+
+    assert!(report_slice::<LF>(b"a\nb\n").is_clean());
+}
+
+#[test]
+fn report_reader_scans_past_first_difference() {
+    // Small reads make the trailing newlines span several chunks.
+    struct Trickle<'a>(&'a [u8]);
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(1);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+    let report = report_reader::<LF, _>(Trickle(b"a\r\nb\r\n\r\n")).unwrap();
+    assert!(report.would_change);
+    assert_eq!(report.final_newline, FinalNewline::Multiple(2));
+}
@@ -0,0 +1,84 @@
+use std::{
+    io::{Read, Write},
+    mem::MaybeUninit,
+};
+
+use eolify::{
+    Checked, IoExt, Normalize, NormalizeChunk, NormalizeChunkResult, ReadExt, Result, CRLF, LF,
+    NDJSON,
+};
+
+const INPUT: &[u8] = b"a\r\nb\rc\n\r\r\n\n{\"x\":\"\r\n\"}\r";
+
+#[test]
+fn checked_formats_match_unchecked() {
+    assert_eq!(Checked::<CRLF>::normalize(INPUT), CRLF::normalize(INPUT));
+    assert_eq!(Checked::<LF>::normalize(INPUT), LF::normalize(INPUT));
+    assert_eq!(
+        Checked::<NDJSON>::normalize(INPUT),
+        NDJSON::normalize(INPUT)
+    );
+}
+
+#[test]
+fn checked_reader_with_small_buffers() {
+    for buffer_size in 1..INPUT.len() {
+        let mut out = Vec::new();
+        Checked::<CRLF>::wrap_reader_with_buffer_size(INPUT, buffer_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, CRLF::normalize(INPUT), "buffer size {buffer_size}");
+    }
+}
+
+#[test]
+fn checked_writer() {
+    let mut writer = Checked::<LF>::wrap_writer_with_buffer_size(Vec::new(), 2);
+    writer.write_all(INPUT).unwrap();
+    assert_eq!(writer.finish().unwrap(), LF::normalize(INPUT));
+}
+
+/// A broken format that turns every `a` into `aa`.
+struct Doubling;
+
+impl NormalizeChunk for Doubling {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> usize {
+        chunk_size * 2
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        let mut pos = 0;
+        for &b in input {
+            output[pos].write(b);
+            pos += 1;
+            if b == b'a' {
+                output[pos].write(b);
+                pos += 1;
+            }
+        }
+        Ok(NormalizeChunkResult::new(pos, None))
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "not idempotent"))]
+fn non_idempotent_format_is_caught() {
+    let mut out = Vec::new();
+    b"banana"
+        .as_ref()
+        .normalize_newlines(Checked(Doubling))
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"baanaanaa");
+}
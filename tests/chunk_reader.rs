@@ -0,0 +1,66 @@
+use std::io::{self, Read};
+
+use eolify::{ChunkReader, ChunksExt, CRLF, LF};
+
+#[test]
+fn chunks_are_read_in_order() {
+    let chunks = vec![Ok(b"foo".to_vec()), Ok(Vec::new()), Ok(b"bar".to_vec())];
+    let mut out = String::new();
+    ChunkReader::new(chunks.into_iter())
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "foobar");
+}
+
+#[test]
+fn crlf_split_across_chunks() {
+    let chunks: Vec<io::Result<&[u8]>> = vec![Ok(b"one\r"), Ok(b""), Ok(b"\ntwo\r"), Ok(b"three")];
+    let mut out = String::new();
+    chunks
+        .normalize_newlines(LF)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "one\ntwo\nthree");
+}
+
+#[test]
+fn lone_lf_at_chunk_start() {
+    let chunks = vec![Ok(b"a".to_vec()), Ok(b"\nb\n".to_vec())];
+    let mut out = String::new();
+    chunks
+        .normalize_newlines(CRLF)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "a\r\nb\r\n");
+}
+
+#[test]
+fn iterator_error_is_returned() {
+    let chunks = vec![
+        Ok(b"a\r".to_vec()),
+        Err(io::Error::other("boom")),
+        Ok(b"\nb".to_vec()),
+    ];
+    let mut reader = ChunkReader::new(chunks.into_iter());
+    let mut buf = [0; 8];
+    assert_eq!(reader.read(&mut buf).unwrap(), 2);
+    assert_eq!(reader.read(&mut buf).unwrap_err().to_string(), "boom");
+    assert_eq!(reader.read(&mut buf).unwrap(), 2);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn small_reads_span_chunks() {
+    let chunks = vec![Ok(b"abc".to_vec()), Ok(b"de".to_vec())];
+    let mut reader = ChunkReader::new(chunks.into_iter());
+    let mut buf = [0; 2];
+    let mut out = Vec::new();
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(out, b"abcde");
+}
@@ -0,0 +1,163 @@
+use std::{
+    io::{Read, Write},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use eolify::{ChunkStats, EolStats, LineTerminator, Reader, WindowedStats, Writer, CRLF, LF};
+
+fn totals(records: &[ChunkStats]) -> (u64, u64, u64) {
+    records.iter().fold((0, 0, 0), |(lf, crlf, cr), r| {
+        (lf + r.lf, crlf + r.crlf, cr + r.cr)
+    })
+}
+
+#[test]
+fn reader_reports_every_chunk() {
+    let (sender, receiver) = mpsc::channel();
+    let input = b"a\r\nb\nc\rd\r\n\r";
+    let mut reader = Reader::<_, LF>::new(input.as_ref(), 4).with_stats(sender);
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    drop(reader);
+
+    let records: Vec<ChunkStats> = receiver.iter().collect();
+    assert_eq!(totals(&records), (1, 2, 2));
+    assert_eq!(records.iter().map(|r| r.len).sum::<usize>(), input.len());
+    assert_eq!(
+        records.iter().map(|r| r.output_len).sum::<usize>(),
+        out.len()
+    );
+    let mut offset = 0;
+    for record in &records {
+        assert_eq!(record.offset, offset);
+        offset += record.len as u64;
+    }
+}
+
+#[test]
+fn crlf_split_across_chunks_is_counted_once() {
+    let (sender, receiver) = mpsc::channel();
+    let mut reader = Reader::<_, LF>::new(b"ab\r\ncd".as_ref(), 3).with_stats(sender);
+    std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+    drop(reader);
+
+    let records: Vec<ChunkStats> = receiver.iter().collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].lines(), 0);
+    assert_eq!(records[1].crlf, 1);
+    assert_eq!(records[1].conversions(LineTerminator::Lf), 1);
+    assert_eq!(records[1].conversions(LineTerminator::CrLf), 0);
+}
+
+#[test]
+fn writer_reports_chunks_and_trailing_cr() {
+    let (sender, receiver) = mpsc::channel();
+    let mut writer = Writer::<_, CRLF>::new(Vec::new(), 4).with_stats(sender);
+    writer.write_all(b"one\ntwo\r\nthree\r").unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"one\r\ntwo\r\nthree\r\n");
+
+    let records: Vec<ChunkStats> = receiver.iter().collect();
+    assert_eq!(totals(&records), (1, 1, 1));
+    let conversions: u64 = records
+        .iter()
+        .map(|r| r.conversions(LineTerminator::CrLf))
+        .sum();
+    assert_eq!(conversions, 2);
+}
+
+#[test]
+fn empty_flushes_keep_a_held_back_cr() {
+    let (sender, receiver) = mpsc::channel();
+    let mut writer = Writer::<_, LF>::new(Vec::new(), 8).with_stats(sender);
+    writer.write_all(b"a\r").unwrap();
+    writer.flush().unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b"\nb\n").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"a\nb\n");
+
+    let records: Vec<ChunkStats> = receiver.iter().collect();
+    assert_eq!(totals(&records), (1, 1, 0));
+}
+
+#[test]
+fn finish_with_normalizes_the_last_chunk_as_the_end() {
+    let (sender, receiver) = mpsc::channel();
+    let mut writer = Writer::<_, CRLF>::new(Vec::new(), 4).with_stats(sender);
+    writer.write_all(b"one\n").unwrap();
+    let out = writer.finish_with(b"two\r").unwrap();
+    assert_eq!(out, b"one\r\ntwo\r\n");
+
+    let records: Vec<ChunkStats> = receiver.iter().collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!((records[1].len, records[1].output_len), (4, 5));
+    assert_eq!(records[1].cr, 1);
+}
+
+#[test]
+fn dropped_receiver_is_ignored() {
+    let (sender, receiver) = mpsc::channel();
+    drop(receiver);
+    let mut out = String::new();
+    Reader::<_, LF>::new(b"x\r\n".as_ref(), 1)
+        .with_stats(sender)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "x\n");
+}
+
+fn chunk(lf: u64, crlf: u64, cr: u64) -> ChunkStats {
+    ChunkStats {
+        len: 10,
+        lf,
+        crlf,
+        cr,
+        ..ChunkStats::default()
+    }
+}
+
+#[test]
+fn window_drops_old_intervals() {
+    let minute = Duration::from_secs(60);
+    let stats = WindowedStats::new(minute, 3);
+    let start = Instant::now() + minute;
+
+    stats.record_at(&chunk(1, 0, 0), start);
+    stats.record_at(&chunk(0, 2, 0), start + minute);
+    stats.record_at(&chunk(0, 0, 3), start + minute * 2);
+
+    let totals = stats.totals_at(start + minute * 2);
+    assert_eq!((totals.lf, totals.crlf, totals.cr), (1, 2, 3));
+    assert_eq!(totals.bytes, 30);
+
+    // One interval later the first bucket has left the window.
+    let totals = stats.totals_at(start + minute * 3);
+    assert_eq!((totals.lf, totals.crlf, totals.cr), (0, 2, 3));
+    let intervals = stats.intervals_at(start + minute * 3);
+    assert_eq!(intervals.len(), 3);
+    assert_eq!(intervals[2], EolStats::default());
+
+    // Recording much later expires everything before it.
+    stats.record_at(&chunk(4, 0, 0), start + minute * 10);
+    assert_eq!(stats.totals_at(start + minute * 10).lines(), 4);
+}
+
+#[test]
+fn windowed_stats_from_wrappers() {
+    let stats = WindowedStats::new(Duration::from_secs(3600), 60);
+
+    let mut output = Vec::new();
+    Reader::<_, LF>::new(b"a\r\nb\rc\n".as_ref(), 2)
+        .with_windowed_stats(stats.clone())
+        .read_to_end(&mut output)
+        .unwrap();
+
+    let mut writer = Writer::<_, CRLF>::new(Vec::new(), 4).with_windowed_stats(stats.clone());
+    writer.write_all(b"d\ne\r\n").unwrap();
+    writer.finish().unwrap();
+
+    let totals = stats.totals();
+    assert_eq!((totals.lf, totals.crlf, totals.cr), (2, 2, 1));
+    assert_eq!(totals.bytes, 12);
+}
@@ -0,0 +1,72 @@
+use std::io::Read;
+
+use eolify::{
+    classify::{classify, ContentKind},
+    EolStats,
+};
+
+#[test]
+fn text_line_endings() {
+    let classification = classify(b"a\nb\r\nc\rd".as_ref(), 100).unwrap();
+    assert!(classification.complete);
+    assert_eq!(
+        classification.kind,
+        ContentKind::Text {
+            utf8_bom: false,
+            eols: EolStats {
+                bytes: 8,
+                lf: 1,
+                crlf: 1,
+                cr: 1,
+            },
+        }
+    );
+}
+
+#[test]
+fn bounded_read_keeps_the_rest() {
+    let input = b"one\r\ntwo\r\nthree\r\n";
+    let classification = classify(input.as_ref(), 4).unwrap();
+    assert!(!classification.complete);
+    assert_eq!(classification.prefix, b"one\r");
+    // The `\r` at the end of the prefix might start a `\r\n`, so it is not counted yet.
+    let ContentKind::Text { eols, .. } = classification.kind else {
+        panic!("expected text");
+    };
+    assert_eq!(eols.lines(), 0);
+
+    let mut all = Vec::new();
+    classification.into_reader().read_to_end(&mut all).unwrap();
+    assert_eq!(all, input);
+}
+
+#[test]
+fn trailing_cr_of_complete_input_is_counted() {
+    let classification = classify(b"a\r\n\r".as_ref(), 100).unwrap();
+    assert!(classification.complete);
+    let ContentKind::Text { eols, .. } = classification.kind else {
+        panic!("expected text");
+    };
+    assert_eq!((eols.lf, eols.crlf, eols.cr), (0, 1, 1));
+}
+
+#[test]
+fn binary_and_empty() {
+    assert_eq!(
+        classify(b"PNG\0\r\n".as_ref(), 100).unwrap().kind,
+        ContentKind::Binary
+    );
+    // UTF-16 text can't be normalized byte by byte.
+    assert_eq!(
+        classify(b"\xFF\xFEa\0\n\0".as_ref(), 100).unwrap().kind,
+        ContentKind::Binary
+    );
+    assert_eq!(
+        classify(b"".as_ref(), 100).unwrap().kind,
+        ContentKind::Empty
+    );
+    assert!(matches!(
+        classify(b"abc".as_ref(), 0).unwrap().kind,
+        ContentKind::Text { .. }
+    ));
+}
@@ -0,0 +1,32 @@
+use std::time::SystemTime;
+
+use eolify::codegen::{normalize_out_dir_file, write_out_dir_file};
+
+// Everything runs in one test, since the helpers read the process-wide `OUT_DIR`.
+#[test]
+fn out_dir_files() {
+    std::env::remove_var("OUT_DIR");
+    let err = write_out_dir_file("gen.rs", "fn f() {}\r\n").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    let out_dir = std::env::temp_dir().join(format!("eolify-codegen-{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    std::env::set_var("OUT_DIR", &out_dir);
+
+    let path = write_out_dir_file("gen.rs", "fn f() {\r\n}\r\n").unwrap();
+    assert_eq!(path, out_dir.join("gen.rs"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"fn f() {\n}\n");
+
+    // Unchanged content leaves the file alone.
+    let modified = |path| std::fs::metadata(path).unwrap().modified().unwrap();
+    let before: SystemTime = modified(&path);
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    write_out_dir_file("gen.rs", "fn f() {\n}\r\n").unwrap();
+    assert_eq!(modified(&path), before);
+
+    std::fs::write(out_dir.join("tool.rs"), "a\r\nb\rc\n").unwrap();
+    let path = normalize_out_dir_file("tool.rs").unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), b"a\nb\nc\n");
+
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
@@ -0,0 +1,66 @@
+use std::io::Write;
+
+use eolify::{CollapseBlankLines, IoExt, Normalize, PreservesUtf8, CRLF, LF};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+#[test]
+fn limits_consecutive_line_endings() {
+    assert_eq!(
+        CollapseBlankLines::<LF, 2>::normalize(b"a\n\n\n\nb\r\n\r\n\r\nc"),
+        b"a\n\nb\n\nc"
+    );
+    assert_eq!(
+        CollapseBlankLines::<LF, 1>::normalize(b"\n\na\r\r\n\rb\n\n"),
+        b"\na\nb\n"
+    );
+    assert_eq!(
+        CollapseBlankLines::<CRLF, 2>::normalize_utf8("a\r\n \r\n\r\n\r\nb"),
+        "a\r\n \r\n\r\nb"
+    );
+}
+
+#[test]
+fn writer_tracks_runs_across_chunks() {
+    let mut writer = CollapseBlankLines::<CRLF, 2>::wrap_writer_with_buffer_size(Vec::new(), 1);
+    writer.write_all(b"one\r").unwrap();
+    writer.write_all(b"\n\r").unwrap();
+    writer.write_all(b"\n\r").unwrap();
+    writer.write_all(b"\ntwo").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"one\r\n\r\ntwo");
+}
+
+fn reference(data: &[u8], max: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut run = 0;
+    for &b in &LF::normalize(data) {
+        if b == b'\n' {
+            run += 1;
+            if run > max {
+                continue;
+            }
+        } else {
+            run = 0;
+        }
+        out.push(b);
+    }
+    out
+}
+
+proptest! {
+    #[test]
+    fn same_as_collapsing_after_normalizing(data in vec(any::<u8>(), 0..256)) {
+        prop_assert_eq!(CollapseBlankLines::<LF, 1>::normalize(&data), reference(&data, 1));
+        prop_assert_eq!(CollapseBlankLines::<LF, 2>::normalize(&data), reference(&data, 2));
+    }
+
+    #[test]
+    fn chunking_does_not_matter(data in vec(any::<u8>(), 0..256), buf_size in 1usize..8) {
+        let mut writer =
+            CollapseBlankLines::<CRLF, 2>::wrap_writer_with_buffer_size(Vec::new(), buf_size);
+        writer.write_all(&data).unwrap();
+        prop_assert_eq!(
+            writer.finish().unwrap(),
+            CollapseBlankLines::<CRLF, 2>::normalize(&data)
+        );
+    }
+}
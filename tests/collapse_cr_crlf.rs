@@ -0,0 +1,53 @@
+use std::io::{Read, Write};
+
+use eolify::{CollapseCrCrLf, Normalize, Reader, Writer, CRLF, LF};
+
+#[test]
+fn collapses_cr_cr_lf() {
+    assert_eq!(
+        CollapseCrCrLf::<CRLF>::normalize(b"a\r\r\nb\r\r\r\nc\r\n"),
+        b"a\r\nb\r\n\r\nc\r\n"
+    );
+    assert_eq!(
+        CollapseCrCrLf::<LF>::normalize(b"a\r\r\nb\r\rc\r"),
+        b"a\nb\n\nc\n"
+    );
+    assert!(CollapseCrCrLf::<LF>::normalize(b"").is_empty());
+}
+
+#[test]
+fn chunk_boundaries_do_not_matter() {
+    let input = b"x\r\r\ny\r\rz\r\r\r\n\n\r\r";
+    let expected_lf = CollapseCrCrLf::<LF>::normalize(input);
+    let expected_crlf = CollapseCrCrLf::<CRLF>::normalize(input);
+    assert_eq!(expected_lf, b"x\ny\n\nz\n\n\n\n\n");
+    for buf_size in 1..=input.len() {
+        let mut out = Vec::new();
+        Reader::<_, CollapseCrCrLf<LF>>::new(input.as_ref(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, expected_lf, "LF with buffer size {buf_size}");
+
+        let mut writer = Writer::<_, CollapseCrCrLf<CRLF>>::new(Vec::new(), buf_size);
+        for byte in input {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        assert_eq!(
+            writer.finish().unwrap(),
+            expected_crlf,
+            "CRLF with buffer size {buf_size}"
+        );
+    }
+}
+
+#[test]
+fn long_runs_across_chunks() {
+    // Only the `\r` directly before the final `\r\n` is dropped.
+    let mut input = vec![b'\r'; 200];
+    input.push(b'\n');
+    let mut out = Vec::new();
+    Reader::<_, CollapseCrCrLf<LF>>::new(input.as_slice(), 7)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, [b'\n'; 199]);
+}
@@ -0,0 +1,52 @@
+use std::io::Write;
+
+use eolify::{CommitWriter, Normalizer, CRLF, LF};
+
+#[test]
+fn nothing_is_written_before_commit() {
+    let mut writer = CommitWriter::<_, CRLF>::new(Vec::new(), 4);
+    writer.write_all(b"one\ntwo\nthree\n").unwrap();
+    writer.flush().unwrap();
+    assert!(writer.get_ref().is_empty());
+    assert_eq!(writer.commit().unwrap(), b"one\r\ntwo\r\nthree\r\n");
+}
+
+#[test]
+fn abort_discards_output() {
+    let mut writer = CommitWriter::<_, LF>::new(Vec::new(), 4);
+    writer.write_all(b"one\r\ntwo").unwrap();
+    assert!(writer.abort().is_empty());
+}
+
+#[test]
+fn dangling_cr_is_resolved_on_commit() {
+    let mut writer = CommitWriter::<_, LF>::new(Vec::new(), 2);
+    writer.write_all(b"a\r").unwrap();
+    assert_eq!(writer.commit().unwrap(), b"a\n");
+}
+
+#[test]
+fn large_output_spills_to_disk() {
+    let mut writer = CommitWriter::<_, CRLF>::new(Vec::new(), 16).with_spill_threshold(32);
+    let input = "line\n".repeat(100);
+    writer.write_all(input.as_bytes()).unwrap();
+    assert!(writer.is_spilled());
+    assert!(writer.get_ref().is_empty());
+    assert_eq!(writer.commit().unwrap(), "line\r\n".repeat(100).as_bytes());
+}
+
+#[test]
+fn small_output_stays_in_memory() {
+    let mut writer = CommitWriter::<_, CRLF>::new(Vec::new(), 16).with_spill_threshold(1024);
+    writer.write_all(b"a\nb\n").unwrap();
+    assert!(!writer.is_spilled());
+    assert_eq!(writer.commit().unwrap(), b"a\r\nb\r\n");
+}
+
+#[test]
+fn builder_commit_writer() {
+    let normalizer = Normalizer::builder().target(LF).buffer_size(3);
+    let mut writer = normalizer.commit_writer(Vec::new());
+    writer.write_all(b"x\r\ny\r\n").unwrap();
+    assert_eq!(writer.commit().unwrap(), b"x\ny\n");
+}
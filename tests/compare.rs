@@ -0,0 +1,45 @@
+#![cfg(feature = "bench-util")]
+
+use eolify::{
+    compare::{compare, compare_str},
+    CRLF, LF,
+};
+
+fn dos2unix(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut iter = input.iter().peekable();
+    while let Some(&b) = iter.next() {
+        if b == b'\r' {
+            iter.next_if_eq(&&b'\n');
+            out.push(b'\n');
+        } else {
+            out.push(b);
+        }
+    }
+    out
+}
+
+#[test]
+fn identical_outputs_are_measured() {
+    let inputs = [b"a\r\nb\rc\n".as_ref(), b"", b"\r\r\n\n"];
+    let comparison = compare::<LF, _, _>(&inputs, 3, dos2unix);
+    assert_eq!(comparison.eolify().bytes(), 33);
+    assert_eq!(comparison.alternative().bytes(), 33);
+    assert!(comparison.speedup() > 0.0);
+}
+
+#[test]
+fn str_alternatives() {
+    let inputs = ["a\nb\r\n", "c"];
+    let comparison = compare_str::<CRLF, _, _>(&inputs, 1, |input| {
+        input.replace("\r\n", "\n").replace('\n', "\r\n")
+    });
+    assert_eq!(comparison.eolify().bytes(), 6);
+}
+
+#[test]
+#[should_panic(expected = "outputs for input 1 differ at offset 1")]
+fn differing_outputs_panic() {
+    let inputs = [b"a\n".as_ref(), b"b\r\n"];
+    let _ = compare::<LF, _, _>(&inputs, 1, |input| input.to_vec());
+}
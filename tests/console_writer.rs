@@ -0,0 +1,29 @@
+use std::io::Write;
+
+use eolify::{ConsoleWriter, PipeEol};
+
+fn write_redirected(pipe_eol: PipeEol, name: &str) -> Vec<u8> {
+    let path =
+        std::env::temp_dir().join(format!("eolify-console-{}-{name}.txt", std::process::id()));
+    let file = std::fs::File::create(&path).unwrap();
+    let mut writer = ConsoleWriter::new(file, pipe_eol);
+    writer.write_all(b"one\r\ntwo\n").unwrap();
+    write!(writer, "three\r").unwrap();
+    writer.finish().unwrap();
+    let content = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    content
+}
+
+#[test]
+fn redirected_output_uses_pipe_eol() {
+    assert_eq!(write_redirected(PipeEol::Lf, "lf"), b"one\ntwo\nthree\n");
+    assert_eq!(
+        write_redirected(PipeEol::CrLf, "crlf"),
+        b"one\r\ntwo\r\nthree\r\n"
+    );
+    assert_eq!(
+        write_redirected(PipeEol::Unchanged, "unchanged"),
+        b"one\r\ntwo\nthree\r"
+    );
+}
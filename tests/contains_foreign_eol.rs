@@ -0,0 +1,44 @@
+use eolify::{Checked, Normalize, NormalizeChunk, CRLF, LF, NDJSON};
+use proptest::prelude::*;
+
+#[test]
+fn crlf_foreign_eols() {
+    assert!(!CRLF::contains_foreign_eol(b""));
+    assert!(!CRLF::contains_foreign_eol(b"a\r\nb\r\n"));
+    assert!(CRLF::contains_foreign_eol(b"a\nb"));
+    assert!(CRLF::contains_foreign_eol(b"a\rb"));
+    assert!(CRLF::contains_foreign_eol(b"a\r"));
+    assert!(CRLF::contains_foreign_eol(b"\r\r\n"));
+    assert!(CRLF::contains_foreign_eol(b"\r\n\n"));
+}
+
+#[test]
+fn lf_foreign_eols() {
+    assert!(!LF::contains_foreign_eol(b""));
+    assert!(!LF::contains_foreign_eol(b"a\nb\n"));
+    assert!(LF::contains_foreign_eol(b"a\r\nb"));
+    assert!(LF::contains_foreign_eol(b"a\r"));
+}
+
+#[test]
+fn default_implementation() {
+    assert!(!NDJSON::contains_foreign_eol(b"{}\n{}\n"));
+    assert!(NDJSON::contains_foreign_eol(b"{}\n{}"));
+    assert!(NDJSON::contains_foreign_eol(b"{}\n\n{}\n"));
+    assert!(!Checked::<CRLF>::contains_foreign_eol(b"a\r\n"));
+    assert!(Checked::<CRLF>::contains_foreign_eol(b"a\n"));
+}
+
+proptest! {
+    #[test]
+    fn matches_normalize(input in proptest::collection::vec(
+        prop_oneof![Just(b'\r'), Just(b'\n'), Just(b'a')], 0..64)
+    ) {
+        prop_assert_eq!(CRLF::contains_foreign_eol(&input), CRLF::normalize(&input) != input);
+        prop_assert_eq!(LF::contains_foreign_eol(&input), LF::normalize(&input) != input);
+        prop_assert_eq!(
+            Checked::<LF>::contains_foreign_eol(&input),
+            LF::normalize(&input) != input
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use eolify::{convert, convert_with, ConvertError, LineTerminator, RepairPolicy};
+
+const ALL: [LineTerminator; 3] = [LineTerminator::Lf, LineTerminator::CrLf, LineTerminator::Cr];
+
+fn text_with(eol: LineTerminator) -> Vec<u8> {
+    ["one", "two", "", "three"]
+        .iter()
+        .fold(Vec::new(), |mut out, line| {
+            out.extend_from_slice(line.as_bytes());
+            out.extend_from_slice(eol.as_bytes());
+            out
+        })
+}
+
+#[test]
+fn converts_between_all_styles() {
+    for from in ALL {
+        for to in ALL {
+            assert_eq!(
+                convert(&text_with(from), from, to).unwrap(),
+                text_with(to),
+                "{from:?} -> {to:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn text_without_line_endings() {
+    assert_eq!(
+        convert(b"plain", LineTerminator::Lf, LineTerminator::CrLf).unwrap(),
+        b"plain"
+    );
+    assert!(convert(b"", LineTerminator::Cr, LineTerminator::Lf)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn rejects_contradicting_input() {
+    assert_eq!(
+        convert(b"a\nb\r\n", LineTerminator::Lf, LineTerminator::CrLf),
+        Err(ConvertError {
+            offset: 3,
+            found: LineTerminator::CrLf,
+            expected: LineTerminator::Lf,
+        })
+    );
+    assert_eq!(
+        convert(b"a\r\nb\rc", LineTerminator::CrLf, LineTerminator::CrLf)
+            .unwrap_err()
+            .found,
+        LineTerminator::Cr
+    );
+    // A `\r` followed by `\n` is a CRLF, not two CR-style line endings.
+    assert_eq!(
+        convert(b"a\r\n", LineTerminator::Cr, LineTerminator::Lf)
+            .unwrap_err()
+            .found,
+        LineTerminator::CrLf
+    );
+}
+
+#[test]
+fn repair_policies() {
+    let input = b"a\nb\r\nc\rd\n";
+    let (from, to) = (LineTerminator::Lf, LineTerminator::CrLf);
+    assert_eq!(
+        convert_with(input, from, to, RepairPolicy::Error),
+        convert(input, from, to)
+    );
+    assert_eq!(
+        convert_with(input, from, to, RepairPolicy::Skip).unwrap(),
+        b"a\r\nbcd\r\n"
+    );
+    assert_eq!(
+        convert_with(input, from, to, RepairPolicy::Replace).unwrap(),
+        b"a\r\nb\r\nc\r\nd\r\n"
+    );
+    assert_eq!(RepairPolicy::default(), RepairPolicy::Error);
+}
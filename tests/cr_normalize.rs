@@ -0,0 +1,75 @@
+use std::io::{Read, Write};
+
+use eolify::{Normalize, NormalizeChunk, Reader, Writer, CR};
+
+fn run(input: &[u8]) -> Vec<u8> {
+    CR::normalize(input)
+}
+
+#[test]
+fn no_cr_or_lf() {
+    assert_eq!(run(b"hello world"), b"hello world");
+}
+
+#[test]
+fn lone_lf_converted_to_cr() {
+    assert_eq!(run(b"line1\nline2\n"), b"line1\rline2\r");
+}
+
+#[test]
+fn crlf_converted_to_cr() {
+    assert_eq!(run(b"foo\r\nbar\r\n"), b"foo\rbar\r");
+}
+
+#[test]
+fn lone_cr_kept() {
+    assert_eq!(run(b"a\rb\r"), b"a\rb\r");
+}
+
+#[test]
+fn mixed() {
+    assert_eq!(run(b"\r\r\n\n\n\r"), b"\r\r\r\r\r");
+}
+
+#[test]
+fn crlf_split_across_chunks() {
+    let mut out = Vec::new();
+    let status = CR::normalize_chunk_into(b"a\r", &mut out, None, false).unwrap();
+    assert_eq!(status.state(), Some(&true));
+    let status = CR::normalize_chunk_into(b"", &mut out, status.state(), false).unwrap();
+    let status = CR::normalize_chunk_into(b"\nb\n", &mut out, status.state(), true).unwrap();
+    assert_eq!(status.state(), Some(&false));
+    assert_eq!(out, b"a\rb\r");
+}
+
+#[test]
+fn foreign_eol_and_len() {
+    assert!(CR::contains_foreign_eol(b"a\r\n"));
+    assert!(CR::contains_foreign_eol(b"a\n"));
+    assert!(!CR::contains_foreign_eol(b"a\rb\r"));
+    let input = b"a\r\nb\nc\r\r\n";
+    assert_eq!(CR::normalized_len(input), run(input).len());
+}
+
+#[test]
+fn reader_and_writer() {
+    let input = b"one\r\ntwo\nthree\r\r\nfour";
+    let expected = run(input);
+    for buf_size in 1..=input.len() {
+        let mut out = Vec::new();
+        Reader::<_, CR>::new(input.as_ref(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, expected, "reader with buffer size {buf_size}");
+
+        let mut writer = Writer::<_, CR>::new(Vec::new(), buf_size);
+        for byte in input {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        assert_eq!(
+            writer.finish().unwrap(),
+            expected,
+            "writer with buffer size {buf_size}"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use eolify::{Normalize, NormalizeChunk, CR, LF};
+use proptest::{
+    arbitrary::any, collection::vec, prop_assert, prop_assert_eq, proptest, test_runner::Config,
+};
+
+proptest! {
+    #![proptest_config(Config::with_cases(25000))]
+
+    #[test]
+    fn normalize_chunk_split_matches_whole(data in vec(any::<u8>(), 0..256), split_byte in any::<u8>()) {
+        let split = (split_byte as usize).min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status1 = CR::normalize_chunk_into(a, &mut out, None, false)
+            .expect("output buffer too small for first chunk");
+        let len1 = out.len();
+        prop_assert!(len1 <= a.len(), "first chunk grew");
+        CR::normalize_chunk_into(b, &mut out, status1.state(), true)
+            .expect("output buffer too small for second chunk");
+
+        prop_assert_eq!(&out, &CR::normalize(&data));
+        prop_assert!(!out.contains(&b'\n'), "found an LF in the output");
+    }
+
+    #[test]
+    fn normalize_idempotent(data in vec(any::<u8>(), 0..256)) {
+        let once = CR::normalize(&data);
+        prop_assert_eq!(CR::normalize(&once), once.clone());
+        prop_assert_eq!(CR::normalized_len(&data), once.len());
+    }
+
+    #[test]
+    fn same_line_count_as_lf(data in vec(any::<u8>(), 0..256)) {
+        // CR and LF only differ in the byte used to end a line.
+        let cr = CR::normalize(&data);
+        let lf = LF::normalize(&data);
+        prop_assert_eq!(cr.len(), lf.len());
+        let swapped: Vec<u8> = lf.iter().map(|&b| if b == b'\n' { b'\r' } else { b }).collect();
+        prop_assert_eq!(cr, swapped);
+    }
+}
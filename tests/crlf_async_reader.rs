@@ -41,6 +41,16 @@ dual_test!(crlf_split_across_readers, {
     assert_eq!(out.as_slice(), b"foo\r\nbar");
 });
 
+dual_test!(position_accounting, {
+    let readers = vec![b"a\nb".as_ref(), b"\r\nc".as_ref()].into_iter();
+    let test_reader = AsyncTestReader::new(readers);
+    let mut nr = CRLF::wrap_async_reader_with_buffer_size(test_reader, 3);
+    let mut out = Vec::new();
+    nr.read_to_end(&mut out).await.unwrap();
+    assert_eq!(nr.bytes_consumed(), 6);
+    assert_eq!(nr.bytes_produced(), 7);
+});
+
 dual_test!(crlf_split_across_three_reader, {
     let readers = vec![b"\r".as_ref(), b"".as_ref(), b"\n".as_ref()].into_iter();
     let test_reader = AsyncTestReader::new(readers);
@@ -77,6 +87,95 @@ dual_test!(trailing_cr_at_eof_emits_crlf, {
     assert_eq!(out, b"foo\r\n".to_vec());
 });
 
+dual_test!(small_and_large_reads_interleaved, {
+    let readers = vec![b"a\nb\r".as_ref(), b"\nc\rd".as_ref(), b"e\nf\r".as_ref()].into_iter();
+    let test_reader = AsyncTestReader::new(readers);
+    let mut nr = CRLF::wrap_async_reader_with_buffer_size(test_reader, 4);
+    let mut out = Vec::new();
+    // Large reads take the output straight into the caller's buffer, small ones go through
+    // the internal buffer.
+    for size in [64, 1, 64, 2, 64, 64, 64].into_iter().cycle() {
+        let mut buf = vec![0; size];
+        let n = nr.read(&mut buf).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(out, b"a\r\nb\r\nc\r\nde\r\nf\r\n".to_vec());
+    assert_eq!(nr.bytes_produced(), out.len() as u64);
+});
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_reads_into_uninitialized_buffer() {
+    use eolify::TokioExt;
+    use std::mem::MaybeUninit;
+    use tokio::io::{AsyncRead, ReadBuf};
+
+    let readers = vec![b"one\ntwo\r".as_ref(), b"\nthree".as_ref()].into_iter();
+    let mut nr = eolify::CRLF::wrap_async_reader_with_buffer_size(AsyncTestReader::new(readers), 8);
+    let mut storage = [MaybeUninit::<u8>::uninit(); 64];
+    let mut buf = ReadBuf::uninit(&mut storage);
+    loop {
+        let filled = buf.filled().len();
+        std::future::poll_fn(|cx| Pin::new(&mut nr).poll_read(cx, &mut buf))
+            .await
+            .unwrap();
+        if buf.filled().len() == filled {
+            break;
+        }
+    }
+    assert_eq!(buf.filled(), b"one\r\ntwo\r\nthree");
+    assert_eq!(buf.initialized().len(), buf.filled().len());
+}
+
+#[cfg(feature = "futures-io")]
+#[async_std::test]
+async fn futures_io_seek_resets_state() {
+    use eolify::FuturesIoExt;
+    use futures_util::{io::Cursor, AsyncReadExt, AsyncSeekExt};
+
+    let mut nr = eolify::CRLF::wrap_async_reader_with_buffer_size(Cursor::new(b"ab\rcd\n"), 3);
+    let mut out = [0; 2];
+    nr.read_exact(&mut out).await.unwrap();
+    assert_eq!(&out, b"ab");
+
+    // The pending `\r` and buffered output are dropped, the second pass starts fresh.
+    assert_eq!(nr.seek(std::io::SeekFrom::Start(3)).await.unwrap(), 3);
+    let mut rest = Vec::new();
+    nr.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(rest, b"cd\r\n");
+
+    nr.seek(std::io::SeekFrom::Start(0)).await.unwrap();
+    let mut all = Vec::new();
+    nr.read_to_end(&mut all).await.unwrap();
+    assert_eq!(all, b"ab\r\ncd\r\n");
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_seek_resets_state() {
+    use eolify::TokioExt;
+    use std::io::Cursor;
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let mut nr = eolify::CRLF::wrap_async_reader_with_buffer_size(Cursor::new(b"ab\rcd\n"), 3);
+    let mut out = [0; 2];
+    nr.read_exact(&mut out).await.unwrap();
+    assert_eq!(&out, b"ab");
+
+    assert_eq!(nr.seek(std::io::SeekFrom::Start(3)).await.unwrap(), 3);
+    let mut rest = Vec::new();
+    nr.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(rest, b"cd\r\n");
+
+    nr.seek(std::io::SeekFrom::Start(0)).await.unwrap();
+    let mut all = Vec::new();
+    nr.read_to_end(&mut all).await.unwrap();
+    assert_eq!(all, b"ab\r\ncd\r\n");
+}
+
 pub struct AsyncTestReader<R, I> {
     readers: I,
     current: Option<R>,
@@ -85,10 +184,7 @@ pub struct AsyncTestReader<R, I> {
 impl<R, I: Iterator<Item = R>> AsyncTestReader<R, I> {
     pub fn new(mut readers: I) -> AsyncTestReader<R, I> {
         let current = readers.next();
-        AsyncTestReader {
-            readers: readers,
-            current: current,
-        }
+        AsyncTestReader { readers, current }
     }
 }
 
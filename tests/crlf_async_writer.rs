@@ -1,5 +1,7 @@
 #![cfg(any(feature = "futures-io", feature = "tokio"))]
 
+use eolify::CRLF;
+
 macro_rules! dual_test {
     ($name:ident, $body:block) => {
         mod $name {
@@ -65,3 +67,148 @@ dual_test!(trailing_cr_at_eof_emits_crlf, {
     let out = writer.finish().await.unwrap();
     assert_eq!(out, b"foo\r\n".to_vec());
 });
+
+dual_test!(interactive_flush_policy_resolves_dangling_cr, {
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 16)
+        .with_flush_policy(eolify::FlushPolicy::interactive());
+    writer.write_all(b"foo\r").await.unwrap();
+    writer.flush().await.unwrap();
+    writer.write_all(b"\nbar").await.unwrap();
+    let out = writer.finish().await.unwrap();
+    assert_eq!(out, b"foo\r\n\r\nbar".to_vec());
+});
+
+dual_test!(interactive_flush_policy_keeps_end_of_stream_for_finish, {
+    let mut writer =
+        eolify::EnsureFinalNewline::<CRLF>::wrap_async_writer_with_buffer_size(Vec::new(), 16)
+            .with_flush_policy(eolify::FlushPolicy::interactive());
+    writer.write_all(b"a").await.unwrap();
+    writer.flush().await.unwrap();
+    writer.write_all(b"b\r").await.unwrap();
+    writer.flush().await.unwrap();
+    writer.write_all(b"\nc").await.unwrap();
+    let out = writer.finish().await.unwrap();
+    assert_eq!(out, b"ab\r\n\r\nc\r\n".to_vec());
+});
+
+dual_test!(eager_flush_policy_keeps_crlf_across_writes, {
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 16)
+        .with_flush_policy(eolify::FlushPolicy::batch().eager(true));
+    writer.write_all(b"foo\r").await.unwrap();
+    writer.write_all(b"\nbar\n").await.unwrap();
+    let out = writer.finish().await.unwrap();
+    assert_eq!(out, b"foo\r\nbar\r\n".to_vec());
+});
+
+dual_test!(position_accounting, {
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\nb\nc").await.unwrap();
+    assert_eq!(writer.bytes_consumed(), 5);
+    assert_eq!(writer.bytes_produced(), 6);
+    writer.flush().await.unwrap();
+    assert_eq!(writer.bytes_produced(), 7);
+});
+
+dual_test!(finish_polled_after_completion_is_an_error, {
+    use std::future::Future;
+
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\n").await.unwrap();
+    let mut finish = std::pin::pin!(writer.finish());
+    assert_eq!((&mut finish).await.unwrap(), b"a\r\n".to_vec());
+    let again = std::future::poll_fn(|cx| finish.as_mut().poll(cx)).await;
+    assert!(again.is_err());
+});
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_shutdown_is_idempotent() {
+    use eolify::TokioExt;
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\nb").await.unwrap();
+    assert!(!writer.is_finished());
+    writer.shutdown().await.unwrap();
+    assert!(writer.is_finished());
+    writer.shutdown().await.unwrap();
+    assert!(writer.is_finished());
+    assert_eq!(writer.finish().await.unwrap(), b"a\r\nb".to_vec());
+}
+
+#[cfg(feature = "futures-io")]
+#[async_std::test]
+async fn futures_io_close_is_idempotent() {
+    use eolify::FuturesIoExt;
+    use futures_util::AsyncWriteExt;
+
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\nb").await.unwrap();
+    assert!(!writer.is_finished());
+    writer.close().await.unwrap();
+    assert!(writer.is_finished());
+    writer.close().await.unwrap();
+    assert!(writer.is_finished());
+    assert_eq!(writer.finish().await.unwrap(), b"a\r\nb".to_vec());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_finish_with_timeout() {
+    use std::time::Duration;
+
+    use eolify::TokioExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 64);
+    let out = writer
+        .finish_with_timeout(Duration::from_secs(5))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(out.is_empty());
+
+    // Nobody reads from the other end, so only the first 4 bytes fit.
+    let (local, mut remote) = tokio::io::duplex(4);
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(local, 64);
+    writer.write_all(b"one\ntwo\nthree").await.unwrap();
+    let timeout = writer
+        .finish_with_timeout(Duration::from_millis(20))
+        .await
+        .unwrap_err();
+    let mut written = [0; 4];
+    remote.read_exact(&mut written).await.unwrap();
+    assert_eq!(written, *b"one\r");
+    assert_eq!(timeout.unflushed, b"\ntwo\r\nthree".to_vec());
+    drop(timeout.writer);
+}
+
+dual_test!(with_async_writer_finishes_on_success, {
+    let (out, value) = CRLF::with_async_writer(Vec::new(), |writer| {
+        Box::pin(async move {
+            writer.write_all(b"a\nb\r").await?;
+            Ok(42)
+        })
+    })
+    .await
+    .unwrap();
+    assert_eq!(out, b"a\r\nb\r\n".to_vec());
+    assert_eq!(value, 42);
+
+    let err = CRLF::with_async_writer(Vec::new(), |writer| {
+        Box::pin(async move {
+            writer.write_all(b"a\n").await?;
+            Err::<(), _>(std::io::Error::other("boom"))
+        })
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(err.to_string(), "boom");
+});
+
+dual_test!(finish_with_final_chunk, {
+    let mut writer = CRLF::wrap_async_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\n").await.unwrap();
+    let out = writer.finish_with(b"b\nlast\r").await.unwrap();
+    assert_eq!(out, b"a\r\nb\r\nlast\r\n".to_vec());
+});
@@ -39,3 +39,35 @@ fn multiple_crs_and_crlf_mixed() {
     let out = run(b"\r\r\n");
     assert_eq!(out, b"\r\n\r\n".to_vec());
 }
+
+#[test]
+fn normalize_chunks_matches_normalize() {
+    let input = b"a\r\nb\nc\r\rd\n\r\n";
+    for chunk_size in 0..=input.len() + 1 {
+        let mut chunks = CRLF::normalize_chunks(input, chunk_size);
+        let mut out = Vec::new();
+        while let Some(chunk) = chunks.next_chunk() {
+            assert!(!chunk.is_empty());
+            out.extend_from_slice(chunk);
+        }
+        assert_eq!(out, CRLF::normalize(input), "chunk size {chunk_size}");
+        assert!(chunks.next_chunk().is_none());
+    }
+}
+
+#[test]
+fn normalize_chunks_of_empty_input() {
+    assert!(CRLF::normalize_chunks(b"", 8).next_chunk().is_none());
+}
+
+#[test]
+fn normalize_chunk_into_appends() {
+    use eolify::NormalizeChunk;
+
+    let mut output = b"prefix:".to_vec();
+    let status = CRLF::normalize_chunk_into(b"a\nb\r", &mut output, None, false).unwrap();
+    assert_eq!(status.output_len(), 5);
+    let status = CRLF::normalize_chunk_into(b"\n", &mut output, status.state(), true).unwrap();
+    assert_eq!(status.output_len(), 1);
+    assert_eq!(output, b"prefix:a\r\nb\r\n");
+}
@@ -19,75 +19,124 @@ fn run(input: &[u8], preceded_by_cr: bool, is_last_chunk: bool) -> (Vec<u8>, boo
 fn no_cr_or_lf() {
     let (out, last) = run(b"hello world", false, false);
     assert_eq!(out, b"hello world");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn lone_lf_converted_to_crlf() {
     let (out, last) = run(b"line1\nline2", false, false);
     assert_eq!(out, b"line1\r\nline2");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn trailing_cr_sets_last_flag() {
     let (out, last) = run(b"foo\r", false, false);
     assert_eq!(out, b"foo\r");
-    assert_eq!(last, true);
+    assert!(last);
 }
 
 #[test]
 fn trailing_cr_in_last_chunk_emits_crlf() {
     let (out, last) = run(b"foo\r", false, true);
     assert_eq!(out, b"foo\r\n");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn cr_not_followed_by_lf_in_middle_becomes_crlf() {
     let (out, last) = run(b"a\rb", false, false);
     assert_eq!(out, b"a\r\nb");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn existing_crlf_kept_as_crlf() {
     let (out, last) = run(b"foo\r\nbar", false, false);
     assert_eq!(out, b"foo\r\nbar");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn last_was_cr_and_next_is_lf_emits_lf() {
     let (out, last) = run(b"\nabc", true, false);
     assert_eq!(out, b"\nabc");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn last_was_cr_and_next_is_not_lf_emits_lf() {
     let (out, last) = run(b"X", true, false);
     assert_eq!(out, b"\nX");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn last_was_cr_and_empty_chunk_does_not_emit_lf_if_not_last() {
     let (out, last) = run(b"", true, false);
     assert_eq!(out, b"");
-    assert_eq!(last, true);
+    assert!(last);
 }
 
 #[test]
 fn last_was_cr_and_empty_chunk_does_emit_lf_if_last() {
     let (out, last) = run(b"", true, true);
     assert_eq!(out, b"\n");
-    assert_eq!(last, false);
+    assert!(!last);
 }
 
 #[test]
 fn multiple_crs_and_crlf_mixed() {
     let (out, last) = run(b"\r\r\n", false, false);
     assert_eq!(out, b"\r\n\r\n");
-    assert_eq!(last, false);
+    assert!(!last);
+}
+
+#[test]
+fn max_output_size_saturates_for_huge_chunks() {
+    assert_eq!(
+        CRLF::max_output_size_for_chunk(usize::MAX / 2, None, false),
+        usize::MAX - 1
+    );
+    assert_eq!(
+        CRLF::max_output_size_for_chunk(usize::MAX / 2, None, true),
+        usize::MAX
+    );
+    assert_eq!(
+        CRLF::max_output_size_for_chunk(usize::MAX / 2 + 1, None, false),
+        usize::MAX
+    );
+    assert_eq!(
+        CRLF::max_output_size_for_chunk(usize::MAX, Some(&true), true),
+        usize::MAX
+    );
+}
+
+#[test]
+fn undersized_output_reports_required_size() {
+    let mut output = [0; 4];
+    let err =
+        CRLF::normalize_chunk(b"abc", slice_to_uninit_mut(&mut output), None, true).unwrap_err();
+    assert_eq!(err.required_size(), 7);
+}
+
+#[test]
+fn const_max_output_size_matches_trait() {
+    use eolify::LF;
+
+    for chunk_size in [0, 1, 7, 4096] {
+        for is_last_chunk in [false, true] {
+            for state in [None, Some(&false), Some(&true)] {
+                assert_eq!(
+                    CRLF::max_output_size(chunk_size, is_last_chunk),
+                    CRLF::max_output_size_for_chunk(chunk_size, state, is_last_chunk)
+                );
+                assert_eq!(
+                    LF::max_output_size(chunk_size, is_last_chunk),
+                    LF::max_output_size_for_chunk(chunk_size, state, is_last_chunk)
+                );
+            }
+        }
+    }
+    assert_eq!(CRLF::max_output_size(usize::MAX, true), usize::MAX);
 }
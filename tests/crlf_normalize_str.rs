@@ -39,3 +39,21 @@ fn multiple_crs_and_crlf_mixed() {
     let out = run("\r\r\n");
     assert_eq!(out, "\r\n\r\n".to_string());
 }
+
+#[test]
+fn normalize_utf8_matches_normalize_str() {
+    use eolify::{PreservesUtf8, Tidy, VisibleSymbols, LF, NDJSON};
+
+    let input = "\u{feff}Grüße\r\nこんにちは\rмир\n";
+    assert_eq!(CRLF::normalize_utf8(input), run(input));
+    assert_eq!(LF::normalize_utf8(input), LF::normalize_str(input));
+    assert_eq!(NDJSON::normalize_utf8(input), NDJSON::normalize_str(input));
+    assert_eq!(
+        VisibleSymbols::normalize_utf8(input),
+        VisibleSymbols::normalize_str(input)
+    );
+    assert_eq!(
+        Tidy::<LF>::normalize_utf8(input),
+        "Grüße\nこんにちは\nмир\n"
+    );
+}
@@ -56,6 +56,38 @@ fn extension_trait() {
     assert_eq!(out, b"\r\n\r\n".to_vec());
 }
 
+#[test]
+fn cloned_reader_continues_mid_stream() {
+    let mut nr = CRLF::wrap_reader_with_buffer_size(b"foo\r\nbar\rbaz\n".as_ref(), 4);
+    let mut head = [0; 3];
+    nr.read_exact(&mut head).unwrap();
+    assert_eq!(&head, b"foo");
+
+    let clone = nr.clone();
+    assert_eq!(read_all(nr), b"\r\nbar\r\nbaz\r\n".to_vec());
+    assert_eq!(read_all(clone), b"\r\nbar\r\nbaz\r\n".to_vec());
+}
+
+#[test]
+fn zero_buffer_size_is_clamped() {
+    let nr = CRLF::wrap_reader_with_buffer_size(b"foo\nbar".as_ref(), 0);
+    assert_eq!(read_all(nr), b"foo\r\nbar".to_vec());
+}
+
+#[test]
+fn position_accounting() {
+    let mut nr = CRLF::wrap_reader_with_buffer_size(b"a\nb\r\nc".as_ref(), 3);
+    let mut head = [0; 2];
+    nr.read_exact(&mut head).unwrap();
+    assert_eq!(nr.bytes_consumed(), 3);
+    assert_eq!(nr.bytes_produced(), 2);
+
+    let mut rest = Vec::new();
+    nr.read_to_end(&mut rest).unwrap();
+    assert_eq!(nr.bytes_consumed(), 6);
+    assert_eq!(nr.bytes_produced(), 7);
+}
+
 fn read_all<R: Read>(mut r: R) -> Vec<u8> {
     let mut out = Vec::new();
     r.read_to_end(&mut out).unwrap();
@@ -70,10 +102,7 @@ pub struct TestReader<R, I> {
 impl<R: Read, I: Iterator<Item = R>> TestReader<R, I> {
     pub fn new(mut readers: I) -> TestReader<R, I> {
         let current = readers.next();
-        TestReader {
-            readers: readers,
-            current: current,
-        }
+        TestReader { readers, current }
     }
 }
 
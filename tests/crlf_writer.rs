@@ -1,6 +1,6 @@
 use std::io::Write;
 
-use eolify::{IoExt, WriteExt, CRLF};
+use eolify::{FlushPolicy, IoExt, WriteExt, CRLF};
 
 #[test]
 fn crlf_split_across_chunks() {
@@ -55,3 +55,95 @@ fn extension_trait() {
     let out = writer.finish().unwrap();
     assert_eq!(out, b"\r\n\r\n".to_vec());
 }
+
+#[test]
+fn batch_flush_policy_buffers_small_writes() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 16);
+    writer.write_all(b"foo\n").unwrap();
+    assert_eq!(writer.get_ref(), b"");
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"foo\r\n");
+}
+
+#[test]
+fn eager_flush_policy_passes_writes_through() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 16)
+        .with_flush_policy(FlushPolicy::batch().eager(true));
+    writer.write_all(b"foo\n").unwrap();
+    assert_eq!(writer.get_ref(), b"foo\r\n");
+    writer.write_all(b"bar\r").unwrap();
+    assert_eq!(writer.get_ref(), b"foo\r\nbar\r");
+    writer.write_all(b"\nbaz").unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"foo\r\nbar\r\nbaz".to_vec());
+}
+
+#[test]
+fn interactive_flush_policy_resolves_dangling_cr() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 16)
+        .with_flush_policy(FlushPolicy::interactive());
+    writer.write_all(b"foo\r").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"foo\r\n");
+    writer.write_all(b"\nbar").unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"foo\r\n\r\nbar".to_vec());
+}
+
+#[test]
+fn large_writes_bypass_input_buffer() {
+    let input = b"one\ntwo\r\nthree\r".repeat(100);
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 5);
+    writer.write_all(&input[..3]).unwrap();
+    writer.write_all(&input[3..]).unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"one\r\ntwo\r\nthree\r\n".repeat(100));
+}
+
+#[test]
+fn zero_buffer_size_is_clamped() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 0);
+    writer.write_all(b"foo\nbar").unwrap();
+    let out = writer.finish().unwrap();
+    assert_eq!(out, b"foo\r\nbar".to_vec());
+}
+
+#[test]
+fn position_accounting() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\nb\nc").unwrap();
+    assert_eq!(writer.bytes_consumed(), 5);
+    assert_eq!(writer.bytes_produced(), 6);
+    writer.flush().unwrap();
+    assert_eq!(writer.bytes_produced(), 7);
+}
+
+#[test]
+fn with_writer_finishes_on_success() {
+    let (out, value) = CRLF::with_writer(Vec::new(), |writer| {
+        writer.write_all(b"a\nb\r")?;
+        Ok(42)
+    })
+    .unwrap();
+    assert_eq!(out, b"a\r\nb\r\n");
+    assert_eq!(value, 42);
+
+    let err = CRLF::with_writer(Vec::new(), |writer| {
+        writer.write_all(b"a\n")?;
+        Err::<(), _>(std::io::Error::other("boom"))
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "boom");
+}
+
+#[test]
+fn finish_with_final_chunk() {
+    let mut writer = CRLF::wrap_writer_with_buffer_size(Vec::new(), 4);
+    writer.write_all(b"a\r").unwrap();
+    let out = writer.finish_with(b"\nlong final chunk\r").unwrap();
+    assert_eq!(out, b"a\r\nlong final chunk\r\n".to_vec());
+    assert_eq!(
+        CRLF::wrap_writer(Vec::new()).finish_with(b"").unwrap(),
+        b"".to_vec()
+    );
+}
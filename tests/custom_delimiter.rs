@@ -0,0 +1,129 @@
+use std::io::Read;
+
+use eolify::{Custom, Delimiter, IoExt, Normalize, NormalizeChunk, LF};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+struct RecordSeparator;
+
+impl Delimiter for RecordSeparator {
+    const BYTES: &'static [u8] = b"\x1e";
+}
+
+struct DoubleCr;
+
+impl Delimiter for DoubleCr {
+    const BYTES: &'static [u8] = b"\r\r\n";
+}
+
+struct Sentinel;
+
+impl Delimiter for Sentinel {
+    const BYTES: &'static [u8] = b"<EOL>";
+}
+
+struct Strip;
+
+impl Delimiter for Strip {
+    const BYTES: &'static [u8] = b"";
+}
+
+#[test]
+fn replaces_every_line_ending() {
+    let input = b"a\r\nb\nc\rd\r";
+    assert_eq!(
+        Custom::<RecordSeparator>::normalize(input),
+        b"a\x1eb\x1ec\x1ed\x1e"
+    );
+    assert_eq!(
+        Custom::<DoubleCr>::normalize(input),
+        b"a\r\r\nb\r\r\nc\r\r\nd\r\r\n"
+    );
+    assert_eq!(
+        Custom::<Sentinel>::normalize(input),
+        b"a<EOL>b<EOL>c<EOL>d<EOL>"
+    );
+    assert_eq!(Custom::<Strip>::normalize(input), b"abcd");
+}
+
+#[test]
+fn delimiter_in_input_is_left_alone() {
+    assert_eq!(
+        Custom::<RecordSeparator>::normalize(b"a\x1eb\n"),
+        b"a\x1eb\x1e"
+    );
+}
+
+#[test]
+fn worst_case_size_scales_with_delimiter() {
+    assert_eq!(Custom::<RecordSeparator>::max_output_size(10, true), 10);
+    assert_eq!(Custom::<Strip>::max_output_size(10, true), 10);
+    assert_eq!(Custom::<Sentinel>::max_output_size(10, false), 50);
+    assert_eq!(
+        Custom::<Sentinel>::max_output_size_for_chunk(usize::MAX, None, true),
+        usize::MAX
+    );
+
+    let input = [b'\r'; 10];
+    let mut out = Vec::new();
+    Custom::<Sentinel>::normalize_chunk_into(&input, &mut out, None, true).unwrap();
+    assert_eq!(out.len(), 50);
+}
+
+#[test]
+fn crlf_split_across_chunks() {
+    let mut out = Vec::new();
+    let status = Custom::<Sentinel>::normalize_chunk_into(b"a\r", &mut out, None, false).unwrap();
+    assert_eq!(status.state(), Some(&true));
+    Custom::<Sentinel>::normalize_chunk_into(b"\nb", &mut out, status.state(), true).unwrap();
+    assert_eq!(out, b"a<EOL>b");
+}
+
+#[test]
+fn reader_with_small_buffer() {
+    let mut out = Vec::new();
+    Custom::<DoubleCr>::wrap_reader_with_buffer_size(b"one\r\ntwo\rthree\n".as_ref(), 1)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"one\r\r\ntwo\r\r\nthree\r\r\n");
+}
+
+#[test]
+fn normalize_str_validates_output() {
+    assert_eq!(Custom::<Sentinel>::normalize_str("x\r\ny"), "x<EOL>y");
+}
+
+proptest! {
+    #[test]
+    fn chunk_split_matches_whole(data in vec(any::<u8>(), 0..256), split_byte in any::<u8>()) {
+        let split = (split_byte as usize).min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status = Custom::<DoubleCr>::normalize_chunk_into(a, &mut out, None, false).unwrap();
+        Custom::<DoubleCr>::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+
+        let whole = Custom::<DoubleCr>::normalize(&data);
+        prop_assert_eq!(&out, &whole);
+        prop_assert_eq!(Custom::<DoubleCr>::normalized_len(&data), whole.len());
+    }
+
+    #[test]
+    fn matches_lf_with_replaced_terminator(data in vec(any::<u8>(), 0..256)) {
+        // Every LF in the LF-normalized output is a line ending, so with the separator absent
+        // from the input both formats only differ in the byte used to end a line.
+        let data: Vec<u8> = data.into_iter().filter(|&b| b != 0x1e).collect();
+        let expected: Vec<u8> = LF::normalize(&data)
+            .into_iter()
+            .map(|b| if b == b'\n' { 0x1e } else { b })
+            .collect();
+        prop_assert_eq!(Custom::<RecordSeparator>::normalize(&data), expected);
+    }
+}
+
+#[test]
+fn compares_without_requiring_traits_on_the_delimiter() {
+    assert_eq!(
+        Custom::<RecordSeparator>::default(),
+        Custom::<RecordSeparator>::default()
+    );
+}
@@ -0,0 +1,99 @@
+use std::{
+    io::{Read, Write},
+    mem::MaybeUninit,
+};
+
+use eolify::{
+    Normalize, NormalizeChunk, NormalizeChunkResult, PreservesUtf8, Reader, Result, Tidy, Writer,
+};
+
+/// A byte transform unrelated to newlines, masking all ASCII digits.
+struct MaskDigits;
+
+impl NormalizeChunk for MaskDigits {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> usize {
+        chunk_size
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        for (out, &b) in output.iter_mut().zip(input) {
+            out.write(if b.is_ascii_digit() { b'#' } else { b });
+        }
+        Ok(NormalizeChunkResult::new(input.len(), None))
+    }
+}
+
+// SAFETY: Only ASCII digits are replaced, by another ASCII byte.
+unsafe impl PreservesUtf8 for MaskDigits {}
+
+/// A transform that does not preserve UTF-8, replacing every `!` with the byte 0xFF.
+struct BreaksUtf8;
+
+impl NormalizeChunk for BreaksUtf8 {
+    type State = ();
+
+    fn max_output_size_for_chunk(
+        chunk_size: usize,
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> usize {
+        chunk_size
+    }
+
+    fn normalize_chunk(
+        input: &[u8],
+        output: &mut [MaybeUninit<u8>],
+        _state: Option<&Self::State>,
+        _is_last_chunk: bool,
+    ) -> Result<NormalizeChunkResult<Self::State>> {
+        for (out, &b) in output.iter_mut().zip(input) {
+            out.write(if b == b'!' { 0xFF } else { b });
+        }
+        Ok(NormalizeChunkResult::new(input.len(), None))
+    }
+}
+
+#[test]
+fn utf8_preserving_transform() {
+    assert_eq!(MaskDigits::normalize_utf8("ü 12"), "ü ##");
+    assert_eq!(MaskDigits::normalize_str("ü 12"), "ü ##");
+}
+
+#[test]
+#[should_panic(expected = "normalization produced invalid UTF-8")]
+fn normalize_str_validates_output() {
+    let _ = BreaksUtf8::normalize_str("hi!");
+}
+
+#[test]
+fn reader_drives_custom_transform() {
+    let mut out = String::new();
+    Reader::<_, MaskDigits>::new(b"pin 1234, card 5678".as_ref(), 3)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "pin ####, card ####");
+}
+
+#[test]
+fn writer_drives_custom_transform() {
+    let mut writer = Writer::<_, MaskDigits>::new(Vec::new(), 4);
+    writer.write_all(b"call 555-0100").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"call ###-####");
+}
+
+#[test]
+#[should_panic(expected = "normalization produced invalid UTF-8")]
+fn adapters_validate_output_of_formats_breaking_utf8() {
+    let _ = Tidy::<BreaksUtf8>::normalize_str("hi!");
+}
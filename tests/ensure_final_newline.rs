@@ -0,0 +1,73 @@
+use std::io::Write;
+
+use eolify::{
+    EnsureFinalNewline, FlushPolicy, IoExt, Normalize, Normalizer, PreservesUtf8, CR, CRLF, LF,
+};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+#[test]
+fn appends_missing_line_ending() {
+    assert_eq!(EnsureFinalNewline::<LF>::normalize(b"a\r\nb"), b"a\nb\n");
+    assert_eq!(
+        EnsureFinalNewline::<CRLF>::normalize(b"a\nb"),
+        b"a\r\nb\r\n"
+    );
+    assert_eq!(EnsureFinalNewline::<CR>::normalize_utf8("a"), "a\r");
+}
+
+#[test]
+fn keeps_existing_line_ending() {
+    assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b"a\n"), b"a\r\n");
+    assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b"a\r"), b"a\r\n");
+    assert_eq!(EnsureFinalNewline::<LF>::normalize(b"a\r\n\n"), b"a\n\n");
+}
+
+#[test]
+fn empty_input_stays_empty() {
+    assert_eq!(EnsureFinalNewline::<CRLF>::normalize(b""), b"");
+}
+
+#[test]
+fn writer_appends_at_finish() {
+    let mut writer = EnsureFinalNewline::<CRLF>::wrap_writer_with_buffer_size(Vec::new(), 1);
+    writer.write_all(b"one\r").unwrap();
+    writer.write_all(b"\ntwo").unwrap();
+    writer.write_all(b"").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"one\r\ntwo\r\n");
+}
+
+#[test]
+fn interactive_flush_is_not_the_end() {
+    let mut writer = EnsureFinalNewline::<LF>::wrap_writer(Vec::new())
+        .with_flush_policy(FlushPolicy::interactive());
+    writer.write_all(b"a").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"a");
+    writer.write_all(b"b\r").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"ab\n");
+    writer.write_all(b"\nc").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"ab\n\nc\n");
+}
+
+#[test]
+fn builder_wraps_target() {
+    let mut out = Vec::new();
+    Normalizer::builder()
+        .target(LF)
+        .ensure_final_newline()
+        .copy(&mut b"a\r\nb".as_ref(), &mut out)
+        .unwrap();
+    assert_eq!(out, b"a\nb\n");
+}
+
+proptest! {
+    #[test]
+    fn same_as_appending_after_normalizing(data in vec(any::<u8>(), 0..256)) {
+        let mut expected = CRLF::normalize(&data);
+        if !expected.is_empty() && !expected.ends_with(b"\r\n") {
+            expected.extend_from_slice(b"\r\n");
+        }
+        prop_assert_eq!(EnsureFinalNewline::<CRLF>::normalize(&data), expected);
+    }
+}
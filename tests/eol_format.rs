@@ -0,0 +1,135 @@
+use std::io::{BufRead, Read, Write};
+
+use eolify::{EolDispatch, EolFormat, LineTerminator};
+
+const INPUT: &[u8] = b"one\ntwo\r\nthree\rfour\r";
+
+#[test]
+fn parse_and_display_round_trip() {
+    for format in EolFormat::ALL {
+        assert_eq!(format.to_string().parse::<EolFormat>(), Ok(format));
+    }
+    assert_eq!("CRLF".parse::<EolFormat>(), Ok(EolFormat::CrLf));
+
+    let err = "lfcr".parse::<EolFormat>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown line ending format `lfcr`, expected one of lf, crlf, cr"
+    );
+}
+
+#[test]
+fn converts_from_and_to_line_terminator() {
+    for terminator in [LineTerminator::Lf, LineTerminator::CrLf, LineTerminator::Cr] {
+        let format = EolFormat::from(terminator);
+        assert_eq!(format.line_terminator(), terminator);
+        assert_eq!(LineTerminator::from(format), terminator);
+    }
+}
+
+#[test]
+fn reader_dispatches_on_format() {
+    let expected: [&[u8]; 3] = [
+        b"one\ntwo\nthree\nfour\n",
+        b"one\r\ntwo\r\nthree\r\nfour\r\n",
+        b"one\rtwo\rthree\rfour\r",
+    ];
+    for (format, expected) in EolFormat::ALL.into_iter().zip(expected) {
+        let mut reader = format.wrap_reader_with_buffer_size(INPUT, 3);
+        assert_eq!(reader.format(), format);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, expected, "{format}");
+        assert_eq!(out, format.normalize(INPUT), "{format}");
+        assert_eq!(reader.bytes_consumed(), INPUT.len() as u64);
+        assert_eq!(reader.bytes_produced(), out.len() as u64);
+    }
+}
+
+#[test]
+fn reader_implements_buf_read() {
+    use eolify::ReadExt;
+
+    let reader = INPUT.normalize_newlines_to(EolFormat::CrLf);
+    let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["one", "two", "three", "four"]);
+}
+
+#[test]
+fn writer_dispatches_on_format() {
+    use eolify::WriteExt;
+
+    for format in EolFormat::ALL {
+        let mut writer = Vec::new().normalize_newlines_to(format);
+        for chunk in INPUT.chunks(2) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(writer.bytes_consumed(), INPUT.len() as u64);
+        let out = writer.finish().unwrap();
+        assert_eq!(out, format.normalize(INPUT), "{format}");
+    }
+}
+
+#[test]
+fn variants_expose_the_typed_wrapper() {
+    match EolFormat::Cr.wrap_writer(Vec::new()) {
+        EolDispatch::Cr(mut writer) => {
+            writer.write_all(b"a\r\n").unwrap();
+            assert_eq!(writer.finish().unwrap(), b"a\r");
+        }
+        _ => panic!("expected the CR variant"),
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_uses_format_names() {
+    let json = serde_json::to_string(&EolFormat::CrLf).unwrap();
+    assert_eq!(json, "\"crlf\"");
+    assert_eq!(
+        serde_json::from_str::<EolFormat>("\"cr\"").unwrap(),
+        EolFormat::Cr
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_wrappers_dispatch_on_format() {
+    use eolify::{TokioAsyncReadExt, TokioAsyncWriteExt};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    for format in EolFormat::ALL {
+        let mut out = Vec::new();
+        INPUT
+            .normalize_newlines_to(format)
+            .read_to_end(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(out, format.normalize(INPUT), "{format}");
+
+        let mut writer = Vec::new().normalize_newlines_to(format);
+        writer.write_all(INPUT).await.unwrap();
+        assert_eq!(writer.finish().await.unwrap(), out, "{format}");
+    }
+}
+
+#[cfg(feature = "futures-io")]
+#[async_std::test]
+async fn futures_io_wrappers_dispatch_on_format() {
+    use eolify::{FuturesIoAsyncReadExt, FuturesIoAsyncWriteExt};
+    use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+    for format in EolFormat::ALL {
+        let mut out = Vec::new();
+        INPUT
+            .normalize_newlines_to(format)
+            .read_to_end(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(out, format.normalize(INPUT), "{format}");
+
+        let mut writer = Vec::new().normalize_newlines_to(format);
+        writer.write_all(INPUT).await.unwrap();
+        assert_eq!(writer.finish().await.unwrap(), out, "{format}");
+    }
+}
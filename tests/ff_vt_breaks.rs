@@ -0,0 +1,43 @@
+use std::io::Write;
+
+use eolify::{FfVtBreaks, IoExt, Normalize, PreservesUtf8, CR, CRLF, LF};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+#[test]
+fn form_feed_and_vertical_tab_become_line_endings() {
+    assert_eq!(FfVtBreaks::<LF>::normalize(b"a\x0cb\x0bc"), b"a\nb\nc");
+    assert_eq!(FfVtBreaks::<CR>::normalize(b"a\x0c\nb"), b"a\r\rb");
+    assert_eq!(FfVtBreaks::<CRLF>::normalize_utf8("\r\x0c"), "\r\n\r\n");
+}
+
+#[test]
+fn lines_match_python_splitlines() {
+    // "a\x0cb\x0b\x0bc\r\nd".splitlines() == ['a', 'b', '', 'c', 'd']
+    let out = FfVtBreaks::<LF>::normalize_str("a\x0cb\x0b\x0bc\r\nd");
+    assert_eq!(
+        out.split('\n').collect::<Vec<_>>(),
+        ["a", "b", "", "c", "d"]
+    );
+}
+
+#[test]
+fn writer_with_small_buffer() {
+    let mut writer = FfVtBreaks::<CRLF>::wrap_writer_with_buffer_size(Vec::new(), 1);
+    writer.write_all(b"one\x0ctwo\r").unwrap();
+    writer.write_all(b"\x0bthree").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"one\r\ntwo\r\n\r\nthree");
+}
+
+proptest! {
+    #[test]
+    fn same_as_replacing_breaks_first(data in vec(any::<u8>(), 0..256)) {
+        let replaced: Vec<u8> = data
+            .iter()
+            .flat_map(|&b| match b {
+                0x0b | 0x0c => b"\r\n".to_vec(),
+                b => vec![b],
+            })
+            .collect();
+        prop_assert_eq!(FfVtBreaks::<LF>::normalize(&data), LF::normalize(&replaced));
+    }
+}
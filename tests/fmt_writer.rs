@@ -0,0 +1,29 @@
+use std::fmt::Write;
+
+use eolify::{FmtWriteExt, FmtWriter, CRLF, LF};
+
+#[test]
+fn writes_to_string() {
+    let mut writer = String::new().normalize_newlines(LF);
+    write!(writer, "one\r\ntwo\r").unwrap();
+    writer.write_str("\nthree\r").unwrap();
+    assert_eq!(writer.finish().unwrap(), "one\ntwo\nthree\n");
+}
+
+#[test]
+fn crlf_split_across_writes() {
+    let mut writer = FmtWriter::<_, CRLF>::new(String::new());
+    writer.write_str("a\r").unwrap();
+    writer.write_str("\nb\n").unwrap();
+    writer.write_char('c').unwrap();
+    assert_eq!(writer.finish().unwrap(), "a\r\nb\r\nc");
+}
+
+#[test]
+fn borrowed_sink() {
+    let mut out = String::new();
+    let mut writer = (&mut out).normalize_newlines(CRLF);
+    writeln!(writer, "ünïcödé").unwrap();
+    writer.finish().unwrap();
+    assert_eq!(out, "ünïcödé\r\n");
+}
@@ -0,0 +1,55 @@
+use eolify::framing::{FixedFrames, FrameError, FrameFill, FrameOverflow};
+
+#[test]
+fn pads_lines_to_width() {
+    let frames = FixedFrames::new(4).frame(b"ab\r\n\ncd").unwrap();
+    assert_eq!(frames, b"ab      cd  ");
+}
+
+#[test]
+fn empty_input_has_no_records() {
+    assert!(FixedFrames::new(80).frame(b"").unwrap().is_empty());
+}
+
+#[test]
+fn terminated_records() {
+    let frames = FixedFrames::new(5)
+        .fill(FrameFill::Terminated {
+            terminator: b';',
+            pad: 0,
+        })
+        .frame(b"abcd\nx\n")
+        .unwrap();
+    assert_eq!(frames, b"abcd;x;\0\0\0");
+}
+
+#[test]
+fn long_line_is_rejected_by_default() {
+    let err = FixedFrames::new(3).frame(b"ok\ntoo long\n").unwrap_err();
+    assert_eq!(err, FrameError::LineTooLong { line: 2, len: 8 });
+}
+
+#[test]
+fn long_line_truncated_or_wrapped() {
+    let frames = FixedFrames::new(3);
+    assert_eq!(
+        frames
+            .overflow(FrameOverflow::Truncate)
+            .frame(b"abcdefg\n")
+            .unwrap(),
+        b"abc"
+    );
+    assert_eq!(
+        frames
+            .overflow(FrameOverflow::Wrap)
+            .frame(b"abcdefg\n")
+            .unwrap(),
+        b"abcdefg  "
+    );
+}
+
+#[test]
+#[should_panic(expected = "non-zero")]
+fn zero_width_panics() {
+    let _ = FixedFrames::new(0);
+}
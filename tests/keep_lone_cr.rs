@@ -0,0 +1,37 @@
+use std::io::Read;
+
+use eolify::{KeepLoneCr, Normalize, Reader, CRLF, LF};
+
+#[test]
+fn lone_cr_is_data() {
+    assert_eq!(
+        KeepLoneCr::<LF>::normalize(b"a\rb\r\nc\n\r"),
+        b"a\rb\nc\n\r"
+    );
+    assert_eq!(
+        KeepLoneCr::<CRLF>::normalize(b"\r\r\n\n\r"),
+        b"\r\r\n\r\n\r"
+    );
+    assert!(KeepLoneCr::<CRLF>::normalize(b"").is_empty());
+}
+
+#[test]
+fn chunk_boundaries_do_not_matter() {
+    let input = b"x\r\ny\rz\r\r\n\n\r";
+    let expected_lf = KeepLoneCr::<LF>::normalize(input);
+    let expected_crlf = KeepLoneCr::<CRLF>::normalize(input);
+    assert_eq!(expected_lf, b"x\ny\rz\r\n\n\r");
+    for buf_size in 1..=input.len() {
+        let mut out = Vec::new();
+        Reader::<_, KeepLoneCr<LF>>::new(input.as_ref(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, expected_lf, "LF with buffer size {buf_size}");
+
+        let mut out = Vec::new();
+        Reader::<_, KeepLoneCr<CRLF>>::new(input.as_ref(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, expected_crlf, "CRLF with buffer size {buf_size}");
+    }
+}
@@ -50,6 +50,15 @@ dual_test!(crlf_split_across_three_reader, {
     assert_eq!(out, b"\n".to_vec());
 });
 
+dual_test!(chunk_normalizing_to_nothing_is_not_end_of_stream, {
+    let readers = vec![b"a\r".as_ref(), b"\n".as_ref(), b"b".as_ref()].into_iter();
+    let test_reader = AsyncTestReader::new(readers);
+    let mut nr = LF::wrap_async_reader_with_buffer_size(test_reader, 4);
+    let mut out = Vec::new();
+    nr.read_to_end(&mut out).await.unwrap();
+    assert_eq!(out, b"a\nb".to_vec());
+});
+
 dual_test!(lone_lf_in_first_reader_kept_as_lf, {
     let readers = vec![b"line1\n".as_ref(), b"line2".as_ref()].into_iter();
     let test_reader = AsyncTestReader::new(readers);
@@ -85,10 +94,7 @@ pub struct AsyncTestReader<R, I> {
 impl<R, I: Iterator<Item = R>> AsyncTestReader<R, I> {
     pub fn new(mut readers: I) -> AsyncTestReader<R, I> {
         let current = readers.next();
-        AsyncTestReader {
-            readers: readers,
-            current: current,
-        }
+        AsyncTestReader { readers, current }
     }
 }
 
@@ -153,3 +159,20 @@ impl<R: tokio::io::AsyncRead + Unpin, I: Iterator<Item = R> + Unpin> tokio::io::
         }
     }
 }
+
+dual_test!(read_to_vec_and_string, {
+    let input = b"one\r\ntwo\rthree".as_ref();
+    let mut expected = Vec::new();
+    pin!(LF::wrap_async_reader(input))
+        .read_to_end(&mut expected)
+        .await
+        .unwrap();
+    assert_eq!(LF::async_read_to_vec(input).await.unwrap(), expected);
+    assert_eq!(
+        LF::async_read_to_string(input).await.unwrap(),
+        "one\ntwo\nthree"
+    );
+    assert!(LF::async_read_to_string(b"\xff\r\n".as_ref())
+        .await
+        .is_err());
+});
@@ -33,8 +33,8 @@ proptest! {
 
         // out1: no CR whatsoever.
         if !out1.is_empty() {
-            for i in 0..out1.len() {
-                prop_assert!(out1[i] != b'\r', "found a CR in out1 at {}", i);
+            for (i, &b) in out1.iter().enumerate() {
+                prop_assert!(b != b'\r', "found a CR in out1 at {}", i);
             }
         } else {
             // empty out1 must reflect ended_with_cr == false (since we passed preceded_by_cr=false)
@@ -43,8 +43,8 @@ proptest! {
 
         // out2: no CR whatsoever.
         if !out2.is_empty() {
-            for i in 0..out2.len() {
-                prop_assert!(out2[i] != b'\r', "found a CR in out2 at {}", i);
+            for (i, &b) in out2.iter().enumerate() {
+                prop_assert!(b != b'\r', "found a CR in out2 at {}", i);
             }
         } else {
             // empty out2 is allowed; if status1.ended_with_cr was true, the LF may have been consumed
@@ -63,8 +63,8 @@ proptest! {
         }
 
         // out1 must not contain any CR
-        for i in 0..out1.len() {
-            prop_assert!(out1[i] != b'\r', "found a CR in out1 at {}", i);
+        for (i, &b) in out1.iter().enumerate() {
+            prop_assert!(b != b'\r', "found a CR in out1 at {}", i);
         }
 
         // Second normalization
@@ -56,6 +56,31 @@ fn extension_trait() {
     assert_eq!(out, b"\n\n".to_vec());
 }
 
+#[test]
+fn chunk_normalizing_to_nothing_is_not_end_of_stream() {
+    let readers = vec![b"a\r".as_ref(), b"\n".as_ref(), b"b".as_ref()].into_iter();
+    let test_reader = TestReader::new(readers);
+    let nr = LF::wrap_reader_with_buffer_size(test_reader, 4);
+    let out = read_all(nr);
+    assert_eq!(out, b"a\nb".to_vec());
+}
+
+#[test]
+fn large_caller_buffer_reads_directly() {
+    let input = b"one\r\ntwo\rthree\r".repeat(100);
+    let mut nr = LF::wrap_reader_with_buffer_size(input.as_slice(), 7);
+    let mut out = Vec::new();
+    let mut buf = [0; 64];
+    loop {
+        let n = nr.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(out, b"one\ntwo\nthree\n".repeat(100));
+}
+
 fn read_all<R: Read>(mut r: R) -> Vec<u8> {
     let mut out = Vec::new();
     r.read_to_end(&mut out).unwrap();
@@ -70,10 +95,7 @@ pub struct TestReader<R, I> {
 impl<R: Read, I: Iterator<Item = R>> TestReader<R, I> {
     pub fn new(mut readers: I) -> TestReader<R, I> {
         let current = readers.next();
-        TestReader {
-            readers: readers,
-            current: current,
-        }
+        TestReader { readers, current }
     }
 }
 
@@ -93,3 +115,36 @@ impl<R: Read, I: Iterator<Item = R>> Read for TestReader<R, I> {
         }
     }
 }
+
+#[test]
+fn read_to_vec_and_string() {
+    let input = b"one\r\ntwo\rthree".as_ref();
+    assert_eq!(LF::read_to_vec(input).unwrap(), b"one\ntwo\nthree");
+    assert_eq!(LF::read_to_string(input).unwrap(), "one\ntwo\nthree");
+    let err = LF::read_to_string(b"\xff\r\n".as_ref()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn buf_read_lines() {
+    use std::io::BufRead;
+
+    let reader = LF::wrap_reader_with_buffer_size(b"one\r\ntwo\rthree".as_ref(), 3);
+    let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["one", "two", "three"]);
+}
+
+#[test]
+fn buf_read_mixed_with_read() {
+    use std::io::BufRead;
+
+    let mut reader = LF::wrap_reader_with_buffer_size(b"ab\r\ncd\r\n".as_ref(), 4);
+    let mut first = String::new();
+    reader.read_line(&mut first).unwrap();
+    assert_eq!(first, "ab\n");
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"cd\n");
+    assert_eq!(reader.bytes_produced(), 6);
+    assert!(reader.fill_buf().unwrap().is_empty());
+}
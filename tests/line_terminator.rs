@@ -0,0 +1,67 @@
+use eolify::LineTerminator;
+
+#[test]
+fn classify_at() {
+    let buf = b"a\nb\r\nc\rd\r";
+    assert_eq!(LineTerminator::classify_at(buf, 0), None);
+    assert_eq!(
+        LineTerminator::classify_at(buf, 1),
+        Some(LineTerminator::Lf)
+    );
+    assert_eq!(
+        LineTerminator::classify_at(buf, 3),
+        Some(LineTerminator::CrLf)
+    );
+    assert_eq!(
+        LineTerminator::classify_at(buf, 4),
+        Some(LineTerminator::Lf)
+    );
+    assert_eq!(
+        LineTerminator::classify_at(buf, 6),
+        Some(LineTerminator::Cr)
+    );
+    assert_eq!(
+        LineTerminator::classify_at(buf, 8),
+        Some(LineTerminator::Cr)
+    );
+    assert_eq!(LineTerminator::classify_at(buf, 9), None);
+    assert_eq!(LineTerminator::classify_at(buf, usize::MAX), None);
+}
+
+#[test]
+fn len_and_bytes() {
+    for (terminator, bytes) in [
+        (LineTerminator::Lf, b"\n".as_ref()),
+        (LineTerminator::CrLf, b"\r\n".as_ref()),
+        (LineTerminator::Cr, b"\r".as_ref()),
+    ] {
+        assert_eq!(terminator.as_bytes(), bytes);
+        assert_eq!(terminator.len(), bytes.len());
+        assert_eq!(LineTerminator::classify_at(bytes, 0), Some(terminator));
+    }
+}
+
+#[test]
+fn find_next_eol_visits_all_line_endings() {
+    use eolify::helpers::find_next_eol;
+
+    let buf = b"a\nb\r\nc\r\rd\r";
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some((pos, terminator)) = find_next_eol(buf, from) {
+        found.push((pos, terminator));
+        from = pos + terminator.len();
+    }
+    assert_eq!(
+        found,
+        [
+            (1, LineTerminator::Lf),
+            (3, LineTerminator::CrLf),
+            (6, LineTerminator::Cr),
+            (7, LineTerminator::Cr),
+            (9, LineTerminator::Cr),
+        ]
+    );
+    assert_eq!(find_next_eol(buf, buf.len()), None);
+    assert_eq!(find_next_eol(buf, buf.len() + 1), None);
+}
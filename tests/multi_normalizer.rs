@@ -0,0 +1,35 @@
+use eolify::{MultiNormalizer, Normalize, CRLF, LF};
+
+#[test]
+fn interleaved_streams_match_separate_normalization() {
+    let streams: [&[u8]; 3] = [b"one\r\ntwo\rthree\n", b"\r\r\n\n\r", b"plain"];
+    let mut multi = MultiNormalizer::<usize, LF>::new();
+    let mut outputs = vec![Vec::new(); streams.len()];
+
+    // Feed the streams round robin, one byte at a time.
+    let longest = streams.iter().map(|s| s.len()).max().unwrap();
+    for pos in 0..longest {
+        for (key, stream) in streams.iter().enumerate() {
+            if let Some(byte) = stream.get(pos) {
+                let out = multi.normalize_for(key, std::slice::from_ref(byte));
+                outputs[key].extend_from_slice(out);
+            }
+        }
+    }
+    assert_eq!(multi.len(), streams.len());
+    for (key, stream) in streams.iter().enumerate() {
+        outputs[key].extend_from_slice(multi.finish(&key));
+        assert_eq!(outputs[key], LF::normalize(stream), "stream {key}");
+    }
+    assert!(multi.is_empty());
+}
+
+#[test]
+fn string_keys_and_removal() {
+    let mut multi = MultiNormalizer::<String, CRLF>::default();
+    assert_eq!(multi.normalize_for("a".to_string(), b"x\n"), b"x\r\n");
+    assert!(multi.contains_key("a"));
+    assert!(multi.remove("a"));
+    assert!(!multi.remove("a"));
+    assert_eq!(multi.finish("unknown"), b"");
+}
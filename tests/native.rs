@@ -0,0 +1,38 @@
+use std::io::Write;
+
+use eolify::{LineTerminator, Native, Normalize, NormalizeChunk, WriteExt, CRLF, LF};
+
+#[test]
+fn matches_platform_format() {
+    let input = b"a\nb\r\nc\rd";
+    let expected = if cfg!(windows) {
+        CRLF::normalize(input)
+    } else {
+        LF::normalize(input)
+    };
+    assert_eq!(Native::normalize(input), expected);
+    assert_eq!(Native::normalized_len(input), expected.len());
+    assert!(Native::contains_foreign_eol(input));
+
+    let eol = Native::LINE_TERMINATOR.as_bytes();
+    assert!(!Native::contains_foreign_eol(
+        &[b"x", eol, b"y", eol].concat()
+    ));
+    assert_eq!(
+        Native::LINE_TERMINATOR,
+        if cfg!(windows) {
+            LineTerminator::CrLf
+        } else {
+            LineTerminator::Lf
+        }
+    );
+}
+
+#[test]
+fn generic_writer() {
+    let mut writer = Vec::new().normalize_newlines(Native);
+    writer.write_all(b"one\rtwo\n").unwrap();
+    let out = writer.finish().unwrap();
+    let eol = Native::LINE_TERMINATOR.as_bytes();
+    assert_eq!(out, [b"one", eol, b"two", eol].concat());
+}
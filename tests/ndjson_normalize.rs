@@ -0,0 +1,55 @@
+use std::io::Read;
+
+use eolify::{IoExt, Normalize, NDJSON};
+
+fn normalize(input: &[u8]) -> Vec<u8> {
+    NDJSON::normalize(input)
+}
+
+#[test]
+fn empty_input_stays_empty() {
+    assert_eq!(normalize(b""), b"");
+}
+
+#[test]
+fn missing_final_lf_is_added() {
+    assert_eq!(normalize(b"{\"a\":1}"), b"{\"a\":1}\n");
+}
+
+#[test]
+fn crlf_and_cr_become_lf() {
+    assert_eq!(normalize(b"{}\r\n{}\r{}\n"), b"{}\n{}\n{}\n");
+}
+
+#[test]
+fn blank_lines_between_records_are_collapsed() {
+    assert_eq!(normalize(b"\r\n{}\r\n\r\n\n\r{}\n\n"), b"{}\n{}\n");
+}
+
+#[test]
+fn line_endings_inside_strings_are_untouched() {
+    assert_eq!(
+        normalize(b"{\"a\":\"x\r\ny\ry\"}\r\n"),
+        b"{\"a\":\"x\r\ny\ry\"}\n"
+    );
+}
+
+#[test]
+fn escaped_quotes_do_not_end_strings() {
+    assert_eq!(
+        normalize(b"{\"a\":\"q\\\"\r\n\\\\\"}\r\n{}"),
+        b"{\"a\":\"q\\\"\r\n\\\\\"}\n{}\n"
+    );
+}
+
+#[test]
+fn state_is_carried_across_small_reads() {
+    let input = b"{\"a\":\"\\\"\r\n\"}\r\n\r\n{\"b\":\"\r\"}\r";
+    for buffer_size in 1..input.len() {
+        let mut out = Vec::new();
+        NDJSON::wrap_reader_with_buffer_size(input.as_ref(), buffer_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, normalize(input), "buffer size {buffer_size}");
+    }
+}
@@ -0,0 +1,62 @@
+use std::io::Read;
+
+use eolify::{IoExt, Nel, NelLatin1, Normalize, NormalizeChunk, PreservesUtf8, CRLF, LF};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+#[test]
+fn utf8_nel_becomes_line_ending() {
+    assert_eq!(Nel::<LF>::normalize_str("a\u{85}b\r\nc"), "a\nb\nc");
+    assert_eq!(Nel::<CRLF>::normalize_utf8("\r\u{85}\n"), "\r\n\r\n\r\n");
+    // Other characters encoded with a 0x85 byte are left alone.
+    assert_eq!(Nel::<LF>::normalize_str("\u{c5}\u{2026}"), "\u{c5}\u{2026}");
+}
+
+#[test]
+fn latin1_nel_becomes_line_ending() {
+    assert_eq!(NelLatin1::<CRLF>::normalize(b"a\x85b\x85"), b"a\r\nb\r\n");
+    assert_eq!(NelLatin1::<LF>::normalize(b"\x85\n"), b"\n\n");
+}
+
+#[test]
+fn utf8_nel_split_across_reads() {
+    let mut out = String::new();
+    Nel::<LF>::wrap_reader_with_buffer_size("one\u{85}two\u{85}".as_bytes(), 1)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "one\ntwo\n");
+}
+
+#[test]
+fn latin1_worst_case_size() {
+    let input = [0x85; 16];
+    let mut out = Vec::new();
+    NelLatin1::<CRLF>::normalize_chunk_into(&input, &mut out, None, true).unwrap();
+    assert_eq!(out.len(), 32);
+    assert!(out.len() <= NelLatin1::<CRLF>::max_output_size_for_chunk(16, None, true));
+}
+
+proptest! {
+    #[test]
+    fn latin1_chunk_split_matches_whole(data in vec(any::<u8>(), 0..256), split in 0usize..256) {
+        let split = split.min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status = NelLatin1::<CRLF>::normalize_chunk_into(a, &mut out, None, false).unwrap();
+        NelLatin1::<CRLF>::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+
+        prop_assert_eq!(&out, &NelLatin1::<CRLF>::normalize(&data));
+    }
+
+    #[test]
+    fn utf8_chunk_split_matches_whole(data in vec(any::<u8>(), 0..256), split in 0usize..256) {
+        let split = split.min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status = Nel::<LF>::normalize_chunk_into(a, &mut out, None, false).unwrap();
+        Nel::<LF>::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+
+        prop_assert_eq!(&out, &Nel::<LF>::normalize(&data));
+    }
+}
@@ -0,0 +1,100 @@
+use eolify::{Normalize, NormalizeEdit, CR, CRLF, LF};
+use proptest::{
+    arbitrary::any, collection::vec, prop_assert_eq, prop_oneof, proptest, strategy::Just,
+    strategy::Strategy, test_runner::Config,
+};
+
+fn eol_heavy_bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    vec(
+        prop_oneof![Just(b'\r'), Just(b'\n'), Just(b'a'), any::<u8>()],
+        0..max_len,
+    )
+}
+
+#[test]
+fn insert_lf_after_cr_joins_line_ending() {
+    let mut buf = CRLF::normalize(b"foo\r\nbar");
+    let edit = CRLF::normalize_edit(&mut buf, 4..5, b"\n");
+    assert_eq!(buf, b"foo\r\nbar".to_vec());
+    assert_eq!(edit.old_range(), 4..5);
+    assert_eq!(edit.new_range(), 4..5);
+}
+
+#[test]
+fn removing_cr_of_crlf_renormalizes_lf() {
+    let mut buf = CRLF::normalize(b"foo\r\nbar\r\nbaz");
+    let edit = CRLF::normalize_edit(&mut buf, 3..4, b"");
+    assert_eq!(buf, b"foo\r\nbar\r\nbaz".to_vec());
+    assert_eq!(edit.map_offset(10), Some(10));
+}
+
+#[test]
+fn replacement_with_dangling_cr_joins_following_lf() {
+    let mut buf = LF::normalize(b"one\ntwo");
+    let edit = LF::normalize_edit(&mut buf, 1..3, b"X\r");
+    assert_eq!(buf, b"oX\ntwo".to_vec());
+    assert_eq!(edit.old_range(), 1..4);
+    assert_eq!(edit.new_range(), 1..3);
+    assert_eq!(edit.map_offset(0), Some(0));
+    assert_eq!(edit.map_offset(2), None);
+    assert_eq!(edit.map_offset(5), Some(4));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn out_of_bounds_edit_panics() {
+    let mut buf = LF::normalize(b"abc");
+    LF::normalize_edit(&mut buf, 2..4, b"");
+}
+
+proptest! {
+    #![proptest_config(Config::with_cases(10000))]
+
+    #[test]
+    fn crlf_edit_matches_full_normalization(
+        doc in eol_heavy_bytes(64),
+        replacement in eol_heavy_bytes(8),
+        a in any::<usize>(),
+        b in any::<usize>(),
+    ) {
+        let mut buf = CRLF::normalize(&doc);
+        let (start, end) = edit_range(buf.len(), a, b);
+        let expected = CRLF::normalize(&[&buf[..start], &replacement, &buf[end..]].concat());
+        CRLF::normalize_edit(&mut buf, start..end, &replacement);
+        prop_assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn lf_edit_matches_full_normalization(
+        doc in eol_heavy_bytes(64),
+        replacement in eol_heavy_bytes(8),
+        a in any::<usize>(),
+        b in any::<usize>(),
+    ) {
+        let mut buf = LF::normalize(&doc);
+        let (start, end) = edit_range(buf.len(), a, b);
+        let expected = LF::normalize(&[&buf[..start], &replacement, &buf[end..]].concat());
+        LF::normalize_edit(&mut buf, start..end, &replacement);
+        prop_assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn cr_edit_matches_full_normalization(
+        doc in eol_heavy_bytes(64),
+        replacement in eol_heavy_bytes(8),
+        a in any::<usize>(),
+        b in any::<usize>(),
+    ) {
+        let mut buf = CR::normalize(&doc);
+        let (start, end) = edit_range(buf.len(), a, b);
+        let expected = CR::normalize(&[&buf[..start], &replacement, &buf[end..]].concat());
+        CR::normalize_edit(&mut buf, start..end, &replacement);
+        prop_assert_eq!(buf, expected);
+    }
+}
+
+fn edit_range(len: usize, a: usize, b: usize) -> (usize, usize) {
+    let a = a % (len + 1);
+    let b = b % (len + 1);
+    (a.min(b), a.max(b))
+}
@@ -0,0 +1,43 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use eolify::{Normalizer, CRLF, LF};
+
+#[test]
+fn small_input_stays_in_memory() {
+    let normalizer = Normalizer::builder().target(LF);
+    let mut output = normalizer.normalize_large(b"a\r\nb\r".as_ref()).unwrap();
+    assert!(!output.is_spilled());
+    assert_eq!(output.len().unwrap(), 4);
+    let mut out = String::new();
+    output.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "a\nb\n");
+}
+
+#[test]
+fn large_input_spills_to_disk() {
+    let normalizer = Normalizer::builder()
+        .target(CRLF)
+        .buffer_size(64)
+        .spill_threshold(100);
+    let input = "line\n".repeat(1000);
+    let mut output = normalizer.normalize_large(input.as_bytes()).unwrap();
+    assert!(output.is_spilled());
+    assert_eq!(output.len().unwrap(), 6000);
+
+    let mut out = String::new();
+    output.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "line\r\n".repeat(1000));
+
+    output.seek(SeekFrom::Start(6 * 999)).unwrap();
+    out.clear();
+    output.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "line\r\n");
+}
+
+#[test]
+fn empty_input() {
+    let normalizer = Normalizer::builder().target(CRLF).spill_threshold(0);
+    let output = normalizer.normalize_large(b"".as_ref()).unwrap();
+    assert!(!output.is_spilled());
+    assert!(output.is_empty().unwrap());
+}
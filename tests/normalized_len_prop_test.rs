@@ -0,0 +1,68 @@
+use eolify::{helpers::vec_to_uninit_mut, Normalize, NormalizeChunk, CRLF, LF};
+use proptest::{
+    arbitrary::any, collection::vec, prop_assert_eq, prop_oneof, proptest, strategy::Just,
+    test_runner::Config,
+};
+
+fn check<N: NormalizeChunk<State = bool>>(
+    data: &[u8],
+    preceded_by_cr: bool,
+    is_last_chunk: bool,
+) -> (usize, usize, Option<bool>, Option<bool>) {
+    let mut out = Vec::with_capacity(N::max_output_size_for_chunk(
+        data.len(),
+        Some(&preceded_by_cr),
+        is_last_chunk,
+    ));
+    let expected = N::normalize_chunk(
+        data,
+        vec_to_uninit_mut(&mut out),
+        Some(&preceded_by_cr),
+        is_last_chunk,
+    )
+    .unwrap();
+    let counted = N::normalized_len_for_chunk(data, Some(&preceded_by_cr), is_last_chunk);
+    (
+        expected.output_len(),
+        counted.output_len(),
+        expected.state().copied(),
+        counted.state().copied(),
+    )
+}
+
+#[test]
+fn normalized_len_matches_normalize() {
+    let input = b"one\ntwo\r\nthree\rfour\r";
+    assert_eq!(CRLF::normalized_len(input), CRLF::normalize(input).len());
+    assert_eq!(LF::normalized_len(input), LF::normalize(input).len());
+    assert_eq!(CRLF::normalized_len(b""), 0);
+    assert_eq!(LF::normalized_len(b""), 0);
+}
+
+proptest! {
+    #![proptest_config(Config::with_cases(25000))]
+
+    #[test]
+    fn crlf_normalized_len_matches(
+        data in vec(prop_oneof![Just(b'\r'), Just(b'\n'), any::<u8>()], 0..64),
+        preceded_by_cr in any::<bool>(),
+        is_last_chunk in any::<bool>(),
+    ) {
+        let (expected_len, len, expected_state, state) =
+            check::<CRLF>(&data, preceded_by_cr, is_last_chunk);
+        prop_assert_eq!(expected_len, len);
+        prop_assert_eq!(expected_state, state);
+    }
+
+    #[test]
+    fn lf_normalized_len_matches(
+        data in vec(prop_oneof![Just(b'\r'), Just(b'\n'), any::<u8>()], 0..64),
+        preceded_by_cr in any::<bool>(),
+        is_last_chunk in any::<bool>(),
+    ) {
+        let (expected_len, len, expected_state, state) =
+            check::<LF>(&data, preceded_by_cr, is_last_chunk);
+        prop_assert_eq!(expected_len, len);
+        prop_assert_eq!(expected_state, state);
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{Read, Write};
+
+use eolify::{IoExt, Normalize, NormalizeChunk, NulToLf, NUL};
+
+#[test]
+fn line_endings_become_nul() {
+    assert_eq!(NUL::normalize(b"a\r\nb\nc\rd"), b"a\0b\0c\0d");
+    assert_eq!(NUL::normalize(b"already\0split\0"), b"already\0split\0");
+    assert_eq!(NUL::normalize_str("x\r\n"), "x\0");
+}
+
+#[test]
+fn nul_becomes_lf() {
+    assert_eq!(NulToLf::normalize(b"a\0b\0"), b"a\nb\n");
+    // Existing line endings are data inside a record and are left alone.
+    assert_eq!(NulToLf::normalize(b"a\r\nb\0"), b"a\r\nb\n");
+    assert_eq!(NulToLf::normalized_len(b"a\0\0b"), 4);
+}
+
+#[test]
+fn foreign_eols() {
+    assert!(NUL::contains_foreign_eol(b"a\0b\r"));
+    assert!(!NUL::contains_foreign_eol(b"a\0b\0"));
+    assert!(NulToLf::contains_foreign_eol(b"a\0"));
+    assert!(!NulToLf::contains_foreign_eol(b"a\r\nb\n"));
+}
+
+#[test]
+fn reader_handles_crlf_split_across_buffers() {
+    let mut out = Vec::new();
+    NUL::wrap_reader_with_buffer_size(b"one\r\ntwo\r\n".as_ref(), 4)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"one\0two\0");
+}
+
+#[test]
+fn round_trip_through_writer() {
+    let mut writer = NulToLf::wrap_writer_with_buffer_size(Vec::new(), 3);
+    writer.write_all(b"first\0second\0").unwrap();
+    let lines = writer.finish().unwrap();
+    assert_eq!(lines, b"first\nsecond\n");
+    assert_eq!(NUL::normalize(&lines), b"first\0second\0");
+}
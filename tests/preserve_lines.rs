@@ -0,0 +1,67 @@
+use std::io::{Read, Write};
+
+use eolify::{
+    IoExt, Normalize, NormalizeChunk, PreserveLines, Reader, Tidy, Visible, CRLF, LF, NDJSON,
+};
+
+#[test]
+fn line_preserving_formats_pass() {
+    let input = b"one\r\ntwo\nthree\rfour\r\n\r";
+    assert_eq!(PreserveLines::<LF>::normalize(input), LF::normalize(input));
+    assert_eq!(
+        PreserveLines::<CRLF>::normalize(input),
+        CRLF::normalize(input)
+    );
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "1 lines in, 0 lines out"))]
+fn visible_cr_is_not_a_line() {
+    // `Visible` renders a lone `\r` as `^M` on the same line.
+    let _ = PreserveLines::<Visible>::normalize(b"a\rb");
+}
+
+#[test]
+fn counts_across_chunk_boundaries() {
+    let input = "a\r\n\r\rb\n".repeat(50);
+    for buf_size in 1..=5 {
+        let mut out = String::new();
+        Reader::<_, PreserveLines<CRLF>>::new(input.as_bytes(), buf_size)
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "a\r\n\r\n\r\nb\r\n".repeat(50));
+    }
+}
+
+#[test]
+fn empty_chunks_keep_a_held_back_cr() {
+    let mut writer = PreserveLines::<LF>::wrap_writer(Vec::new());
+    writer.write_all(b"a\r").unwrap();
+    writer.flush().unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b"\nb\n").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"a\nb\n");
+
+    let mut output = Vec::new();
+    let mut state = None;
+    for chunk in [&b"\r"[..], b"", b"\r\n", b""] {
+        let status =
+            PreserveLines::<LF>::normalize_chunk_into(chunk, &mut output, state.as_ref(), false)
+                .unwrap();
+        state = status.state().cloned();
+    }
+    PreserveLines::<LF>::normalize_chunk_into(b"", &mut output, state.as_ref(), true).unwrap();
+    assert_eq!(output, b"\n\n");
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "changed the line count"))]
+fn merging_lines_is_detected() {
+    let _ = PreserveLines::<NDJSON>::normalize(b"{}\r\n\r\n{}\n");
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "1 lines in, 2 lines out"))]
+fn adding_lines_is_detected() {
+    let _ = PreserveLines::<Tidy<LF>>::normalize(b"a\nb");
+}
@@ -0,0 +1,52 @@
+use std::io::Read;
+
+use eolify::registry::{self, DynFormat};
+
+#[test]
+fn builtin_formats_are_registered() {
+    let names = registry::format_names();
+    for name in ["cr", "crlf", "lf", "ndjson"] {
+        assert!(names.iter().any(|n| n == name), "{name} missing");
+    }
+}
+
+#[test]
+fn unknown_format_is_none() {
+    assert!(registry::format("no-such-format").is_none());
+}
+
+#[test]
+fn normalize_with_named_format() {
+    let lf = registry::format("lf").unwrap();
+    assert_eq!(lf.normalize(b"a\r\nb\rc"), b"a\nb\nc");
+    let crlf = registry::format("crlf").unwrap();
+    assert_eq!(crlf.normalize(b"a\nb\rc"), b"a\r\nb\r\nc");
+}
+
+#[test]
+fn reader_and_copy_with_named_format() {
+    let crlf = registry::format("crlf").unwrap();
+    let mut out = String::new();
+    crlf.reader(Box::new(b"a\nb".as_ref()))
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "a\r\nb");
+
+    let mut written = Vec::new();
+    let n = crlf.copy(&mut b"x\ny".as_ref(), &mut written).unwrap();
+    assert_eq!(written, b"x\r\ny");
+    assert_eq!(n, 4);
+}
+
+#[test]
+fn third_party_format_can_be_registered() {
+    fn factory() -> Box<dyn DynFormat> {
+        Box::new(eolify::LF)
+    }
+    assert!(registry::register_format("unix", factory).is_none());
+    assert!(registry::register_format("unix", factory).is_some());
+    assert_eq!(
+        registry::format("unix").unwrap().normalize(b"a\r\n"),
+        b"a\n"
+    );
+}
@@ -0,0 +1,93 @@
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+
+use eolify::{
+    sample::{sample_lines, sample_lines_path, MAX_SAMPLE_LINE_LEN},
+    CRLF, LF,
+};
+
+fn numbered_lines(count: usize, eol: &str) -> Vec<u8> {
+    (0..count)
+        .map(|i| format!("line {i:04}{eol}"))
+        .collect::<String>()
+        .into_bytes()
+}
+
+#[test]
+fn evenly_spaced_lines() {
+    let input = numbered_lines(1000, "\r\n");
+    let samples = sample_lines::<LF, _>(Cursor::new(&input), 4).unwrap();
+    let lines: Vec<_> = samples.iter().map(|s| s.normalized.as_slice()).collect();
+    assert_eq!(
+        lines,
+        [
+            b"line 0000\n".as_ref(),
+            b"line 0250\n",
+            b"line 0500\n",
+            b"line 0750\n"
+        ]
+    );
+    assert_eq!(samples[1].offset, 250 * 11);
+    assert!(samples
+        .iter()
+        .all(|s| s.needs_normalization() && !s.truncated));
+}
+
+#[test]
+fn fewer_lines_than_samples() {
+    let samples = sample_lines::<CRLF, _>(Cursor::new(b"a\nb\rc"), 10).unwrap();
+    let raw: Vec<_> = samples.iter().map(|s| s.raw.as_slice()).collect();
+    assert_eq!(raw, [b"a\n".as_ref(), b"b\r", b"c"]);
+    assert_eq!(samples[2].normalized, b"c");
+    assert!(!samples[2].needs_normalization());
+
+    assert!(sample_lines::<LF, _>(Cursor::new(b""), 3)
+        .unwrap()
+        .is_empty());
+    assert!(sample_lines::<LF, _>(Cursor::new(b"a\n"), 0)
+        .unwrap()
+        .is_empty());
+}
+
+/// A reader returning at most one byte per read, so every `\r\n` spans two buffer fills.
+struct OneByte(Cursor<Vec<u8>>);
+
+impl Read for OneByte {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(1);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+impl Seek for OneByte {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.0.seek(pos)
+    }
+}
+
+#[test]
+fn crlf_split_across_reads() {
+    let input = numbered_lines(10, "\r\n");
+    let samples = sample_lines::<LF, _>(BufReader::new(OneByte(Cursor::new(input))), 5).unwrap();
+    assert_eq!(samples.len(), 5);
+    assert_eq!(samples[4].raw, b"line 0008\r\n");
+    assert_eq!(samples[4].normalized, b"line 0008\n");
+}
+
+#[test]
+fn long_lines_are_truncated() {
+    let mut input = vec![b'x'; MAX_SAMPLE_LINE_LEN + 10];
+    input.extend_from_slice(b"\nnext\n");
+    let samples = sample_lines::<LF, _>(Cursor::new(&input), 2).unwrap();
+    assert!(samples[0].truncated);
+    assert_eq!(samples[0].raw.len(), MAX_SAMPLE_LINE_LEN);
+    assert_eq!(samples[1].raw, b"next\n");
+}
+
+#[test]
+fn sample_file() {
+    let path = std::env::temp_dir().join(format!("eolify-sample-{}", std::process::id()));
+    std::fs::write(&path, numbered_lines(100, "\n")).unwrap();
+    let samples = sample_lines_path::<CRLF>(&path, 3).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(samples[2].normalized, b"line 0067\r\n");
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "serde")]
+
+use eolify::{
+    CollapseBlankLines, CollapseCrCrLf, EnsureFinalNewline, LineTerminator, NdjsonState,
+    NormalizeChunk, StripBom, Terminal, Tidy, TidyState, TrimTrailingNewlines, UnicodeSeparators,
+    CRLF, NDJSON,
+};
+
+/// Normalize `first`, persist the state as JSON, then resume with `second` from the restored
+/// state.
+fn resume<N: NormalizeChunk>(first: &[u8], second: &[u8]) -> Vec<u8>
+where
+    N::State: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let mut output = Vec::new();
+    let status = N::normalize_chunk_into(first, &mut output, None, false).unwrap();
+    let checkpoint = serde_json::to_string(&status.state()).unwrap();
+
+    let restored: Option<N::State> = serde_json::from_str(&checkpoint).unwrap();
+    N::normalize_chunk_into(second, &mut output, restored.as_ref(), true).unwrap();
+    output
+}
+
+#[test]
+fn crlf_resumes_after_dangling_cr() {
+    assert_eq!(resume::<CRLF>(b"a\r", b"\nb"), b"a\r\nb");
+}
+
+#[test]
+fn ndjson_resumes_inside_string() {
+    assert_eq!(
+        resume::<NDJSON>(b"{\"a\":\"x\r", b"\ny\"}\r\n\r\n"),
+        b"{\"a\":\"x\r\ny\"}\n"
+    );
+    let _: NdjsonState =
+        serde_json::from_str(&serde_json::to_string(&NdjsonState::default()).unwrap()).unwrap();
+}
+
+#[test]
+fn collapse_resumes_in_cr_run() {
+    assert_eq!(resume::<CollapseCrCrLf<CRLF>>(b"a\r\r", b"\nb"), b"a\r\nb");
+}
+
+#[test]
+fn collapse_blank_lines_resumes_in_run() {
+    assert_eq!(
+        resume::<CollapseBlankLines<CRLF, 2>>(b"a\n\n\r", b"\n\nb"),
+        b"a\r\n\r\nb"
+    );
+}
+
+#[test]
+fn unicode_separators_resume_inside_separator() {
+    assert_eq!(
+        resume::<UnicodeSeparators<CRLF>>(b"a\xe2\x80", b"\xa9b"),
+        b"a\r\nb"
+    );
+}
+
+#[test]
+fn strip_bom_resumes_in_bom() {
+    assert_eq!(resume::<StripBom<CRLF>>(b"\xEF\xBB", b"\xBFa\n"), b"a\r\n");
+}
+
+#[test]
+fn ensure_final_newline_resumes_mid_line() {
+    assert_eq!(
+        resume::<EnsureFinalNewline<CRLF>>(b"a\n", b"b"),
+        b"a\r\nb\r\n"
+    );
+}
+
+#[test]
+fn trim_trailing_newlines_resumes_with_held_line_endings() {
+    assert_eq!(
+        resume::<TrimTrailingNewlines<CRLF>>(b"a\r\n\r", b"\nb\n"),
+        b"a\r\n\r\nb"
+    );
+}
+
+#[test]
+fn terminal_resumes_in_escape_string() {
+    assert_eq!(
+        resume::<Terminal<CRLF>>(b"\x1b]0;a\r", b"\nb\x07\n"),
+        b"\x1b]0;a\r\nb\x07\r\n"
+    );
+}
+
+#[test]
+fn tidy_resumes_in_bom() {
+    assert_eq!(resume::<Tidy<CRLF>>(b"\xEF\xBB", b"\xBFa\n"), b"a\r\n");
+    let state: TidyState<bool> =
+        serde_json::from_str(&serde_json::to_string(&TidyState::<bool>::default()).unwrap())
+            .unwrap();
+    assert_eq!(state, TidyState::default());
+}
+
+#[test]
+fn line_terminator_round_trips() {
+    let json = serde_json::to_string(&LineTerminator::CrLf).unwrap();
+    assert_eq!(
+        serde_json::from_str::<LineTerminator>(&json).unwrap(),
+        LineTerminator::CrLf
+    );
+}
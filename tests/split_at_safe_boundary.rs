@@ -0,0 +1,44 @@
+use eolify::{helpers::split_at_safe_boundary, Normalize, CRLF, LF};
+use proptest::prelude::*;
+
+#[test]
+fn split_between_cr_and_lf_is_moved() {
+    assert_eq!(
+        split_at_safe_boundary(b"a\r\nb", 2),
+        (b"a\r\n".as_ref(), b"b".as_ref())
+    );
+}
+
+#[test]
+fn other_splits_are_kept() {
+    assert_eq!(
+        split_at_safe_boundary(b"a\r\nb", 1),
+        (b"a".as_ref(), b"\r\nb".as_ref())
+    );
+    assert_eq!(
+        split_at_safe_boundary(b"a\r\rb", 2),
+        (b"a\r".as_ref(), b"\rb".as_ref())
+    );
+    assert_eq!(split_at_safe_boundary(b"", 0), (b"".as_ref(), b"".as_ref()));
+}
+
+#[test]
+fn split_point_is_clamped() {
+    assert_eq!(
+        split_at_safe_boundary(b"ab", 10),
+        (b"ab".as_ref(), b"".as_ref())
+    );
+}
+
+proptest! {
+    #[test]
+    fn halves_normalize_independently(
+        input in proptest::collection::vec(
+            prop_oneof![Just(b'\r'), Just(b'\n'), Just(b'a')], 0..64),
+        pos in 0usize..70,
+    ) {
+        let (head, tail) = split_at_safe_boundary(&input, pos);
+        prop_assert_eq!([CRLF::normalize(head), CRLF::normalize(tail)].concat(), CRLF::normalize(&input));
+        prop_assert_eq!([LF::normalize(head), LF::normalize(tail)].concat(), LF::normalize(&input));
+    }
+}
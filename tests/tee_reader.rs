@@ -0,0 +1,71 @@
+use std::io::Read;
+
+use eolify::{HashWriter, Normalizer, TeeExt, TeeReader, TeeWriter, CRLF, LF};
+
+#[test]
+fn archives_original_while_normalizing() {
+    let input = "one\r\ntwo\rthree\n".repeat(1000);
+    let mut reader = input.as_bytes().normalize_newlines_tee(LF, Vec::new());
+    let mut normalized = String::new();
+    reader.read_to_string(&mut normalized).unwrap();
+    assert_eq!(normalized, "one\ntwo\nthree\n".repeat(1000));
+    assert_eq!(reader.into_inner().sink(), input.as_bytes());
+}
+
+#[test]
+fn passes_original_through_and_normalizes_to_sink() {
+    let mut reader = b"a\nb\r".as_ref().tee_normalized(CRLF, Vec::new());
+    let mut original = Vec::new();
+    reader.read_to_end(&mut original).unwrap();
+    let (_, writer) = reader.into_inner();
+    assert_eq!(original, b"a\nb\r");
+    assert_eq!(writer.finish().unwrap(), b"a\r\nb\r\n");
+}
+
+#[test]
+fn composes_with_builder() {
+    let normalizer = Normalizer::builder().target(CRLF).buffer_size(2);
+    let mut reader = normalizer.reader(TeeReader::new(b"x\ny\n".as_ref(), Vec::new()));
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, b"x\r\ny\r\n");
+    assert_eq!(reader.into_inner().into_inner().1, b"x\ny\n");
+}
+
+fn hash_of(bytes: &[u8]) -> u64 {
+    use std::hash::Hasher;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[test]
+fn hashes_original_and_normalized_in_one_pass() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let input = b"x\r\ny\rz";
+    let reader = input
+        .as_ref()
+        .normalize_newlines_tee(LF, HashWriter::new(DefaultHasher::new()));
+    let mut reader = TeeReader::new(reader, HashWriter::new(DefaultHasher::new()));
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+
+    let (inner, normalized_hash) = reader.into_inner();
+    let original_hash = inner.into_inner().into_inner().1;
+    assert_eq!(original_hash.finish(), hash_of(input));
+    assert_eq!(normalized_hash.finish(), hash_of(b"x\ny\nz"));
+}
+
+#[test]
+fn tee_writer_hashes_normalized_output() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::io::Write;
+
+    let sink = TeeWriter::new(Vec::new(), HashWriter::new(DefaultHasher::new()));
+    let mut writer = Normalizer::builder().target(CRLF).writer(sink);
+    writer.write_all(b"a\nb").unwrap();
+    let (out, hash) = writer.finish().unwrap().into_inner();
+    assert_eq!(out, b"a\r\nb");
+    assert_eq!(hash.finish(), hash_of(b"a\r\nb"));
+}
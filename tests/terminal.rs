@@ -0,0 +1,93 @@
+use std::io::{Read, Write};
+
+use eolify::{FlushPolicy, IoExt, Normalize, Normalizer, Reader, Terminal, CRLF, LF};
+use proptest::{collection::vec, prop_assert_eq, prop_oneof, proptest, sample::select};
+
+#[test]
+fn keeps_redraws_and_collapses_pty_line_endings() {
+    assert_eq!(
+        Terminal::<LF>::normalize(b"1/3\r2/3\r3/3\r\r\ndone\n"),
+        b"1/3\r2/3\r3/3\ndone\n"
+    );
+    assert_eq!(
+        Terminal::<CRLF>::normalize(b"a\nb\r\r\nc\r"),
+        b"a\r\nb\r\nc\r"
+    );
+}
+
+#[test]
+fn control_sequences_are_left_alone() {
+    assert_eq!(
+        Terminal::<LF>::normalize(b"\x1b[32mok\x1b[0m\r\r\n\r\x1b[2Kredraw\r\n"),
+        b"\x1b[32mok\x1b[0m\n\r\x1b[2Kredraw\n"
+    );
+}
+
+#[test]
+fn escape_strings_are_copied_unchanged() {
+    // OSC terminated by BEL, then DCS terminated by ST.
+    assert_eq!(
+        Terminal::<CRLF>::normalize(b"\x1b]2;a\nb\x07x\n\x1bPq\r\n\x1b\\y\n"),
+        b"\x1b]2;a\nb\x07x\r\n\x1bPq\r\n\x1b\\y\r\n"
+    );
+    // An `ESC` that doesn't start the terminator stays part of the string.
+    assert_eq!(
+        Terminal::<LF>::normalize(b"\x1b_\x1b[\r\n\x1b\\\r\n"),
+        b"\x1b_\x1b[\r\n\x1b\\\n"
+    );
+}
+
+#[test]
+fn unterminated_escape_strings_end_after_the_window() {
+    assert_eq!(
+        Terminal::<LF, 4>::normalize(b"\x1b]ab\r\ncd\r\n"),
+        b"\x1b]ab\r\ncd\n"
+    );
+    assert_eq!(
+        Terminal::<LF, 0>::normalize(b"\x1b]\r\n\x07"),
+        b"\x1b]\n\x07"
+    );
+}
+
+#[test]
+fn builder_and_flush_at_boundary() {
+    let mut writer = Normalizer::builder()
+        .target(LF)
+        .terminal()
+        .flush_policy(FlushPolicy::interactive())
+        .writer(Vec::new());
+    writer.write_all(b"50%\r").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"50%\r");
+    writer.write_all(b"\x1b]0;title\r").unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b"\n\x07done\r\r\n").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"50%\r\x1b]0;title\r\n\x07done\n");
+}
+
+proptest! {
+    #[test]
+    fn chunk_boundaries_do_not_matter(
+        pieces in vec(
+            prop_oneof![
+                select(vec![&b"\r"[..], b"\n", b"\x1b", b"]", b"P", b"[", b"\x07", b"\\"]),
+                select(vec![&b"a"[..], b"\xC3\xA9", b"\x1b]0;", b"\x1b\\"]),
+            ],
+            0..48,
+        ),
+        buf_size in 1usize..8,
+    ) {
+        let input = pieces.concat();
+        let expected = Terminal::<CRLF, 8>::normalize(&input);
+
+        let mut out = Vec::new();
+        Reader::<_, Terminal<CRLF, 8>>::new(input.as_slice(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        prop_assert_eq!(&out, &expected);
+
+        let mut writer = Terminal::<CRLF, 8>::wrap_writer_with_buffer_size(Vec::new(), buf_size);
+        writer.write_all(&input).unwrap();
+        prop_assert_eq!(writer.finish().unwrap(), expected);
+    }
+}
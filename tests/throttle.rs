@@ -0,0 +1,81 @@
+use std::{
+    io::{Read, Write},
+    time::{Duration, Instant},
+};
+
+use eolify::{IoExt, Normalizer, LF};
+
+const INPUT: &[u8] = &[b'x'; 1000];
+
+#[test]
+fn throttled_reader() {
+    let start = Instant::now();
+    let mut out = Vec::new();
+    LF::wrap_reader_with_buffer_size(INPUT, 100)
+        .with_throttle(10_000)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, INPUT);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[test]
+fn throttled_writer() {
+    let start = Instant::now();
+    let mut writer = LF::wrap_writer_with_buffer_size(Vec::new(), 100).with_throttle(10_000);
+    for chunk in INPUT.chunks(50) {
+        writer.write_all(chunk).unwrap();
+    }
+    assert_eq!(writer.finish().unwrap(), INPUT);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[test]
+fn builder_throttle() {
+    let normalizer = Normalizer::builder()
+        .target(LF)
+        .buffer_size(100)
+        .throttle(20_000);
+    let start = Instant::now();
+    let mut out = Vec::new();
+    normalizer.copy(&mut &INPUT[..], &mut out).unwrap();
+    assert_eq!(out, INPUT);
+    assert!(start.elapsed() >= Duration::from_millis(40));
+}
+
+#[test]
+fn unthrottled_is_fast() {
+    let start = Instant::now();
+    let mut out = Vec::new();
+    LF::wrap_reader_with_buffer_size(INPUT, 100)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_millis(40));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn throttled_tokio_reader_and_writer() {
+    use eolify::TokioExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let start = Instant::now();
+    let mut out = Vec::new();
+    LF::wrap_async_reader_with_buffer_size(INPUT, 100)
+        .with_throttle(10_000)
+        .read_to_end(&mut out)
+        .await
+        .unwrap();
+    assert_eq!(out, INPUT);
+    assert!(start.elapsed() >= Duration::from_millis(90));
+
+    let start = Instant::now();
+    let mut writer = std::pin::pin!(
+        LF::wrap_async_writer_with_buffer_size(Vec::new(), 100).with_throttle(10_000)
+    );
+    for chunk in INPUT.chunks(50) {
+        writer.write_all(chunk).await.unwrap();
+    }
+    writer.shutdown().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
@@ -0,0 +1,66 @@
+use std::io::Read;
+
+use eolify::{Normalize, Normalizer, Reader, Tidy, CRLF, LF};
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+fn read_with_buffer_size(input: &[u8], buf_size: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    Reader::<_, Tidy<LF>>::new(input, buf_size)
+        .read_to_end(&mut out)
+        .unwrap();
+    out
+}
+
+#[test]
+fn strips_bom_and_adds_final_newline() {
+    assert_eq!(Tidy::<LF>::normalize(b"\xEF\xBB\xBFa\r\nb"), b"a\nb\n");
+    assert_eq!(Tidy::<CRLF>::normalize(b"a\nb\n"), b"a\r\nb\r\n");
+    assert_eq!(Tidy::<LF>::normalize(b"a\r"), b"a\n");
+    assert_eq!(Tidy::<LF>::normalize(b"\xFF\xFEa"), b"a\n");
+}
+
+#[test]
+fn empty_content_stays_empty() {
+    assert!(Tidy::<LF>::normalize(b"").is_empty());
+    assert!(Tidy::<LF>::normalize(BOM).is_empty());
+}
+
+#[test]
+fn partial_bom_is_content() {
+    assert_eq!(Tidy::<LF>::normalize(b"\xEF\xBB"), b"\xEF\xBB\n");
+    assert_eq!(Tidy::<LF>::normalize(b"\xEF\xBBx\r\n"), b"\xEF\xBBx\n");
+    // Only a BOM at the very start is stripped.
+    assert_eq!(Tidy::<LF>::normalize(b"x\xEF\xBB\xBF"), b"x\xEF\xBB\xBF\n");
+}
+
+#[test]
+fn chunk_boundaries_do_not_matter() {
+    let inputs: [&[u8]; 5] = [
+        b"\xEF\xBB\xBFone\r\ntwo\rthree",
+        b"\xEF\xBBone\r\n",
+        b"\xEF",
+        b"plain\n\n",
+        b"\r\n\r\n",
+    ];
+    for input in inputs {
+        let expected = Tidy::<LF>::normalize(input);
+        for buf_size in 1..=4 {
+            assert_eq!(
+                read_with_buffer_size(input, buf_size),
+                expected,
+                "{input:?} with buffer size {buf_size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn builder_tidy() {
+    let normalizer = Normalizer::builder().target(CRLF).tidy().buffer_size(2);
+    let mut out = Vec::new();
+    normalizer
+        .copy(&mut b"\xEF\xBB\xBFa\nb".as_ref(), &mut out)
+        .unwrap();
+    assert_eq!(out, b"a\r\nb\r\n");
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "tokio")]
+
+use eolify::{TokioAsyncStreamExt, CRLF, LF};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn halves_normalize_independently() {
+    let (local, mut remote) = tokio::io::duplex(64);
+    let (mut reader, mut writer) = local.split_normalized(LF, CRLF);
+
+    writer.write_all(b"hello\nworld\n").await.unwrap();
+    writer.shutdown().await.unwrap();
+    let mut sent = Vec::new();
+    remote.read_to_end(&mut sent).await.unwrap();
+    assert_eq!(sent, b"hello\r\nworld\r\n");
+
+    remote.write_all(b"one\r\ntwo\r").await.unwrap();
+    drop(remote);
+    let mut received = Vec::new();
+    reader.read_to_end(&mut received).await.unwrap();
+    assert_eq!(received, b"one\ntwo\n");
+}
@@ -0,0 +1,70 @@
+use eolify::{
+    trace::{replay, ChunkTrace, TraceError},
+    Normalize, CRLF, LF,
+};
+
+#[test]
+fn text_form_round_trips() {
+    let trace: ChunkTrace = "4, 0,3,1!".parse().unwrap();
+    assert_eq!(trace.calls().len(), 4);
+    assert_eq!(trace.input_len(), 8);
+    assert!(trace.calls()[3].is_last_chunk);
+    assert_eq!(trace.to_string(), "4,0,3,1!");
+    assert_eq!("".parse::<ChunkTrace>().unwrap(), ChunkTrace::new());
+    assert_eq!(
+        "4,x!".parse::<ChunkTrace>(),
+        Err(TraceError::InvalidCall("x!".to_string()))
+    );
+}
+
+#[test]
+fn replay_matches_normalize() {
+    let input = b"a\r\r\nb\r";
+    let trace: ChunkTrace = "2,1,1,2,0!".parse().unwrap();
+    assert_eq!(replay::<LF>(&trace, input).unwrap(), LF::normalize(input));
+}
+
+#[test]
+fn replay_without_last_chunk_keeps_dangling_state() {
+    let trace: ChunkTrace = "2".parse().unwrap();
+    assert_eq!(replay::<CRLF>(&trace, b"a\r").unwrap(), b"a\r");
+}
+
+#[test]
+fn replay_rejects_length_mismatch() {
+    let trace: ChunkTrace = "2,2!".parse().unwrap();
+    assert_eq!(
+        replay::<LF>(&trace, b"abc"),
+        Err(TraceError::LengthMismatch {
+            trace_len: 4,
+            input_len: 3
+        })
+    );
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn wrappers_record_replayable_traces() {
+    use std::io::{Read, Write};
+
+    let input = "one\r\ntwo\rthree\n".repeat(3);
+
+    let mut reader = eolify::Reader::<_, CRLF>::new(input.as_bytes(), 5).with_trace();
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    let trace = reader.trace().unwrap().clone();
+    assert_eq!(trace.input_len(), input.len());
+    assert_eq!(replay::<CRLF>(&trace, input.as_bytes()).unwrap(), out);
+
+    let mut writer = eolify::Writer::<_, LF>::new(Vec::new(), 4).with_trace();
+    writer.write_all(input.as_bytes()).unwrap();
+    writer.flush().unwrap();
+    let trace = writer.trace().unwrap().clone();
+    let out = writer.finish().unwrap();
+    let mut trace_with_end = trace.clone();
+    trace_with_end.push(0, true);
+    assert_eq!(
+        replay::<LF>(&trace_with_end, input.as_bytes()).unwrap(),
+        out
+    );
+}
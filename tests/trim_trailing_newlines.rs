@@ -0,0 +1,167 @@
+use std::io::{Read, Write};
+
+use eolify::{FlushPolicy, IoExt, Normalize, PreservesUtf8, TrimTrailingNewlines, CRLF, LF};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+#[test]
+fn removes_trailing_line_endings() {
+    assert_eq!(
+        TrimTrailingNewlines::<LF>::normalize(b"a\nb\r\n\r\r\n"),
+        b"a\nb"
+    );
+    assert_eq!(
+        TrimTrailingNewlines::<CRLF>::normalize_utf8("\n\na"),
+        "\r\n\r\na"
+    );
+    assert_eq!(TrimTrailingNewlines::<CRLF>::normalize(b"\r\n\n"), b"");
+}
+
+#[test]
+fn keeps_at_most_one() {
+    assert_eq!(
+        TrimTrailingNewlines::<CRLF, 1>::normalize(b"a\n\n"),
+        b"a\r\n"
+    );
+    assert_eq!(TrimTrailingNewlines::<CRLF, 1>::normalize(b"a\r"), b"a\r\n");
+    assert_eq!(TrimTrailingNewlines::<CRLF, 1>::normalize(b"a"), b"a");
+}
+
+#[test]
+fn writer_holds_back_across_chunks() {
+    let mut writer = TrimTrailingNewlines::<CRLF>::wrap_writer_with_buffer_size(Vec::new(), 1);
+    writer.write_all(b"one\r").unwrap();
+    writer.write_all(b"\n\rtwo\r").unwrap();
+    writer.write_all(b"\n").unwrap();
+    assert_eq!(writer.finish().unwrap(), b"one\r\n\r\ntwo");
+}
+
+#[test]
+fn interactive_flush_is_not_the_end() {
+    let mut writer = TrimTrailingNewlines::<LF>::wrap_writer(Vec::new())
+        .with_flush_policy(FlushPolicy::interactive());
+    writer.write_all(b"a\n").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref(), b"a");
+    writer.write_all(b"b\r").unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b"\nc\n").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.finish().unwrap(), b"a\nb\n\nc");
+}
+
+#[test]
+fn long_runs_before_text_are_written_in_full() {
+    let mut input = b"a".to_vec();
+    input.extend(std::iter::repeat_n(b'\n', 10_000));
+    input.push(b'b');
+
+    let mut out = Vec::new();
+    TrimTrailingNewlines::<CRLF>::wrap_reader_with_buffer_size(input.as_slice(), 4)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, CRLF::normalize(&input));
+}
+
+#[test]
+fn normalized_len_of_long_run_before_text() {
+    let mut input = vec![b'\n'; 5000];
+    input.push(b'a');
+    assert_eq!(
+        TrimTrailingNewlines::<LF>::normalized_len(&input),
+        input.len()
+    );
+    assert_eq!(TrimTrailingNewlines::<CRLF>::normalized_len(&input), 10_001);
+}
+
+#[test]
+fn buffers_do_not_grow_for_long_runs() {
+    let mut input = vec![b'\n'; 1 << 20];
+    input.extend_from_slice(b"a\n\n");
+    let expected = [b"\r\n".repeat(1 << 20), b"a".to_vec()].concat();
+
+    let mut writer = TrimTrailingNewlines::<CRLF>::wrap_writer_with_buffer_size(Vec::new(), 64);
+    let capacity = writer.buffer_capacity();
+    writer.write_all(&input).unwrap();
+    assert_eq!(writer.buffer_capacity(), capacity);
+    assert_eq!(writer.finish().unwrap(), expected);
+
+    let mut reader =
+        TrimTrailingNewlines::<CRLF>::wrap_reader_with_buffer_size(input.as_slice(), 64);
+    let capacity = reader.buffer_capacity();
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(reader.buffer_capacity(), capacity);
+    assert_eq!(out, expected);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn async_buffers_do_not_grow_for_long_runs() {
+    use eolify::TokioExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut input = vec![b'\r'; 100_000];
+    input.push(b'a');
+    let expected = [vec![b'\n'; 100_000], b"a".to_vec()].concat();
+
+    let mut writer = TrimTrailingNewlines::<LF>::wrap_async_writer_with_buffer_size(Vec::new(), 16);
+    let capacity = writer.buffer_capacity();
+    writer.write_all(&input).await.unwrap();
+    assert_eq!(writer.buffer_capacity(), capacity);
+    assert_eq!(writer.finish().await.unwrap(), expected);
+
+    let mut reader =
+        TrimTrailingNewlines::<LF>::wrap_async_reader_with_buffer_size(input.as_slice(), 16);
+    let capacity = reader.buffer_capacity();
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).await.unwrap();
+    assert_eq!(reader.buffer_capacity(), capacity);
+    assert_eq!(out, expected);
+}
+
+fn reference(data: &[u8], keep: usize) -> Vec<u8> {
+    let mut out = LF::normalize(data);
+    let text_len = out.iter().rposition(|&b| b != b'\n').map_or(0, |i| i + 1);
+    let kept = (out.len() - text_len).min(keep);
+    out.truncate(text_len + kept);
+    out
+}
+
+proptest! {
+    #[test]
+    fn same_as_trimming_after_normalizing(data in vec(any::<u8>(), 0..256)) {
+        prop_assert_eq!(TrimTrailingNewlines::<LF>::normalize(&data), reference(&data, 0));
+        prop_assert_eq!(TrimTrailingNewlines::<LF, 1>::normalize(&data), reference(&data, 1));
+    }
+
+    #[test]
+    fn chunking_does_not_matter(data in vec(any::<u8>(), 0..256), buf_size in 1usize..8) {
+        let mut writer =
+            TrimTrailingNewlines::<LF, 1>::wrap_writer_with_buffer_size(Vec::new(), buf_size);
+        writer.write_all(&data).unwrap();
+        prop_assert_eq!(writer.finish().unwrap(), reference(&data, 1));
+    }
+
+    #[test]
+    fn long_runs_with_small_buffers(
+        runs in vec((0usize..400, any::<u8>()), 0..6),
+        buf_size in 1usize..8,
+    ) {
+        let mut data = Vec::new();
+        for (len, b) in runs {
+            data.extend(std::iter::repeat_n(b'\n', len));
+            data.push(b);
+        }
+
+        let mut writer =
+            TrimTrailingNewlines::<LF, 1>::wrap_writer_with_buffer_size(Vec::new(), buf_size);
+        writer.write_all(&data).unwrap();
+        prop_assert_eq!(writer.finish().unwrap(), reference(&data, 1));
+
+        let mut out = Vec::new();
+        TrimTrailingNewlines::<LF, 1>::wrap_reader_with_buffer_size(data.as_slice(), buf_size)
+            .read_to_end(&mut out)
+            .unwrap();
+        prop_assert_eq!(out, reference(&data, 1));
+    }
+}
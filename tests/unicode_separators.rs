@@ -0,0 +1,110 @@
+use std::io::Read;
+
+use eolify::{IoExt, Normalize, NormalizeChunk, PreservesUtf8, UnicodeSeparators, CR, CRLF, LF};
+use proptest::{
+    collection::vec, prop_assert_eq, prop_oneof, proptest, strategy::Just, strategy::Strategy,
+};
+
+#[test]
+fn separators_become_line_endings() {
+    assert_eq!(
+        UnicodeSeparators::<LF>::normalize_str("a\u{2028}b\u{2029}c"),
+        "a\nb\nc"
+    );
+    assert_eq!(
+        UnicodeSeparators::<CRLF>::normalize_str("a\u{2028}b\nc"),
+        "a\r\nb\r\nc"
+    );
+    assert_eq!(
+        UnicodeSeparators::<CR>::normalize_utf8("a\u{2029}\r\n"),
+        "a\r\r"
+    );
+}
+
+#[test]
+fn separators_next_to_line_endings_are_not_merged() {
+    assert_eq!(
+        UnicodeSeparators::<LF>::normalize_str("\r\u{2028}\n"),
+        "\n\n\n"
+    );
+    assert_eq!(
+        UnicodeSeparators::<LF>::normalize_str("\u{2028}\u{2029}"),
+        "\n\n"
+    );
+}
+
+#[test]
+fn other_characters_pass_through() {
+    // Same lead bytes as the separators, but different characters.
+    let text = "\u{2026}\u{2030}\u{20ac}";
+    assert_eq!(UnicodeSeparators::<LF>::normalize_str(text), text);
+}
+
+#[test]
+fn incomplete_separator_at_end_is_kept() {
+    assert_eq!(
+        UnicodeSeparators::<LF>::normalize(b"a\xe2\x80"),
+        b"a\xe2\x80"
+    );
+}
+
+#[test]
+fn separator_split_across_single_byte_reads() {
+    let mut out = String::new();
+    UnicodeSeparators::<CRLF>::wrap_reader_with_buffer_size("x\u{2028}y\u{2029}".as_bytes(), 1)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "x\r\ny\r\n");
+}
+
+#[test]
+fn worst_case_size_is_not_exceeded() {
+    let input = "\r".repeat(10);
+    let mut out = Vec::new();
+    let required = UnicodeSeparators::<CRLF>::max_output_size_for_chunk(10, None, true);
+    UnicodeSeparators::<CRLF>::normalize_chunk_into(input.as_bytes(), &mut out, None, true)
+        .unwrap();
+    assert!(out.len() <= required);
+}
+
+fn text() -> impl Strategy<Value = String> {
+    vec(
+        prop_oneof![
+            Just("\u{2028}"),
+            Just("\u{2029}"),
+            Just("\u{2026}"),
+            Just("\r"),
+            Just("\n"),
+            Just("a"),
+        ],
+        0..64,
+    )
+    .prop_map(|pieces| pieces.concat())
+}
+
+proptest! {
+    #[test]
+    fn chunk_split_matches_whole(text in text(), split in 0usize..256) {
+        let data = text.as_bytes();
+        let split = split.min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status = UnicodeSeparators::<CRLF>::normalize_chunk_into(a, &mut out, None, false)
+            .unwrap();
+        UnicodeSeparators::<CRLF>::normalize_chunk_into(b, &mut out, status.state(), true)
+            .unwrap();
+
+        prop_assert_eq!(&out, &UnicodeSeparators::<CRLF>::normalize(data));
+    }
+
+    #[test]
+    fn same_as_replacing_separators_first(text in text()) {
+        // A separator counts as one line ending even next to `\r` or `\n`, just like `\r\n`.
+        let replaced = text.replace(['\u{2028}', '\u{2029}'], "\r\n");
+        prop_assert_eq!(
+            UnicodeSeparators::<LF>::normalize_str(&text),
+            LF::normalize_str(&replaced)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::io::Read;
+
+use eolify::{
+    Breaks, Custom, Delimiter, IoExt, Normalize, NormalizeChunk, Universal, CR, CRLF, LF,
+};
+use proptest::{arbitrary::any, collection::vec, prop_assert_eq, proptest};
+
+type Ascii<D> = Universal<{ Breaks::ASCII }, D>;
+type SplitLines = Universal<{ Breaks::UNICODE }, LF>;
+
+#[test]
+fn ascii_breaks_match_the_fixed_formats() {
+    let input = b"a\r\nb\nc\rd\r\r\n\n\r";
+    assert_eq!(Ascii::<LF>::normalize(input), LF::normalize(input));
+    assert_eq!(Ascii::<CRLF>::normalize(input), CRLF::normalize(input));
+    assert_eq!(Ascii::<CR>::normalize(input), CR::normalize(input));
+}
+
+#[test]
+fn unicode_breaks() {
+    let out = SplitLines::normalize_str("a\u{85}b\u{2028}c\u{2029}d\x0be\x0cf\r\ng");
+    assert_eq!(out, "a\nb\nc\nd\ne\nf\ng");
+    // Latin-1 NEL is not part of the set, and neither are other characters with a 0x85 byte.
+    assert_eq!(SplitLines::normalize(b"\x85"), b"\x85");
+    assert_eq!(SplitLines::normalize_str("\u{2026}"), "\u{2026}");
+}
+
+#[test]
+fn unselected_sequences_pass_through() {
+    type LfOnly = Universal<{ Breaks::LF }, CRLF>;
+    assert_eq!(LfOnly::normalize(b"a\rb\nc\r\n"), b"a\rb\r\nc\r\r\n");
+
+    type CrOnly = Universal<{ Breaks::CR }, LF>;
+    assert_eq!(CrOnly::normalize(b"a\r\nb\n"), b"a\n\nb\n");
+
+    type Nothing = Universal<0, LF>;
+    assert_eq!(Nothing::normalize(b"a\r\n\x0c"), b"a\r\n\x0c");
+}
+
+#[test]
+fn latin1_and_utf8_nel_together() {
+    type Nels = Universal<{ Breaks::NEL | Breaks::NEL_LATIN1 }, LF>;
+    assert_eq!(Nels::normalize(b"a\xc2\x85b\x85c"), b"a\nb\nc");
+}
+
+#[test]
+fn custom_delimiter_target() {
+    struct Pilcrow;
+
+    impl Delimiter for Pilcrow {
+        const BYTES: &'static [u8] = "\u{b6}".as_bytes();
+    }
+
+    type Paragraphs = Universal<{ Breaks::ASCII | Breaks::PS }, Pilcrow>;
+    assert_eq!(
+        Paragraphs::normalize_str("a\u{2029}b\r\n"),
+        "a\u{b6}b\u{b6}"
+    );
+    assert_eq!(
+        Universal::<{ Breaks::ASCII }, Pilcrow>::normalize(b"x\ny"),
+        Custom::<Pilcrow>::normalize(b"x\ny")
+    );
+}
+
+#[test]
+fn breaks_split_across_single_byte_reads() {
+    let mut out = String::new();
+    SplitLines::wrap_reader_with_buffer_size("a\r\nb\u{2028}c\u{85}d\r".as_bytes(), 1)
+        .read_to_string(&mut out)
+        .unwrap();
+    assert_eq!(out, "a\nb\nc\nd\n");
+}
+
+#[test]
+fn boundary_resolves_only_a_held_back_cr() {
+    let mut output = Vec::with_capacity(32);
+    let status =
+        SplitLines::normalize_chunk_at_boundary(b"a\r", output.spare_capacity_mut(), None).unwrap();
+    // SAFETY: normalize_chunk_at_boundary initialized the first `output_len` bytes.
+    unsafe { output.set_len(status.output_len()) };
+    assert_eq!(output, b"a\n");
+
+    let mut out = Vec::new();
+    let status = SplitLines::normalize_chunk_into(b"\nb", &mut out, status.state(), false).unwrap();
+    out.reserve(32);
+    let status = SplitLines::normalize_chunk_at_boundary(
+        b"\xe2\x80",
+        out.spare_capacity_mut(),
+        status.state(),
+    )
+    .unwrap();
+    assert_eq!(status.output_len(), 0);
+    SplitLines::normalize_chunk_into(b"\xa8", &mut out, status.state(), true).unwrap();
+    assert_eq!(out, b"\nb\n");
+}
+
+#[test]
+fn worst_case_size() {
+    type Wide = Universal<{ Breaks::UNICODE }, CRLF>;
+    assert_eq!(Wide::max_output_size(10, true), 24);
+    let input = [0x0c; 10];
+    let mut out = Vec::new();
+    Wide::normalize_chunk_into(&input, &mut out, None, true).unwrap();
+    assert_eq!(out.len(), 20);
+}
+
+proptest! {
+    #[test]
+    fn chunk_split_matches_whole(data in vec(any::<u8>(), 0..256), split in 0usize..256) {
+        let split = split.min(data.len());
+        let (a, b) = data.split_at(split);
+
+        let mut out = Vec::new();
+        let status = SplitLines::normalize_chunk_into(a, &mut out, None, false).unwrap();
+        SplitLines::normalize_chunk_into(b, &mut out, status.state(), true).unwrap();
+
+        prop_assert_eq!(&out, &SplitLines::normalize(&data));
+    }
+
+    #[test]
+    fn ascii_matches_lf(data in vec(any::<u8>(), 0..256)) {
+        prop_assert_eq!(Ascii::<LF>::normalize(&data), LF::normalize(&data));
+        prop_assert_eq!(Ascii::<CRLF>::normalize(&data), CRLF::normalize(&data));
+    }
+}
+
+#[test]
+fn compares_like_the_other_formats() {
+    assert_eq!(SplitLines::default(), SplitLines::default());
+    assert_eq!(Ascii::<CRLF>::default(), Ascii::<CRLF>::default());
+}
@@ -0,0 +1,41 @@
+use std::io::Read;
+
+use eolify::{Normalize, ReadExt, Visible, VisibleSymbols};
+
+#[test]
+fn renders_all_endings() {
+    assert_eq!(
+        Visible::normalize(b"lf\ncrlf\r\ncr\rend"),
+        b"lf$\ncrlf^M$\ncr^Mend"
+    );
+    assert_eq!(
+        VisibleSymbols::normalize_str("lf\ncrlf\r\ncr\rend"),
+        "lf␊\ncrlf␍␊\ncr␍end"
+    );
+}
+
+#[test]
+fn empty_input() {
+    assert!(Visible::normalize(b"").is_empty());
+    assert_eq!(Visible::normalized_len(b"\r\n\r\n"), 8);
+}
+
+#[test]
+fn streams_in_tiny_chunks() {
+    let input = "a\r\n\rb\n\n".repeat(100);
+    for buf_size in [1, 2, 3, 64] {
+        let mut out = String::new();
+        eolify::Reader::<_, VisibleSymbols>::new(input.as_bytes(), buf_size)
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "a␍␊\n␍b␊\n␊\n".repeat(100), "buf_size {buf_size}");
+    }
+
+    let mut out = Vec::new();
+    b"x\r\n"
+        .as_ref()
+        .normalize_newlines(Visible)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, b"x^M$\n");
+}